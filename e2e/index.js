@@ -1,4 +1,5 @@
 import { ApiPromise, Keyring, WsProvider } from '@polkadot/api';
+import { u8aConcat } from '@polkadot/util';
 import { mnemonicGenerate } from '@polkadot/util-crypto';
 import Spinnies from 'spinnies';
 
@@ -121,9 +122,16 @@ import { submit } from './utils.js';
     chain.tx.ad.create(
       2_000_000_000_000_000_000n,
       ['Polkadot'],
-      'ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG',
-      0,
-      500000
+      {
+        landing: 'https://parami.io',
+        cid: 'QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG',
+        contentHash: '0x' + '00'.repeat(32),
+        mime: 'image/png',
+      },
+      { source: 'Caller', rates: [0] },
+      500000,
+      null,
+      null
     ),
     alice
   );
@@ -148,9 +156,22 @@ import { submit } from './utils.js';
   spinnies.add('pay', { text: 'Paying...' });
   const before = await chain.query.assets.account(0, s);
   const beforeBalance = before.balance.toHuman().replaceAll(',', '');
+  const nonce = (await chain.query.ad.nonceOf(did)).toNumber() + 1;
+  const height = (await chain.query.system.number()).toNumber();
+  const payload = u8aConcat(
+    chain.createType('H256', ad).toU8a(),
+    chain.createType('H160', kol).toU8a(),
+    chain.createType('u64', nonce).toU8a(),
+    chain.createType('BlockNumber', height).toU8a()
+  );
+  const receipt = {
+    nonce,
+    height,
+    signature: { Sr25519: c.sign(payload) },
+  };
   await submit(
     chain,
-    chain.tx.ad.pay(ad, kol, did, [['Polkadot', 5]], null),
+    chain.tx.ad.pay(ad, kol, did, [['Polkadot', 5]], null, receipt),
     alice
   );
   const after = await chain.query.assets.account(0, s);
@@ -47,6 +47,7 @@ where
     C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
     C::Api: pallet_mmr_rpc::MmrRuntimeApi<Block, <Block as sp_runtime::traits::Block>::Hash>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+    C::Api: parami_ad_rpc::AdRuntimeApi<Block, Hash, DecentralizedId, Balance>,
     C::Api: parami_swap_rpc::SwapRuntimeApi<Block, AssetId, Balance>,
     C::Api: BlockBuilder<Block>,
     P: TransactionPool + 'static,
@@ -56,6 +57,7 @@ where
     use pallet_contracts_rpc::{Contracts, ContractsApi};
     use pallet_mmr_rpc::{Mmr, MmrApi};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
+    use parami_ad_rpc::{AdApi, AdRpcHandler};
     use parami_did_rpc::{DidApi, DidRpcHandler};
     use parami_swap_rpc::{SwapApi, SwapsRpcHandler};
     use substrate_frame_rpc_system::{FullSystem, SystemApi};
@@ -88,6 +90,7 @@ where
     {
         io.extend_with(did_rpc);
     }
+    io.extend_with(AdApi::to_delegate(AdRpcHandler::new(client.clone())));
     io.extend_with(SwapApi::to_delegate(SwapsRpcHandler::new(client.clone())));
 
     io
@@ -0,0 +1,81 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use parami_primitives::BalanceWrapper;
+use sp_runtime::{
+    traits::{MaybeDisplay, MaybeFromStr},
+    DispatchError,
+};
+
+type ApiResult<T> = Result<T, DispatchError>;
+
+/// (paid, visitors, awarded, tokens, spent)
+pub type StatsTuple<Balance> = (
+    BalanceWrapper<Balance>,
+    u32,
+    BalanceWrapper<Balance>,
+    BalanceWrapper<Balance>,
+    BalanceWrapper<Balance>,
+);
+
+sp_api::decl_runtime_apis! {
+    pub trait AdRuntimeApi<Hash, DecentralizedId, Balance>
+    where
+        Hash: Codec,
+        DecentralizedId: Codec,
+        Balance: Codec + MaybeDisplay + MaybeFromStr,
+    {
+        /// Get dry-run result of pay
+        ///
+        /// # Arguments
+        ///
+        /// * `ad` - The Advertisement ID
+        /// * `kol` - The DID of the KOL
+        /// * `visitor` - The DID of the visitor
+        /// * `referer` - The DID of the referer, ignored if referers are registered ones
+        ///
+        /// # Results
+        ///
+        /// tuple of (score, amount, award, swapped)
+        ///
+        /// * `score` - The score of the visitor
+        /// * `amount` - The amount of tokens to be paid
+        /// * `award` - The amount of tokens to be awarded to referers of all levels
+        /// * `swapped` - Whether a swap would be triggered
+        fn dryly_pay(
+            ad: Hash,
+            kol: DecentralizedId,
+            visitor: DecentralizedId,
+            referer: Option<DecentralizedId>,
+        ) -> ApiResult<(u32, BalanceWrapper<Balance>, BalanceWrapper<Balance>, bool)>;
+
+        /// Get payout statistics of an advertisement
+        ///
+        /// # Arguments
+        ///
+        /// * `ad` - The Advertisement ID
+        ///
+        /// # Results
+        ///
+        /// tuple of (paid, visitors, awarded, tokens, spent)
+        ///
+        /// * `paid` - The amount of tokens paid to visitors, referral awards excluded
+        /// * `visitors` - The number of payouts to visitors
+        /// * `awarded` - The amount of tokens awarded to referers
+        /// * `tokens` - The amount of tokens bought with budget for payouts
+        /// * `spent` - The amount of budget spent on buying tokens
+        fn get_stats(ad: Hash) -> ApiResult<StatsTuple<Balance>>;
+
+        /// Get payout statistics of an advertisement through a KOL
+        ///
+        /// # Arguments
+        ///
+        /// * `ad` - The Advertisement ID
+        /// * `kol` - The DID of the KOL
+        ///
+        /// # Results
+        ///
+        /// same as `get_stats`, limited to the slot of the KOL
+        fn get_slot_stats(ad: Hash, kol: DecentralizedId) -> ApiResult<StatsTuple<Balance>>;
+    }
+}
@@ -0,0 +1,179 @@
+pub use self::gen_client::Client as AdClient;
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+pub use parami_ad_rpc_runtime_api::{AdRuntimeApi, StatsTuple};
+use parami_primitives::BalanceWrapper;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+    generic::BlockId,
+    traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
+};
+use std::sync::Arc;
+
+#[rpc]
+pub trait AdApi<BlockHash, Hash, DecentralizedId, Balance>
+where
+    Balance: MaybeDisplay + MaybeFromStr,
+{
+    /// Get dry-run result of pay
+    ///
+    /// # Arguments
+    ///
+    /// * `ad` - The Advertisement ID
+    /// * `kol` - The DID of the KOL
+    /// * `visitor` - The DID of the visitor
+    /// * `referer` - The DID of the referer, ignored if referers are registered ones
+    ///
+    /// # Results
+    ///
+    /// tuple of (score, amount, award, swapped)
+    ///
+    /// * `score` - The score of the visitor
+    /// * `amount` - The amount of tokens to be paid
+    /// * `award` - The amount of tokens to be awarded to referers of all levels
+    /// * `swapped` - Whether a swap would be triggered
+    #[rpc(name = "ad_drylyPay")]
+    fn dryly_pay(
+        &self,
+        ad: Hash,
+        kol: DecentralizedId,
+        visitor: DecentralizedId,
+        referer: Option<DecentralizedId>,
+        at: Option<BlockHash>,
+    ) -> Result<(u32, BalanceWrapper<Balance>, BalanceWrapper<Balance>, bool)>;
+
+    /// Get payout statistics of an advertisement
+    ///
+    /// # Arguments
+    ///
+    /// * `ad` - The Advertisement ID
+    ///
+    /// # Results
+    ///
+    /// tuple of (paid, visitors, awarded, tokens, spent)
+    ///
+    /// * `paid` - The amount of tokens paid to visitors, referral awards excluded
+    /// * `visitors` - The number of payouts to visitors
+    /// * `awarded` - The amount of tokens awarded to referers
+    /// * `tokens` - The amount of tokens bought with budget for payouts
+    /// * `spent` - The amount of budget spent on buying tokens
+    #[rpc(name = "ad_getStats")]
+    fn get_stats(&self, ad: Hash, at: Option<BlockHash>) -> Result<StatsTuple<Balance>>;
+
+    /// Get payout statistics of an advertisement through a KOL
+    ///
+    /// # Arguments
+    ///
+    /// * `ad` - The Advertisement ID
+    /// * `kol` - The DID of the KOL
+    ///
+    /// # Results
+    ///
+    /// same as `ad_getStats`, limited to the slot of the KOL
+    #[rpc(name = "ad_getSlotStats")]
+    fn get_slot_stats(
+        &self,
+        ad: Hash,
+        kol: DecentralizedId,
+        at: Option<BlockHash>,
+    ) -> Result<StatsTuple<Balance>>;
+}
+
+pub struct AdRpcHandler<C, Block, Hash, DecentralizedId, Balance> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<(Block, Hash, DecentralizedId, Balance)>,
+}
+
+impl<C, Block, Hash, DecentralizedId, Balance>
+    AdRpcHandler<C, Block, Hash, DecentralizedId, Balance>
+{
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, Hash, DecentralizedId, Balance>
+    AdApi<<Block as BlockT>::Hash, Hash, DecentralizedId, Balance>
+    for AdRpcHandler<C, Block, Hash, DecentralizedId, Balance>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: AdRuntimeApi<Block, Hash, DecentralizedId, Balance>,
+    Hash: Codec + Send + Sync + 'static,
+    DecentralizedId: Codec + Send + Sync + 'static,
+    Balance: Codec + MaybeDisplay + MaybeFromStr + Send + Sync + 'static,
+{
+    fn dryly_pay(
+        &self,
+        ad: Hash,
+        kol: DecentralizedId,
+        visitor: DecentralizedId,
+        referer: Option<DecentralizedId>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<(u32, BalanceWrapper<Balance>, BalanceWrapper<Balance>, bool)> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api
+            .dryly_pay(&at, ad, kol, visitor, referer)
+            .map_err(|e| RpcError {
+                code: ErrorCode::InternalError,
+                message: "Unable to dry-run pay.".into(),
+                data: Some(format!("{:?}", e).into()),
+            })?;
+
+        res.map_err(|e| RpcError {
+            code: ErrorCode::ServerError(1),
+            message: "Unable to dry-run pay.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+
+    fn get_stats(
+        &self,
+        ad: Hash,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<StatsTuple<Balance>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api.get_stats(&at, ad).map_err(|e| RpcError {
+            code: ErrorCode::InternalError,
+            message: "Unable to get stats.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })?;
+
+        res.map_err(|e| RpcError {
+            code: ErrorCode::ServerError(1),
+            message: "Unable to get stats.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+
+    fn get_slot_stats(
+        &self,
+        ad: Hash,
+        kol: DecentralizedId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<StatsTuple<Balance>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api.get_slot_stats(&at, ad, kol).map_err(|e| RpcError {
+            code: ErrorCode::InternalError,
+            message: "Unable to get slot stats.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })?;
+
+        res.map_err(|e| RpcError {
+            code: ErrorCode::ServerError(1),
+            message: "Unable to get slot stats.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+}
@@ -3,25 +3,67 @@ use super::*;
 #[allow(unused)]
 use crate::Pallet as Ad;
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{
+    fungibles::{Create, Mutate},
+    Get, Hooks,
+};
 use frame_system::RawOrigin;
 use parami_advertiser::Pallet as Advertiser;
 use parami_did::Pallet as Did;
 use parami_nft::Pallet as Nft;
 use parami_tag::Pallet as Tag;
 use rand::{rngs::SmallRng, Rng, SeedableRng};
+use sp_core::{crypto::KeyTypeId, sr25519};
 use sp_runtime::traits::{Bounded, Saturating};
 
+const RECEIPT: KeyTypeId = KeyTypeId(*b"rcpt");
+
+const CID: &[u8] = b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+
+fn creative<T: Config>(m: u32) -> CreativeOf<T> {
+    types::AdCreative {
+        landing: vec![b'a'; m as usize].try_into().unwrap(),
+        cid: CID.to_vec().try_into().unwrap(),
+        content_hash: Default::default(),
+        mime: b"image/png".to_vec().try_into().unwrap(),
+    }
+}
+
+fn sign_receipt<T>(
+    public: &sr25519::Public,
+    ad: &HashOf<T>,
+    kol: &DidOf<T>,
+    visitor: &DidOf<T>,
+    scores: &[(Vec<u8>, i8)],
+) -> ReceiptOf<T>
+where
+    T: Config,
+    T::Signature: From<sr25519::Signature>,
+{
+    let height = <frame_system::Pallet<T>>::block_number();
+    let payload = Ad::<T>::receipt_payload(ad, kol, visitor, None, scores, 1, height);
+    let signature = sp_io::crypto::sr25519_sign(RECEIPT, public, &payload).unwrap();
+
+    types::Receipt {
+        nonce: 1,
+        height,
+        signature: signature.into(),
+    }
+}
+
 benchmarks! {
     where_clause {
         where
         T: parami_advertiser::Config,
         T: parami_did::Config,
         T: parami_nft::Config,
-        T: parami_tag::Config
+        T: parami_tag::Config,
+        T::Signature: From<sr25519::Signature>,
+        T::AccountId: From<sr25519::Public>
     }
 
     create {
-        let m in 0 .. 1000;
+        let m in 0 .. T::MaxMetadataLen::get();
         let n in 1 .. 1000;
 
         let caller: T::AccountId = whitelisted_caller();
@@ -40,18 +82,18 @@ benchmarks! {
         let mut rng = SmallRng::from_seed(Default::default());
 
         for i in 0..n {
-            let name: Vec<u8> = (0..50).map(|_| rng.gen()).collect();
+            let name: Vec<u8> = (0..<T as Config>::MaxTagLen::get()).map(|_| rng.gen()).collect();
             Tag::<T>::create(RawOrigin::Signed(caller.clone()).into(), name.clone())?;
             tags.push(name);
         }
 
-        let metadata = vec![0u8; m as usize];
-    }: _(RawOrigin::Signed(caller), min, tags, metadata, 1, HeightOf::<T>::max_value())
+        let creative = creative::<T>(m);
+    }: _(RawOrigin::Signed(caller), min, tags, creative, types::RewardSplit::from(1), HeightOf::<T>::max_value(), None, None)
     verify {
         assert_ne!(<Metadata<T>>::iter_values().next(), None);
     }
 
-    update_reward_rate {
+    update_reward_split {
         let caller: T::AccountId = whitelisted_caller();
 
         let max = BalanceOf::<T>::max_value();
@@ -68,16 +110,23 @@ benchmarks! {
             RawOrigin::Signed(caller.clone()).into(),
             min,
             vec![],
-            Default::default(),
-            1,
+            creative::<T>(0),
+            types::RewardSplit::from(1),
             HeightOf::<T>::max_value(),
+            None,
+            None,
         )?;
 
         let ad = <Metadata<T>>::iter_keys().next().unwrap();
-    }: _(RawOrigin::Signed(caller), ad, 100)
+
+        let split = types::RewardSplit {
+            source: types::RefererSource::Registered,
+            rates: vec![1; T::MaxReferralLevels::get() as usize].try_into().unwrap(),
+        };
+    }: _(RawOrigin::Signed(caller), ad, split.clone())
     verify {
         let ad = <Metadata<T>>::get(&ad).unwrap();
-        assert_eq!(ad.reward_rate, 100);
+        assert_eq!(ad.reward_split, split);
     }
 
     update_tags {
@@ -99,7 +148,7 @@ benchmarks! {
         let mut rng = SmallRng::from_seed(Default::default());
 
         for i in 0..n {
-            let name: Vec<u8> = (0..50).map(|_| rng.gen()).collect();
+            let name: Vec<u8> = (0..<T as Config>::MaxTagLen::get()).map(|_| rng.gen()).collect();
             Tag::<T>::create(RawOrigin::Signed(caller.clone()).into(), name.clone())?;
             tags.push(name);
         }
@@ -108,9 +157,11 @@ benchmarks! {
             RawOrigin::Signed(caller.clone()).into(),
             min,
             vec![],
-            Default::default(),
-            1,
+            creative::<T>(0),
+            types::RewardSplit::from(1),
             HeightOf::<T>::max_value(),
+            None,
+            None,
         )?;
 
         let ad = <Metadata<T>>::iter_keys().next().unwrap();
@@ -119,6 +170,83 @@ benchmarks! {
         assert_eq!(Tag::<T>::tags_of(&ad).len(), tags.len());
     }
 
+    update_targeting {
+        let n in 1 .. T::MaxTargetingLen::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+
+        let max = BalanceOf::<T>::max_value();
+        let min = <T as parami_did::Config>::Currency::minimum_balance();
+
+        let pot = <T as parami_did::Config>::Currency::minimum_balance().saturating_mul(1_000_000u32.into());
+
+        <T as parami_did::Config>::Currency::make_free_balance_be(&caller, max);
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Advertiser::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), pot)?;
+
+        let mut scores = vec![];
+        let mut rng = SmallRng::from_seed(Default::default());
+
+        for i in 0..n {
+            let name: Vec<u8> = (0..<T as Config>::MaxTagLen::get()).map(|_| rng.gen()).collect();
+            Tag::<T>::create(RawOrigin::Signed(caller.clone()).into(), name.clone())?;
+            scores.push((name.try_into().unwrap(), 5));
+        }
+
+        Ad::<T>::create(
+            RawOrigin::Signed(caller.clone()).into(),
+            min,
+            vec![],
+            creative::<T>(0),
+            types::RewardSplit::from(1),
+            HeightOf::<T>::max_value(),
+            None,
+            None,
+        )?;
+
+        let ad = <Metadata<T>>::iter_keys().next().unwrap();
+
+        let targeting = types::Targeting {
+            scores: scores.try_into().unwrap(),
+            links: Default::default(),
+            min_age: Zero::zero(),
+        };
+    }: _(RawOrigin::Signed(caller), ad, Some(targeting))
+    verify {
+        assert_ne!(<Metadata<T>>::get(&ad).unwrap().targeting, None);
+    }
+
+    update_frequency {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let max = BalanceOf::<T>::max_value();
+        let min = <T as parami_did::Config>::Currency::minimum_balance();
+
+        let pot = <T as parami_did::Config>::Currency::minimum_balance().saturating_mul(1_000_000u32.into());
+
+        <T as parami_did::Config>::Currency::make_free_balance_be(&caller, max);
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Advertiser::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), pot)?;
+
+        Ad::<T>::create(
+            RawOrigin::Signed(caller.clone()).into(),
+            min,
+            vec![],
+            creative::<T>(0),
+            types::RewardSplit::from(1),
+            HeightOf::<T>::max_value(),
+            None,
+            None,
+        )?;
+
+        let ad = <Metadata<T>>::iter_keys().next().unwrap();
+    }: _(RawOrigin::Signed(caller), ad, types::Frequency::Times(3))
+    verify {
+        assert_eq!(<Metadata<T>>::get(&ad).unwrap().frequency, types::Frequency::Times(3));
+    }
+
     add_budget {
         let caller: T::AccountId = whitelisted_caller();
 
@@ -136,9 +264,11 @@ benchmarks! {
             RawOrigin::Signed(caller.clone()).into(),
             pot,
             vec![],
-            Default::default(),
-            1,
+            creative::<T>(0),
+            types::RewardSplit::from(1),
             HeightOf::<T>::max_value(),
+            None,
+            None,
         )?;
 
         let ad = <Metadata<T>>::iter_keys().next().unwrap();
@@ -170,9 +300,11 @@ benchmarks! {
             RawOrigin::Signed(caller.clone()).into(),
             pot,
             vec![],
-            Default::default(),
-            1,
+            creative::<T>(0),
+            types::RewardSplit::from(1),
             HeightOf::<T>::max_value(),
+            None,
+            None,
         )?;
         let ad = <Metadata<T>>::iter_keys().next().unwrap();
 
@@ -189,7 +321,7 @@ benchmarks! {
         )?;
     }: _(RawOrigin::Signed(caller.clone()), ad, did, pot)
     verify {
-        assert_ne!(<SlotOf<T>>::get(&did), None);
+        assert_ne!(Ad::<T>::find_slot(&did, &ad), None);
     }
 
     pay {
@@ -199,7 +331,8 @@ benchmarks! {
 
         let kol: T::AccountId = account("kol", 1, 1);
 
-        let visitor: T::AccountId = account("visitor", 2, 2);
+        let public = sp_io::crypto::sr25519_generate(RECEIPT, None);
+        let visitor: T::AccountId = public.into();
 
         let max = BalanceOf::<T>::max_value();
         let min = <T as parami_did::Config>::Currency::minimum_balance();
@@ -223,7 +356,7 @@ benchmarks! {
             let mut rng = SmallRng::from_seed(Default::default());
 
             for i in 0..n {
-                let name: Vec<u8> = (0..50).map(|_| rng.gen()).collect();
+                let name: Vec<u8> = (0..<T as Config>::MaxTagLen::get()).map(|_| rng.gen()).collect();
                 Tag::<T>::create(RawOrigin::Signed(caller.clone()).into(), name.clone())?;
                 tags.push(name.clone());
                 scores.push((name, 5))
@@ -234,9 +367,11 @@ benchmarks! {
             RawOrigin::Signed(caller.clone()).into(),
             pot,
             tags,
-            Default::default(),
-            1,
+            creative::<T>(0),
+            types::RewardSplit::from(1),
             HeightOf::<T>::max_value(),
+            None,
+            None,
         )?;
         let ad = <Metadata<T>>::iter_keys().next().unwrap();
 
@@ -253,17 +388,507 @@ benchmarks! {
         )?;
 
         Ad::<T>::bid(RawOrigin::Signed(caller.clone()).into(), ad, slot, pot)?;
-    }: _(RawOrigin::Signed(caller.clone()), ad, slot, visitor, scores, None)
+
+        let receipt = sign_receipt::<T>(&public, &ad, &slot, &visitor, &scores);
+    }: _(RawOrigin::Signed(caller.clone()), ad, slot, visitor, scores, None, receipt)
     verify {
         use frame_support::traits::fungibles::Inspect;
 
         let slot = Did::<T>::meta(&slot).unwrap();
         let nft = slot.nft.unwrap();
 
-        let visitor: T::AccountId = account("visitor", 2, 2);
+        let visitor: T::AccountId = public.into();
 
         assert!(<T as parami_nft::Config>::Assets::balance(nft, &visitor) > min.into());
     }
+
+    pay_batch {
+        let n in 1 .. T::MaxBatchSize::get();
+        let m in 100 .. 1000;
+
+        let caller: T::AccountId = whitelisted_caller();
+
+        let kol: T::AccountId = account("kol", 1, 1);
+
+        let max = BalanceOf::<T>::max_value();
+        let min = <T as parami_did::Config>::Currency::minimum_balance();
+        let pot = min.saturating_mul(1_000_000u32.into());
+
+        <T as parami_did::Config>::Currency::make_free_balance_be(&caller, max);
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Advertiser::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), pot)?;
+
+        <T as parami_did::Config>::Currency::make_free_balance_be(&kol, pot);
+        Did::<T>::register(RawOrigin::Signed(kol.clone()).into(), None)?;
+        let slot = Did::<T>::did_of(&kol).unwrap();
+
+        let mut visitors = vec![];
+        for _ in 0..n {
+            let public = sp_io::crypto::sr25519_generate(RECEIPT, None);
+            let visitor: T::AccountId = public.into();
+
+            <T as parami_did::Config>::Currency::make_free_balance_be(&visitor, pot);
+            Did::<T>::register(RawOrigin::Signed(visitor.clone()).into(), None)?;
+            visitors.push((public, Did::<T>::did_of(&visitor).unwrap()));
+        }
+
+        let mut tags = vec![];
+        let mut scores: Vec<Vec<(Vec<u8>, i8)>> = visitors.iter().map(|_| vec![]).collect();
+
+        let mut rng = SmallRng::from_seed(Default::default());
+
+        for i in 0..m {
+            let name: Vec<u8> = (0..<T as Config>::MaxTagLen::get()).map(|_| rng.gen()).collect();
+            Tag::<T>::create(RawOrigin::Signed(caller.clone()).into(), name.clone())?;
+            tags.push(name.clone());
+            scores[(i % n) as usize].push((name, 5));
+        }
+
+        Ad::<T>::create(
+            RawOrigin::Signed(caller.clone()).into(),
+            pot,
+            tags,
+            creative::<T>(0),
+            types::RewardSplit::from(1),
+            HeightOf::<T>::max_value(),
+            None,
+            None,
+        )?;
+        let ad = <Metadata<T>>::iter_keys().next().unwrap();
+
+        Nft::<T>::back(
+            RawOrigin::Signed(caller.clone()).into(),
+            slot,
+            pot.saturating_mul(2u32.into()),
+        )?;
+
+        Nft::<T>::mint(
+            RawOrigin::Signed(kol).into(),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec(),
+        )?;
+
+        Ad::<T>::bid(RawOrigin::Signed(caller.clone()).into(), ad, slot, pot)?;
+
+        let items: Vec<_> = visitors
+            .iter()
+            .zip(scores)
+            .map(|((public, visitor), scores)| types::PayItem {
+                visitor: *visitor,
+                receipt: sign_receipt::<T>(public, &ad, &slot, visitor, &scores),
+                scores,
+                referer: None,
+            })
+            .collect();
+    }: _(RawOrigin::Signed(caller.clone()), ad, slot, items)
+    verify {
+        for (_, visitor) in visitors {
+            assert_ne!(<Payout<T>>::get(&ad, &visitor), None);
+        }
+    }
+
+    terminate {
+        let n in 1 .. T::MaxSlotsPerAd::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+
+        let max = BalanceOf::<T>::max_value();
+        let min = <T as parami_did::Config>::Currency::minimum_balance();
+        let pot = min.saturating_mul(1_000_000u32.into());
+        let budget = pot.saturating_mul(n.into());
+
+        <T as parami_did::Config>::Currency::make_free_balance_be(&caller, max);
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Advertiser::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), budget)?;
+
+        Ad::<T>::create(
+            RawOrigin::Signed(caller.clone()).into(),
+            budget,
+            vec![],
+            creative::<T>(0),
+            types::RewardSplit::from(1),
+            HeightOf::<T>::max_value(),
+            None,
+            None,
+        )?;
+        let ad = <Metadata<T>>::iter_keys().next().unwrap();
+
+        for i in 0 .. n {
+            let kol: T::AccountId = account("kol", i, i);
+
+            <T as parami_did::Config>::Currency::make_free_balance_be(&kol, pot);
+
+            Did::<T>::register(RawOrigin::Signed(kol.clone()).into(), None)?;
+            let did = Did::<T>::did_of(&kol).unwrap();
+
+            Nft::<T>::back(
+                RawOrigin::Signed(caller.clone()).into(),
+                did,
+                pot.saturating_mul(2u32.into()),
+            )?;
+
+            Nft::<T>::mint(
+                RawOrigin::Signed(kol).into(),
+                b"Test Token".to_vec(),
+                b"XTT".to_vec(),
+            )?;
+
+            Ad::<T>::bid(RawOrigin::Signed(caller.clone()).into(), ad, did, pot)?;
+        }
+    }: _(RawOrigin::Signed(caller.clone()), ad)
+    verify {
+        assert_eq!(<Metadata<T>>::get(&ad).unwrap().status, types::AdStatus::Terminated);
+        assert_eq!(<SlotsOf<T>>::get(&ad), None);
+    }
+
+    set_capacity {
+        let kol: T::AccountId = account("kol", 1, 1);
+
+        let pot = <T as parami_did::Config>::Currency::minimum_balance().saturating_mul(1_000_000u32.into());
+
+        <T as parami_did::Config>::Currency::make_free_balance_be(&kol, pot);
+
+        Did::<T>::register(RawOrigin::Signed(kol.clone()).into(), None)?;
+        let did = Did::<T>::did_of(&kol).unwrap();
+
+        let capacity = T::MaxSlotsPerKol::get();
+    }: _(RawOrigin::Signed(kol), capacity)
+    verify {
+        assert_eq!(<CapacityOf<T>>::get(&did), capacity);
+    }
+
+    on_initialize {
+        let n in 1 .. T::MaxExpiredPerBlock::get().min(T::MaxSlotsPerAd::get());
+
+        let caller: T::AccountId = whitelisted_caller();
+
+        let max = BalanceOf::<T>::max_value();
+        let min = <T as parami_did::Config>::Currency::minimum_balance();
+        let pot = min.saturating_mul(1_000_000u32.into());
+        let budget = pot.saturating_mul(n.into());
+
+        <T as parami_did::Config>::Currency::make_free_balance_be(&caller, max);
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Advertiser::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), budget)?;
+
+        Ad::<T>::create(
+            RawOrigin::Signed(caller.clone()).into(),
+            budget,
+            vec![],
+            creative::<T>(0),
+            types::RewardSplit::from(1),
+            HeightOf::<T>::max_value(),
+            None,
+            None,
+        )?;
+        let ad = <Metadata<T>>::iter_keys().next().unwrap();
+
+        for i in 0 .. n {
+            let kol: T::AccountId = account("kol", i, i);
+
+            <T as parami_did::Config>::Currency::make_free_balance_be(&kol, pot);
+
+            Did::<T>::register(RawOrigin::Signed(kol.clone()).into(), None)?;
+            let did = Did::<T>::did_of(&kol).unwrap();
+
+            Nft::<T>::back(
+                RawOrigin::Signed(caller.clone()).into(),
+                did,
+                pot.saturating_mul(2u32.into()),
+            )?;
+
+            Nft::<T>::mint(
+                RawOrigin::Signed(kol).into(),
+                b"Test Token".to_vec(),
+                b"XTT".to_vec(),
+            )?;
+
+            Ad::<T>::bid(RawOrigin::Signed(caller.clone()).into(), ad, did, pot)?;
+        }
+
+        let (_, _, deadline) = <DeadlineOf<T>>::iter().next().unwrap();
+    }: {
+        Ad::<T>::on_initialize(deadline);
+    }
+    verify {
+        assert_eq!(<SlotsOf<T>>::get(&ad).unwrap_or_default().len(), 0);
+    }
+
+    pause {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let kol: T::AccountId = account("kol", 1, 1);
+
+        let max = BalanceOf::<T>::max_value();
+        let min = <T as parami_did::Config>::Currency::minimum_balance();
+        let pot = min.saturating_mul(1_000_000u32.into());
+
+        <T as parami_did::Config>::Currency::make_free_balance_be(&caller, max);
+        <T as parami_did::Config>::Currency::make_free_balance_be(&kol, pot);
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Advertiser::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), pot)?;
+
+        Did::<T>::register(RawOrigin::Signed(kol.clone()).into(), None)?;
+        let did = Did::<T>::did_of(&kol).unwrap();
+
+        Ad::<T>::create(
+            RawOrigin::Signed(caller.clone()).into(),
+            pot,
+            vec![],
+            creative::<T>(0),
+            types::RewardSplit::from(1),
+            HeightOf::<T>::max_value(),
+            None,
+            None,
+        )?;
+        let ad = <Metadata<T>>::iter_keys().next().unwrap();
+
+        Nft::<T>::back(
+            RawOrigin::Signed(caller.clone()).into(),
+            did,
+            pot.saturating_mul(2u32.into()),
+        )?;
+
+        Nft::<T>::mint(
+            RawOrigin::Signed(kol).into(),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec(),
+        )?;
+
+        Ad::<T>::bid(RawOrigin::Signed(caller.clone()).into(), ad, did, pot)?;
+    }: _(RawOrigin::Signed(caller.clone()), ad)
+    verify {
+        assert_eq!(<Metadata<T>>::get(&ad).unwrap().status, types::AdStatus::Paused);
+    }
+
+    resume {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let kol: T::AccountId = account("kol", 1, 1);
+
+        let max = BalanceOf::<T>::max_value();
+        let min = <T as parami_did::Config>::Currency::minimum_balance();
+        let pot = min.saturating_mul(1_000_000u32.into());
+
+        <T as parami_did::Config>::Currency::make_free_balance_be(&caller, max);
+        <T as parami_did::Config>::Currency::make_free_balance_be(&kol, pot);
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Advertiser::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), pot)?;
+
+        Did::<T>::register(RawOrigin::Signed(kol.clone()).into(), None)?;
+        let did = Did::<T>::did_of(&kol).unwrap();
+
+        Ad::<T>::create(
+            RawOrigin::Signed(caller.clone()).into(),
+            pot,
+            vec![],
+            creative::<T>(0),
+            types::RewardSplit::from(1),
+            HeightOf::<T>::max_value(),
+            None,
+            None,
+        )?;
+        let ad = <Metadata<T>>::iter_keys().next().unwrap();
+
+        Nft::<T>::back(
+            RawOrigin::Signed(caller.clone()).into(),
+            did,
+            pot.saturating_mul(2u32.into()),
+        )?;
+
+        Nft::<T>::mint(
+            RawOrigin::Signed(kol).into(),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec(),
+        )?;
+
+        Ad::<T>::bid(RawOrigin::Signed(caller.clone()).into(), ad, did, pot)?;
+
+        Ad::<T>::pause(RawOrigin::Signed(caller.clone()).into(), ad)?;
+
+        let deadline = <DeadlineOf<T>>::get(&did, &ad).unwrap();
+
+        let height = <frame_system::Pallet<T>>::block_number();
+        <frame_system::Pallet<T>>::set_block_number(height + 10u32.into());
+    }: _(RawOrigin::Signed(caller.clone()), ad)
+    verify {
+        assert_eq!(<Metadata<T>>::get(&ad).unwrap().status, types::AdStatus::Active);
+        assert!(<DeadlineOf<T>>::get(&did, &ad).unwrap() >= deadline);
+    }
+
+    set_slot_config {
+        let n in 1 .. T::MaxSlotConfigLen::get();
+
+        let kol: T::AccountId = account("kol", 1, 1);
+
+        let pot = <T as parami_did::Config>::Currency::minimum_balance().saturating_mul(1_000_000u32.into());
+
+        <T as parami_did::Config>::Currency::make_free_balance_be(&kol, pot);
+
+        Did::<T>::register(RawOrigin::Signed(kol.clone()).into(), None)?;
+        let did = Did::<T>::did_of(&kol).unwrap();
+
+        let mut tags = vec![];
+        let mut rng = SmallRng::from_seed(Default::default());
+
+        for i in 0..n {
+            let name: Vec<u8> = (0..<T as Config>::MaxTagLen::get()).map(|_| rng.gen()).collect();
+            Tag::<T>::create(RawOrigin::Signed(kol.clone()).into(), name.clone())?;
+            tags.push(name.try_into().unwrap());
+        }
+
+        let config = types::SlotConfig {
+            reserve: pot,
+            tags: tags.try_into().unwrap(),
+            blocked: Default::default(),
+        };
+    }: _(RawOrigin::Signed(kol), config)
+    verify {
+        assert_eq!(<SlotConfigOf<T>>::get(&did).unwrap().reserve, pot);
+    }
+
+    evict {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let kol: T::AccountId = account("kol", 1, 1);
+
+        let max = BalanceOf::<T>::max_value();
+        let min = <T as parami_did::Config>::Currency::minimum_balance();
+        let pot = min.saturating_mul(1_000_000u32.into());
+
+        <T as parami_did::Config>::Currency::make_free_balance_be(&caller, max);
+        <T as parami_did::Config>::Currency::make_free_balance_be(&kol, pot);
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Advertiser::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), pot)?;
+
+        Did::<T>::register(RawOrigin::Signed(kol.clone()).into(), None)?;
+        let did = Did::<T>::did_of(&kol).unwrap();
+
+        Ad::<T>::create(
+            RawOrigin::Signed(caller.clone()).into(),
+            pot,
+            vec![],
+            creative::<T>(0),
+            types::RewardSplit::from(1),
+            HeightOf::<T>::max_value(),
+            None,
+            None,
+        )?;
+        let ad = <Metadata<T>>::iter_keys().next().unwrap();
+
+        Nft::<T>::back(
+            RawOrigin::Signed(caller.clone()).into(),
+            did,
+            pot.saturating_mul(2u32.into()),
+        )?;
+
+        Nft::<T>::mint(
+            RawOrigin::Signed(kol.clone()).into(),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec(),
+        )?;
+
+        Ad::<T>::bid(RawOrigin::Signed(caller.clone()).into(), ad, did, pot)?;
+    }: _(RawOrigin::Signed(kol), ad)
+    verify {
+        assert_eq!(Ad::<T>::find_slot(&did, &ad), None);
+    }
+
+    update_metadata {
+        let m in 0 .. T::MaxMetadataLen::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+
+        let max = BalanceOf::<T>::max_value();
+        let min = <T as parami_did::Config>::Currency::minimum_balance();
+
+        let pot = <T as parami_did::Config>::Currency::minimum_balance().saturating_mul(1_000_000u32.into());
+
+        <T as parami_did::Config>::Currency::make_free_balance_be(&caller, max);
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Advertiser::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), pot)?;
+
+        Ad::<T>::create(
+            RawOrigin::Signed(caller.clone()).into(),
+            min,
+            vec![],
+            creative::<T>(0),
+            types::RewardSplit::from(1),
+            HeightOf::<T>::max_value(),
+            None,
+            None,
+        )?;
+
+        let ad = <Metadata<T>>::iter_keys().next().unwrap();
+
+        let creative = creative::<T>(m);
+    }: _(RawOrigin::Signed(caller), ad, creative.clone())
+    verify {
+        let ad = <Metadata<T>>::get(&ad).unwrap();
+        assert_eq!(ad.creative, creative);
+        assert_eq!(ad.revision, 1);
+    }
+
+    stake_fragments {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let min = <T as parami_did::Config>::Currency::minimum_balance();
+        let pot = min.saturating_mul(1_000_000u32.into());
+
+        let nft: AssetOf<T> = Default::default();
+
+        <T as parami_nft::Config>::Assets::create(nft, caller.clone(), true, min)?;
+        <T as parami_nft::Config>::Assets::mint_into(nft, &caller, pot)?;
+    }: _(RawOrigin::Signed(caller.clone()), nft, pot)
+    verify {
+        assert_eq!(Ad::<T>::holding_of(nft, &caller).staked, pot);
+    }
+
+    unstake_fragments {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let min = <T as parami_did::Config>::Currency::minimum_balance();
+        let pot = min.saturating_mul(1_000_000u32.into());
+
+        let nft: AssetOf<T> = Default::default();
+
+        <T as parami_nft::Config>::Assets::create(nft, caller.clone(), true, min)?;
+        <T as parami_nft::Config>::Assets::mint_into(nft, &caller, pot)?;
+
+        Ad::<T>::stake_fragments(RawOrigin::Signed(caller.clone()).into(), nft, pot)?;
+    }: _(RawOrigin::Signed(caller.clone()), nft, pot)
+    verify {
+        assert_eq!(Ad::<T>::holding_of(nft, &caller).staked, Zero::zero());
+    }
+
+    claim_holder_share {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let min = <T as parami_did::Config>::Currency::minimum_balance();
+        let pot = min.saturating_mul(1_000_000u32.into());
+
+        let nft: AssetOf<T> = Default::default();
+
+        <T as parami_nft::Config>::Assets::create(nft, caller.clone(), true, min)?;
+        <T as parami_nft::Config>::Assets::mint_into(nft, &caller, pot)?;
+
+        Ad::<T>::stake_fragments(RawOrigin::Signed(caller.clone()).into(), nft, pot)?;
+
+        // a share of one fragment per staked fragment
+        <T as parami_nft::Config>::Assets::mint_into(nft, &Ad::<T>::holders_account(nft), pot)?;
+        <HolderPool<T>>::mutate(nft, |pool| pool.acc = FixedU128::one().into_inner());
+    }: _(RawOrigin::Signed(caller.clone()), nft)
+    verify {
+        use frame_support::traits::fungibles::Inspect;
+
+        assert_eq!(<T as parami_nft::Config>::Assets::balance(nft, &caller), pot);
+    }
 }
 
 impl_benchmark_test_suite!(Tag, crate::mock::new_test_ext(), crate::mock::Test);
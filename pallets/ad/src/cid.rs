@@ -0,0 +1,125 @@
+use sp_std::prelude::*;
+
+const BASE58: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE32: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+const SHA2_256: u64 = 0x12;
+const SHA2_512: u64 = 0x13;
+const BLAKE2B_256: u64 = 0xb220;
+
+/// Check if an IPFS CID in text form is well-formed
+///
+/// CIDv0 is a base58btc encoded sha2-256 multihash,
+/// CIDv1 is a base32 encoded (with `b` prefix) version, codec and multihash
+pub fn is_valid(cid: &[u8]) -> bool {
+    match cid {
+        [b'Q', b'm', ..] if cid.len() == 46 => {
+            decode_base58(cid).map_or(false, |bytes| match read_multihash(&bytes) {
+                Some((code, _)) => code == SHA2_256,
+                None => false,
+            })
+        }
+        [b'b', encoded @ ..] => decode_base32(encoded).map_or(false, |bytes| is_valid_v1(&bytes)),
+        _ => false,
+    }
+}
+
+fn is_valid_v1(bytes: &[u8]) -> bool {
+    let (version, rest) = match read_varint(bytes) {
+        Some(read) => read,
+        None => return false,
+    };
+
+    if version != 1 {
+        return false;
+    }
+
+    match read_varint(rest) {
+        Some((_codec, multihash)) => read_multihash(multihash).is_some(),
+        None => false,
+    }
+}
+
+/// Read the hash function code of a multihash,
+/// the digest must have the declared length, and the known length of the function
+fn read_multihash(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    let (code, rest) = read_varint(bytes)?;
+    let (len, digest) = read_varint(rest)?;
+
+    let expected = match code {
+        SHA2_256 | BLAKE2B_256 => Some(32),
+        SHA2_512 => Some(64),
+        _ => None,
+    };
+
+    if len == 0 || digest.len() as u64 != len || expected.map_or(false, |e| e != len) {
+        return None;
+    }
+
+    Some((code, digest))
+}
+
+/// Read an unsigned varint, at most 9 bytes as in multiformats
+fn read_varint(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    let mut value = 0u64;
+
+    for (i, byte) in bytes.iter().enumerate().take(9) {
+        value |= ((byte & 0x7f) as u64) << (i * 7);
+
+        if byte & 0x80 == 0 {
+            return Some((value, &bytes[i + 1..]));
+        }
+    }
+
+    None
+}
+
+fn decode_base58(input: &[u8]) -> Option<Vec<u8>> {
+    // little-endian while decoding
+    let mut bytes: Vec<u8> = Vec::new();
+
+    for c in input {
+        let mut carry = BASE58.iter().position(|x| x == c)? as u32;
+
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+
+        while carry > 0 {
+            bytes.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+
+    // leading ones are leading zeros
+    let zeros = input.iter().take_while(|c| **c == b'1').count();
+    bytes.resize(bytes.len() + zeros, 0);
+
+    bytes.reverse();
+
+    Some(bytes)
+}
+
+fn decode_base32(input: &[u8]) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for c in input {
+        let value = BASE32.iter().position(|x| x == c)? as u32;
+
+        buffer = (buffer << 5) | value;
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    Some(bytes)
+}
@@ -14,27 +14,40 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+mod cid;
+
+mod migrations;
+
+pub mod policy;
+
 mod types;
 
+use codec::Encode;
 use frame_support::{
-    dispatch::DispatchResult,
+    dispatch::{DispatchResult, DispatchResultWithPostInfo},
     ensure,
     traits::{
-        fungibles::Transfer,
+        fungibles::{Inspect, Transfer},
         Currency,
         ExistenceRequirement::{AllowDeath, KeepAlive},
     },
+    transactional,
     weights::Weight,
-    PalletId,
+    BoundedVec, PalletId,
 };
-use parami_did::Pallet as Did;
+use parami_did::{EnsureDid, Pallet as Did};
 use parami_magic::Pallet as Magic;
-use parami_traits::{Swaps, Tags};
+use parami_primitives::derive;
+use parami_traits::{Links, Swaps, Tags};
+use policy::BidPolicy;
 use sp_runtime::{
-    traits::{AccountIdConversion, Hash, One, Saturating, Zero},
-    DispatchError,
+    traits::{
+        AccountIdConversion, Hash, IdentifyAccount, One, SaturatedConversion, Saturating, Verify,
+        Zero,
+    },
+    DispatchError, FixedPointNumber, FixedU128, PerThing, Percent,
 };
-use sp_std::prelude::*;
+use sp_std::{collections::btree_set::BTreeSet, prelude::*};
 
 use weights::WeightInfo;
 
@@ -44,8 +57,40 @@ type BalanceOf<T> = <<T as parami_did::Config>::Currency as Currency<AccountOf<T
 type DidOf<T> = <T as parami_did::Config>::DecentralizedId;
 type HashOf<T> = <<T as frame_system::Config>::Hashing as Hash>::Output;
 type HeightOf<T> = <T as frame_system::Config>::BlockNumber;
-type MetaOf<T> = types::Metadata<AccountOf<T>, BalanceOf<T>, DidOf<T>, HashOf<T>, HeightOf<T>>;
+type LinkOf<T> = <<T as Config>::Links as Links>::AccountType;
+type ReceiptOf<T> = types::Receipt<HeightOf<T>, <T as Config>::Signature>;
+type MetaOf<T> = types::Metadata<
+    AccountOf<T>,
+    BalanceOf<T>,
+    DidOf<T>,
+    HashOf<T>,
+    BoundedVec<LinkOf<T>, <T as Config>::MaxTargetingLen>,
+    HeightOf<T>,
+    BoundedVec<u16, <T as Config>::MaxReferralLevels>,
+    BoundedVec<(TagOf<T>, i32), <T as Config>::MaxTargetingLen>,
+    AssetOf<T>,
+    BoundedVec<u8, <T as Config>::MaxMetadataLen>,
+>;
+type CommitmentOf<T> = types::Commitment<BalanceOf<T>, DidOf<T>, HashOf<T>>;
+type HolderPoolOf<T> = types::HolderPool<BalanceOf<T>>;
+type HoldingOf<T> = types::Holding<BalanceOf<T>>;
+type MigrationOf<T> = types::Migration<DidOf<T>, HashOf<T>>;
+type PayoutOf<T> = types::Payout<HeightOf<T>>;
+type CreativeOf<T> = types::AdCreative<HashOf<T>, BoundedVec<u8, <T as Config>::MaxMetadataLen>>;
+type RewardSplitOf<T> = types::RewardSplit<BoundedVec<u16, <T as Config>::MaxReferralLevels>>;
+type SlotConfOf<T> = types::SlotConfig<
+    BalanceOf<T>,
+    BoundedVec<DidOf<T>, <T as Config>::MaxSlotConfigLen>,
+    BoundedVec<TagOf<T>, <T as Config>::MaxSlotConfigLen>,
+>;
 type SlotMetaOf<T> = types::Slot<BalanceOf<T>, HashOf<T>, HeightOf<T>, AssetOf<T>>;
+type StatsOf<T> = types::Stats<BalanceOf<T>>;
+type TagOf<T> = BoundedVec<u8, <T as Config>::MaxTagLen>;
+type TargetingOf<T> = types::Targeting<
+    BoundedVec<LinkOf<T>, <T as Config>::MaxTargetingLen>,
+    HeightOf<T>,
+    BoundedVec<(TagOf<T>, i32), <T as Config>::MaxTargetingLen>,
+>;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -73,10 +118,75 @@ pub mod pallet {
         #[pallet::constant]
         type PayoutBase: Get<BalanceOf<Self>>;
 
+        /// The maximum length of each field of creatives
+        #[pallet::constant]
+        type MaxMetadataLen: Get<u32>;
+
         /// The maximum lifetime of a slot
         #[pallet::constant]
         type SlotLifetime: Get<HeightOf<Self>>;
 
+        /// The maximum number of concurrent slots of a KOL
+        #[pallet::constant]
+        type MaxSlotsPerKol: Get<u32>;
+
+        /// The maximum number of ongoing advertisements of an advertiser
+        #[pallet::constant]
+        type MaxAdsPerAdvertiser: Get<u32>;
+
+        /// The maximum number of slots of an advertisement
+        #[pallet::constant]
+        type MaxSlotsPerAd: Get<u32>;
+
+        /// The maximum number of sealed bids for slots of a KOL in a round
+        #[pallet::constant]
+        type MaxBidsPerRound: Get<u32>;
+
+        /// The maximum number of visitors paid in a batch
+        #[pallet::constant]
+        type MaxBatchSize: Get<u32>;
+
+        /// The maximum number of slots and advertisements expiring in a block
+        #[pallet::constant]
+        type MaxExpiredPerBlock: Get<u32>;
+
+        /// The maximum number of blocks an expiry is queued after its due height,
+        /// when queues of the heights before are full
+        #[pallet::constant]
+        type MaxExpiryDelay: Get<u32>;
+
+        /// Whether to extend deadlines of slots by paused time when resuming an advertisement
+        #[pallet::constant]
+        type ExtendSlotsOnResume: Get<bool>;
+
+        /// The maximum number of referer levels awarded in a payout
+        #[pallet::constant]
+        type MaxReferralLevels: Get<u32>;
+
+        /// The maximum length of a tag in targeting and slot rules
+        #[pallet::constant]
+        type MaxTagLen: Get<u32>;
+
+        /// The maximum number of tag scores, or of linked account types, in targeting
+        #[pallet::constant]
+        type MaxTargetingLen: Get<u32>;
+
+        /// The maximum number of accepted tags, or of blocked advertisers, in slot rules of a KOL
+        #[pallet::constant]
+        type MaxSlotConfigLen: Get<u32>;
+
+        /// The share of slot budgets paid to KOLs
+        #[pallet::constant]
+        type KolShare: Get<Percent>;
+
+        /// The share of slot budgets used to buy fragments of KOLs,
+        /// claimed by holders pro rata to the fragments they staked
+        #[pallet::constant]
+        type HolderShare: Get<Percent>;
+
+        /// The rules of bidding for slots
+        type BidPolicy: BidPolicy<Self>;
+
         /// The swaps trait
         type Swaps: Swaps<
             AccountId = AccountOf<Self>,
@@ -88,6 +198,15 @@ pub mod pallet {
         /// The means of storing the tags and tags of advertisement
         type Tags: Tags<DecentralizedId = DidOf<Self>, Hash = HashOf<Self>>;
 
+        /// The means of checking linked accounts of visitors
+        type Links: Links<DecentralizedId = DidOf<Self>>;
+
+        /// The signature of view receipts signed by visitors
+        type Signature: Parameter + Member + Verify<Signer = Self::Signer>;
+
+        /// The signer of view receipts, identifying the account of a visitor
+        type Signer: IdentifyAccount<AccountId = AccountOf<Self>>;
+
         /// The origin which may do calls
         type CallOrigin: EnsureOrigin<Self::Origin, Success = (DidOf<Self>, AccountOf<Self>)>;
 
@@ -100,6 +219,7 @@ pub mod pallet {
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::generate_storage_info]
     pub struct Pallet<T>(_);
 
     /// Metadata of an advertisement
@@ -110,7 +230,8 @@ pub mod pallet {
     /// Advertisement of an advertiser
     #[pallet::storage]
     #[pallet::getter(fn ads_of)]
-    pub(super) type AdsOf<T: Config> = StorageMap<_, Identity, DidOf<T>, Vec<HashOf<T>>>;
+    pub(super) type AdsOf<T: Config> =
+        StorageMap<_, Identity, DidOf<T>, BoundedVec<HashOf<T>, T::MaxAdsPerAdvertiser>>;
 
     /// End time of an advertisement
     #[pallet::storage]
@@ -129,15 +250,109 @@ pub mod pallet {
         HeightOf<T>,
     >;
 
-    /// Slot of a KOL
+    /// Slots expiring at a height
+    #[pallet::storage]
+    pub(super) type SlotExpiry<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        HeightOf<T>,
+        Identity,
+        (DidOf<T>, HashOf<T>), // (KOL DID, Advertisement ID)
+        (),
+    >;
+
+    /// Advertisements ending at a height
+    #[pallet::storage]
+    pub(super) type AdExpiry<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, HeightOf<T>, Identity, HashOf<T>, ()>;
+
+    /// Number of slots and advertisements expiring at a height
+    #[pallet::storage]
+    pub(super) type ExpiryCount<T: Config> =
+        StorageMap<_, Twox64Concat, HeightOf<T>, u32, ValueQuery>;
+
+    /// Last nonce of view receipts of a visitor for an advertisement
+    #[pallet::storage]
+    #[pallet::getter(fn nonce_of)]
+    pub(super) type NonceOf<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        HashOf<T>, // Advertisement ID
+        Identity,
+        DidOf<T>, // Visitor DID
+        u64,
+        ValueQuery,
+    >;
+
+    /// Nonce of advertisement IDs derived from an account
+    #[pallet::storage]
+    #[pallet::getter(fn id_nonce_of)]
+    pub(super) type IdNonceOf<T: Config> = StorageMap<_, Blake2_256, AccountOf<T>, u64, ValueQuery>;
+
+    /// Height when an advertisement was paused
+    #[pallet::storage]
+    #[pallet::getter(fn paused_at)]
+    pub(super) type PausedAt<T: Config> = StorageMap<_, Identity, HashOf<T>, HeightOf<T>>;
+
+    #[pallet::type_value]
+    pub(super) fn DefaultCapacity() -> u32 {
+        1
+    }
+
+    /// Number of concurrent slots offered by a KOL
+    #[pallet::storage]
+    #[pallet::getter(fn capacity_of)]
+    pub(super) type CapacityOf<T: Config> =
+        StorageMap<_, Identity, DidOf<T>, u32, ValueQuery, DefaultCapacity>;
+
+    /// Rules of a KOL on accepting bids for slots
+    #[pallet::storage]
+    #[pallet::getter(fn slot_config_of)]
+    pub(super) type SlotConfigOf<T: Config> = StorageMap<_, Identity, DidOf<T>, SlotConfOf<T>>;
+
+    /// Slots of a KOL, ranked by budget in descending order
     #[pallet::storage]
     #[pallet::getter(fn slot_of)]
-    pub(super) type SlotOf<T: Config> = StorageMap<_, Identity, DidOf<T>, SlotMetaOf<T>>;
+    pub(super) type SlotOf<T: Config> =
+        StorageMap<_, Identity, DidOf<T>, BoundedVec<SlotMetaOf<T>, T::MaxSlotsPerKol>, ValueQuery>;
 
     /// Slots of an advertisement
     #[pallet::storage]
     #[pallet::getter(fn slots_of)]
-    pub(super) type SlotsOf<T: Config> = StorageMap<_, Identity, HashOf<T>, Vec<DidOf<T>>>;
+    pub(super) type SlotsOf<T: Config> =
+        StorageMap<_, Identity, HashOf<T>, BoundedVec<DidOf<T>, T::MaxSlotsPerAd>>;
+
+    /// Fragments of a KOL staked to earn the holder share
+    #[pallet::storage]
+    #[pallet::getter(fn holder_pool_of)]
+    pub(super) type HolderPool<T: Config> =
+        StorageMap<_, Twox64Concat, AssetOf<T>, HolderPoolOf<T>, ValueQuery>;
+
+    /// Fragments of a KOL staked by a holder, with the share not claimed yet
+    #[pallet::storage]
+    #[pallet::getter(fn holding_of)]
+    pub(super) type Holding<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        AssetOf<T>, // NFT ID
+        Blake2_128Concat,
+        AccountOf<T>, // Holder Account
+        HoldingOf<T>,
+        ValueQuery,
+    >;
+
+    /// Tokens left in the pot of an advertisement by dropped slots
+    #[pallet::storage]
+    #[pallet::getter(fn unsold_of)]
+    pub(super) type UnsoldOf<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        HashOf<T>, // Advertisement ID
+        Twox64Concat,
+        AssetOf<T>, // NFT ID
+        BalanceOf<T>,
+        ValueQuery,
+    >;
 
     /// Payouts of an advertisement
     #[pallet::storage]
@@ -148,9 +363,56 @@ pub mod pallet {
         HashOf<T>,
         Identity,
         DidOf<T>, //
+        PayoutOf<T>,
+    >;
+
+    /// Slot (by its creation height) in which a visitor was last paid through a KOL
+    #[pallet::storage]
+    #[pallet::getter(fn slot_payout)]
+    pub(super) type SlotPayout<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        HashOf<T>,
+        Twox64Concat,
+        (DidOf<T>, DidOf<T>), // (KOL, visitor)
         HeightOf<T>,
     >;
 
+    /// Payout statistics of an advertisement
+    #[pallet::storage]
+    #[pallet::getter(fn stats)]
+    pub(super) type Stats<T: Config> = StorageMap<_, Identity, HashOf<T>, StatsOf<T>, ValueQuery>;
+
+    /// Payout statistics of an advertisement through a KOL
+    #[pallet::storage]
+    #[pallet::getter(fn slot_stats)]
+    pub(super) type SlotStats<T: Config> =
+        StorageDoubleMap<_, Identity, HashOf<T>, Identity, DidOf<T>, StatsOf<T>, ValueQuery>;
+
+    /// Start of current sealed-bid round of a KOL
+    #[pallet::storage]
+    #[pallet::getter(fn round_of)]
+    pub(super) type RoundOf<T: Config> = StorageMap<_, Identity, DidOf<T>, HeightOf<T>>;
+
+    /// Sealed bids for slots of a KOL in current round
+    #[pallet::storage]
+    #[pallet::getter(fn commitments_of)]
+    pub(super) type CommitmentsOf<T: Config> = StorageMap<
+        _,
+        Identity,
+        DidOf<T>, // Slot (KOL DID)
+        BoundedVec<CommitmentOf<T>, T::MaxBidsPerRound>,
+        ValueQuery,
+    >;
+
+    /// Storage version of the pallet
+    #[pallet::storage]
+    pub(crate) type StorageVersion<T: Config> = StorageValue<_, types::Releases, ValueQuery>;
+
+    /// Storage migration in progress across blocks, with the number of items skipped
+    #[pallet::storage]
+    pub(crate) type Migrating<T: Config> = StorageValue<_, (MigrationOf<T>, u32)>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -175,47 +437,172 @@ pub mod pallet {
         ),
         /// Swap Triggered \[id, kol, remain\]
         SwapTriggered(HashOf<T>, DidOf<T>, BalanceOf<T>),
+        /// Advertisement payout skipped \[id, visitor, reason\]
+        PaySkipped(HashOf<T>, DidOf<T>, types::PaySkipReason),
+        /// Advertisement terminated by advertiser
+        Terminated { ad: HashOf<T>, creator: DidOf<T> },
+        /// Number of slots of a KOL changed \[kol, capacity\]
+        CapacityChanged(DidOf<T>, u32),
+        /// Advertiser committed a sealed bid for slot \[kol, id\]
+        BidCommitted(DidOf<T>, HashOf<T>),
+        /// Advertisement paused \[id\]
+        Paused(HashOf<T>),
+        /// Advertisement resumed \[id, paused blocks\]
+        Resumed(HashOf<T>, HeightOf<T>),
+        /// Referer awarded from a payout \[id, referer, value\]
+        Awarded(HashOf<T>, DidOf<T>, BalanceOf<T>),
+        /// Remain of advertisement refunded to advertiser
+        Refunded {
+            ad: HashOf<T>,
+            creator: DidOf<T>,
+            amount: BalanceOf<T>,
+        },
+        /// Budget of advertisement moved into a slot of a KOL
+        SlotOpened {
+            ad: HashOf<T>,
+            kol: DidOf<T>,
+            nft: AssetOf<T>,
+            budget: BalanceOf<T>,
+        },
+        /// Budget of a slot swapped into tokens of the KOL
+        Swapped {
+            ad: HashOf<T>,
+            kol: DidOf<T>,
+            amount: BalanceOf<T>,
+            tokens: BalanceOf<T>,
+        },
+        /// Slot closed, tokens sold and remain returned to advertisement
+        SlotClosed {
+            ad: HashOf<T>,
+            kol: DidOf<T>,
+            tokens_sold: BalanceOf<T>,
+            currency_returned: BalanceOf<T>,
+            remain_returned: BalanceOf<T>,
+        },
+        /// Share of slot budget paid to KOL
+        KolShared {
+            ad: HashOf<T>,
+            kol: DidOf<T>,
+            amount: BalanceOf<T>,
+        },
+        /// Share of slot budget used to buy fragments for holders of KOL
+        HolderShared {
+            ad: HashOf<T>,
+            kol: DidOf<T>,
+            amount: BalanceOf<T>,
+            tokens: BalanceOf<T>,
+        },
+        /// Creative of advertisement updated \[id, revision\]
+        MetadataUpdated(HashOf<T>, u32),
+        /// Rules of slots of a KOL changed \[kol\]
+        SlotConfigured(DidOf<T>),
+        /// Advertisement evicted from slot by KOL \[kol, id\]
+        Evicted(DidOf<T>, HashOf<T>),
+        /// Slot closed at deadline while its tokens failed to be sold,
+        /// the tokens are kept in the pot of advertisement until it is refunded
+        SlotDropped {
+            ad: HashOf<T>,
+            kol: DidOf<T>,
+            tokens: BalanceOf<T>,
+        },
+        /// Advertiser revealed a sealed bid for slot \[kol, id, value\]
+        BidRevealed(DidOf<T>, HashOf<T>, BalanceOf<T>),
+        /// Sealed-bid round of a KOL settled \[kol, winner\]
+        BidSettled(DidOf<T>, Option<HashOf<T>>),
+        /// Tokens of dropped slots returned to advertiser
+        UnsoldRefunded {
+            ad: HashOf<T>,
+            creator: DidOf<T>,
+            nft: AssetOf<T>,
+            tokens: BalanceOf<T>,
+        },
+        /// Fragments staked by holder \[nft, who, amount\]
+        FragmentsStaked(AssetOf<T>, AccountOf<T>, BalanceOf<T>),
+        /// Fragments unstaked by holder \[nft, who, amount\]
+        FragmentsUnstaked(AssetOf<T>, AccountOf<T>, BalanceOf<T>),
+        /// Holder share claimed \[nft, who, amount\]
+        HolderShareClaimed(AssetOf<T>, AccountOf<T>, BalanceOf<T>),
+        /// Storage migration finished \[skipped\]
+        Migrated(u32),
     }
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
         fn on_initialize(n: HeightOf<T>) -> Weight {
-            Self::begin_block(n).unwrap_or_else(|e| {
-                sp_runtime::print(e);
-                0
-            })
+            migrations::step::<T>().saturating_add(Self::begin_block(n))
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            if <StorageVersion<T>>::get() == types::Releases::V1 {
+                return 0;
+            }
+
+            migrations::migrate::<T>()
         }
     }
 
     #[pallet::error]
     pub enum Error<T> {
+        AlreadyTerminated,
+        BelowReserve,
+        Blocked,
+        CapacityOutOfRange,
+        CommitClosed,
         Deadline,
         DidNotExists,
         EmptyTags,
+        EmptyVisitors,
+        ExpiryQueueFull,
         InsufficientBalance,
+        InsufficientFragments,
         InsufficientTokens,
+        InvalidCid,
+        InvalidCommitment,
+        InvalidReceipt,
+        NonceUsed,
         NotExists,
         NotMinted,
         NotOwned,
+        NotPaused,
+        NotSealed,
+        NotSettled,
         Paid,
+        Paused,
+        RevealClosed,
+        RevealOpen,
+        RewardSplitOutOfRange,
         ScoreOutOfRange,
+        Sealed,
+        SlotsFull,
+        TagNotAccepted,
         TagNotExists,
+        TargetMismatch,
+        TooManyAds,
+        TooManyBids,
+        TooManySlots,
+        TooManyVisitors,
         Underbid,
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        #[pallet::weight(<T as Config>::WeightInfo::create(
-            metadata.len() as u32,
-            tags.len() as u32
-        ))]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::create(
+                (creative.landing.len() + creative.cid.len() + creative.mime.len()) as u32,
+                tags.len() as u32 + targeting.as_ref().map_or(0, |t| t.scores.len() as u32)
+            )
+            .saturating_add(Pallet::<T>::expiry_weight(1))
+        )]
+        #[transactional]
         pub fn create(
             origin: OriginFor<T>,
             #[pallet::compact] budget: BalanceOf<T>,
             tags: Vec<Vec<u8>>,
-            metadata: Vec<u8>,
-            reward_rate: u16,
+            creative: CreativeOf<T>,
+            reward_split: RewardSplitOf<T>,
             deadline: HeightOf<T>,
+            targeting: Option<TargetingOf<T>>,
+            asset: Option<AssetOf<T>>,
         ) -> DispatchResult {
             let created = <frame_system::Pallet<T>>::block_number();
 
@@ -227,20 +614,34 @@ pub mod pallet {
                 ensure!(T::Tags::exists(tag), Error::<T>::TagNotExists);
             }
 
+            Self::ensure_targeting(&targeting)?;
+
+            Self::ensure_reward_split(&reward_split)?;
+
+            Self::ensure_creative(&creative)?;
+
+            let mut ads = <AdsOf<T>>::get(&creator).unwrap_or_default().into_inner();
+            ensure!(
+                (ads.len() as u32) < T::MaxAdsPerAdvertiser::get(),
+                Error::<T>::TooManyAds
+            );
+
             // 1. derive deposit poll account and advertisement ID
 
-            // TODO: use a HMAC-based algorithm.
-            let mut raw = <AccountOf<T>>::encode(&who);
-            let mut ord = T::BlockNumber::encode(&created);
-            raw.append(&mut ord);
+            let nonce = Self::id_nonce_of(&who);
+            <IdNonceOf<T>>::insert(&who, nonce.saturating_add(1));
 
-            let id = <T as frame_system::Config>::Hashing::hash(&raw);
+            let id = derive::id::<<T as frame_system::Config>::Hashing, _>(
+                &<T as Config>::PalletId::get().0,
+                &who,
+                nonce,
+            );
 
             let pot = <T as Config>::PalletId::get().into_sub_account(&id);
 
             // 2. deposit budget
 
-            <T as parami_did::Config>::Currency::transfer(&who, &pot, budget, KeepAlive)?;
+            Self::transfer_budget(asset, &who, &pot, budget, true)?;
 
             // 3. insert metadata, ads_of, tags_of
 
@@ -252,21 +653,23 @@ pub mod pallet {
                     pot,
                     budget,
                     remain: budget,
-                    metadata,
-                    reward_rate,
+                    creative,
+                    revision: 0,
+                    reward_split,
                     created,
+                    targeting,
+                    frequency: Default::default(),
+                    asset,
+                    status: types::AdStatus::Active,
                 },
             );
 
-            <EndtimeOf<T>>::insert(&id, deadline);
+            Self::schedule_ad(&id, deadline)?;
 
-            <AdsOf<T>>::mutate(&creator, |maybe| {
-                if let Some(ads) = maybe {
-                    ads.push(id);
-                } else {
-                    *maybe = Some(vec![id]);
-                }
-            });
+            ads.push(id);
+            let ads: BoundedVec<_, T::MaxAdsPerAdvertiser> =
+                ads.try_into().map_err(|_| Error::<T>::TooManyAds)?;
+            <AdsOf<T>>::insert(&creator, ads);
 
             for tag in tags {
                 T::Tags::add_tag(&id, tag)?;
@@ -278,11 +681,11 @@ pub mod pallet {
             Ok(())
         }
 
-        #[pallet::weight(<T as Config>::WeightInfo::update_reward_rate())]
-        pub fn update_reward_rate(
+        #[pallet::weight(<T as Config>::WeightInfo::update_reward_split())]
+        pub fn update_reward_split(
             origin: OriginFor<T>,
             ad: HashOf<T>,
-            reward_rate: u16,
+            reward_split: RewardSplitOf<T>,
         ) -> DispatchResult {
             let (did, _) = T::CallOrigin::ensure_origin(origin)?;
 
@@ -293,7 +696,9 @@ pub mod pallet {
 
             let mut meta = Self::ensure_owned(did, ad)?;
 
-            meta.reward_rate = reward_rate;
+            Self::ensure_reward_split(&reward_split)?;
+
+            meta.reward_split = reward_split;
 
             <Metadata<T>>::insert(&ad, meta);
 
@@ -331,6 +736,58 @@ pub mod pallet {
             Ok(())
         }
 
+        #[pallet::weight(<T as Config>::WeightInfo::update_targeting(
+            targeting.as_ref().map_or(0, |t| t.scores.len() as u32)
+        ))]
+        pub fn update_targeting(
+            origin: OriginFor<T>,
+            ad: HashOf<T>,
+            targeting: Option<TargetingOf<T>>,
+        ) -> DispatchResult {
+            let (did, _) = T::CallOrigin::ensure_origin(origin)?;
+
+            let height = <frame_system::Pallet<T>>::block_number();
+
+            let endtime = <EndtimeOf<T>>::get(&ad).ok_or(Error::<T>::NotExists)?;
+            ensure!(endtime > height, Error::<T>::Deadline);
+
+            let mut meta = Self::ensure_owned(did, ad)?;
+
+            Self::ensure_targeting(&targeting)?;
+
+            meta.targeting = targeting;
+
+            <Metadata<T>>::insert(&ad, meta);
+
+            Self::deposit_event(Event::Updated(ad));
+
+            Ok(())
+        }
+
+        #[pallet::weight(<T as Config>::WeightInfo::update_frequency())]
+        pub fn update_frequency(
+            origin: OriginFor<T>,
+            ad: HashOf<T>,
+            frequency: types::Frequency<HeightOf<T>>,
+        ) -> DispatchResult {
+            let (did, _) = T::CallOrigin::ensure_origin(origin)?;
+
+            let height = <frame_system::Pallet<T>>::block_number();
+
+            let endtime = <EndtimeOf<T>>::get(&ad).ok_or(Error::<T>::NotExists)?;
+            ensure!(endtime > height, Error::<T>::Deadline);
+
+            let mut meta = Self::ensure_owned(did, ad)?;
+
+            meta.frequency = frequency;
+
+            <Metadata<T>>::insert(&ad, meta);
+
+            Self::deposit_event(Event::Updated(ad));
+
+            Ok(())
+        }
+
         #[pallet::weight(<T as Config>::WeightInfo::add_budget())]
         pub fn add_budget(
             origin: OriginFor<T>,
@@ -346,7 +803,7 @@ pub mod pallet {
 
             let mut meta = Self::ensure_owned(did, ad)?;
 
-            <T as parami_did::Config>::Currency::transfer(&who, &meta.pot, value, KeepAlive)?;
+            Self::transfer_budget(meta.asset, &who, &meta.pot, value, true)?;
 
             meta.budget.saturating_accrue(value);
             meta.remain.saturating_accrue(value);
@@ -358,7 +815,9 @@ pub mod pallet {
             Ok(())
         }
 
-        #[pallet::weight(<T as Config>::WeightInfo::bid())]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::bid().saturating_add(Pallet::<T>::expiry_weight(1))
+        )]
         pub fn bid(
             origin: OriginFor<T>,
             ad: HashOf<T>,
@@ -367,83 +826,153 @@ pub mod pallet {
         ) -> DispatchResult {
             let (did, _) = T::CallOrigin::ensure_origin(origin)?;
 
+            ensure!(T::BidPolicy::sealed().is_none(), Error::<T>::Sealed);
+
+            Self::place_bid(did, ad, kol, value)
+        }
+
+        #[pallet::weight(<T as Config>::WeightInfo::commit_bid())]
+        #[transactional]
+        pub fn commit_bid(
+            origin: OriginFor<T>,
+            ad: HashOf<T>,
+            kol: DidOf<T>,
+            commitment: HashOf<T>,
+        ) -> DispatchResult {
+            let (did, _) = T::CallOrigin::ensure_origin(origin)?;
+
+            let (commit, reveal) = T::BidPolicy::sealed().ok_or(Error::<T>::NotSealed)?;
+
             let height = <frame_system::Pallet<T>>::block_number();
 
             let endtime = <EndtimeOf<T>>::get(&ad).ok_or(Error::<T>::NotExists)?;
             ensure!(endtime > height, Error::<T>::Deadline);
 
-            let mut meta = Self::ensure_owned(did, ad)?;
+            let _ = Self::ensure_owned(did, ad)?;
 
-            ensure!(meta.remain >= value, Error::<T>::InsufficientBalance);
+            // 1. start a new round if there is none in progress
+            // bids revealed in a finished round must be settled first
 
-            let kol_meta = Did::<T>::meta(&kol).ok_or(Error::<T>::NotMinted)?;
-            let nft = kol_meta.nft.ok_or(Error::<T>::NotMinted)?;
+            let round = match <RoundOf<T>>::get(&kol) {
+                Some(start) if height < start.saturating_add(commit).saturating_add(reveal) => {
+                    start
+                }
+                _ => {
+                    ensure!(
+                        <CommitmentsOf<T>>::get(&kol)
+                            .iter()
+                            .all(|c| c.value.is_none()),
+                        Error::<T>::NotSettled
+                    );
 
-            let created = <frame_system::Pallet<T>>::block_number();
+                    <CommitmentsOf<T>>::remove(&kol);
 
-            // 1. check slot of kol
+                    <RoundOf<T>>::insert(&kol, height);
 
-            let slot = <SlotOf<T>>::get(&kol);
+                    height
+                }
+            };
 
-            // 2. if slot is used
-            // require a 20% increase of current budget
-            // and drawback current ad
+            ensure!(
+                height < round.saturating_add(commit),
+                Error::<T>::CommitClosed
+            );
 
-            if let Some(slot) = slot {
-                let quote = T::Swaps::token_in_dry(slot.nft, slot.tokens)?;
-                let remain = slot.remain.saturating_add(quote);
+            // 2. store commitment, a previous one of this ad is replaced
 
-                ensure!(
-                    value.saturating_mul(100u32.into()) / 120u32.into() > remain,
-                    Error::<T>::Underbid
-                );
+            <CommitmentsOf<T>>::try_mutate(&kol, |commitments| -> DispatchResult {
+                let commitment = types::Commitment {
+                    did,
+                    ad,
+                    hash: commitment,
+                    value: None,
+                };
 
-                let _ = Self::drawback(&kol, &slot)?;
-            }
+                match commitments.iter_mut().find(|c| c.ad == ad) {
+                    Some(c) => *c = commitment,
+                    None => commitments
+                        .try_push(commitment)
+                        .map_err(|_| Error::<T>::TooManyBids)?,
+                }
 
-            // 3. update slot
+                Ok(())
+            })?;
 
-            let lifetime = T::SlotLifetime::get();
-            let slotlife = created.saturating_add(lifetime);
-            let deadline = if slotlife > endtime {
-                endtime
-            } else {
-                slotlife
-            };
+            Self::deposit_event(Event::BidCommitted(kol, ad));
 
-            let mut slot = types::Slot {
-                nft,
-                budget: value,
-                remain: value,
-                tokens: Zero::zero(),
-                created,
-                ad,
-            };
+            Ok(())
+        }
 
-            Self::swap_by_10percent(kol, &meta, &mut slot, One::one())?;
+        #[pallet::weight(<T as Config>::WeightInfo::reveal_bid())]
+        pub fn reveal_bid(
+            origin: OriginFor<T>,
+            ad: HashOf<T>,
+            kol: DidOf<T>,
+            #[pallet::compact] value: BalanceOf<T>,
+            salt: Vec<u8>,
+        ) -> DispatchResult {
+            let (did, _) = T::CallOrigin::ensure_origin(origin)?;
 
-            <SlotOf<T>>::insert(&kol, &slot);
+            let (commit, reveal) = T::BidPolicy::sealed().ok_or(Error::<T>::NotSealed)?;
 
-            <DeadlineOf<T>>::insert(&kol, &ad, deadline);
+            let height = <frame_system::Pallet<T>>::block_number();
 
-            meta.remain.saturating_reduce(value);
+            let round = <RoundOf<T>>::get(&kol).ok_or(Error::<T>::NotExists)?;
 
-            <Metadata<T>>::insert(&ad, &meta);
+            let opening = round.saturating_add(commit);
+            ensure!(
+                height >= opening && height < opening.saturating_add(reveal),
+                Error::<T>::RevealClosed
+            );
 
-            <SlotsOf<T>>::mutate(&ad, |maybe| {
-                if let Some(slots) = maybe {
-                    slots.push(kol);
-                } else {
-                    *maybe = Some(vec![kol]);
-                }
-            });
+            let hash = <T as frame_system::Config>::Hashing::hash_of(&(did, ad, kol, value, salt));
+
+            // bids are only placed when the round is settled
+            <CommitmentsOf<T>>::try_mutate(&kol, |commitments| -> DispatchResult {
+                let commitment = commitments
+                    .iter_mut()
+                    .find(|c| c.ad == ad)
+                    .ok_or(Error::<T>::NotExists)?;
+
+                ensure!(
+                    commitment.did == did && commitment.hash == hash,
+                    Error::<T>::InvalidCommitment
+                );
+
+                commitment.value = Some(value);
+
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::BidRevealed(kol, ad, value));
+
+            Ok(())
+        }
+
+        #[pallet::weight(
+            <T as Config>::WeightInfo::settle_bid(T::MaxBidsPerRound::get())
+                .saturating_add(Pallet::<T>::expiry_weight(T::MaxBidsPerRound::get()))
+        )]
+        pub fn settle_bid(origin: OriginFor<T>, kol: DidOf<T>) -> DispatchResult {
+            let _ = T::CallOrigin::ensure_origin(origin)?;
+
+            let (commit, reveal) = T::BidPolicy::sealed().ok_or(Error::<T>::NotSealed)?;
+
+            let height = <frame_system::Pallet<T>>::block_number();
+
+            let round = <RoundOf<T>>::get(&kol).ok_or(Error::<T>::NotExists)?;
+            ensure!(
+                height >= round.saturating_add(commit).saturating_add(reveal),
+                Error::<T>::RevealOpen
+            );
 
-            Self::deposit_event(Event::Bid(kol, ad, value));
+            Self::settle_round(kol);
 
             Ok(())
         }
 
         #[pallet::weight(<T as Config>::WeightInfo::pay(scores.len() as u32))]
+        #[transactional]
         pub fn pay(
             origin: OriginFor<T>,
             ad: HashOf<T>,
@@ -451,6 +980,7 @@ pub mod pallet {
             visitor: DidOf<T>,
             scores: Vec<(Vec<u8>, i8)>,
             referer: Option<DidOf<T>>,
+            receipt: ReceiptOf<T>,
         ) -> DispatchResult {
             ensure!(!scores.is_empty(), Error::<T>::EmptyTags);
 
@@ -458,45 +988,30 @@ pub mod pallet {
 
             let height = <frame_system::Pallet<T>>::block_number();
 
-            let endtime = <EndtimeOf<T>>::get(&ad).ok_or(Error::<T>::NotExists)?;
-            ensure!(endtime > height, Error::<T>::Deadline);
-
-            let deadline = <DeadlineOf<T>>::get(&kol, &ad).ok_or(Error::<T>::NotExists)?;
-            ensure!(deadline > height, Error::<T>::Deadline);
+            let (meta, mut slot) = Self::ensure_payable(did, ad, kol, height)?;
 
-            let meta = Self::ensure_owned(did, ad)?;
-
-            ensure!(!<Payout<T>>::contains_key(&ad, &visitor), Error::<T>::Paid);
-
-            // 1. get slot, check current ad
-            let mut slot = <SlotOf<T>>::get(&kol).ok_or(Error::<T>::NotExists)?;
-            ensure!(slot.ad == ad, Error::<T>::Underbid);
+            ensure!(
+                Self::is_payable(&meta, &kol, &slot, &visitor, height),
+                Error::<T>::Paid
+            );
 
-            // 2. scoring visitor
+            Self::ensure_scores(&ad, &scores)?;
 
-            let mut socring = 5i32;
+            ensure!(
+                Self::is_targeted(&meta, &visitor, height),
+                Error::<T>::TargetMismatch
+            );
 
-            let tags = T::Tags::tags_of(&ad);
-            let personas = T::Tags::personas_of(&visitor);
-            let length = personas.len();
-            for (tag, score) in personas {
-                let delta = if tags.contains_key(&tag) {
-                    score.saturating_mul(10)
-                } else {
-                    score
-                };
-                socring.saturating_accrue(delta);
-            }
+            let account =
+                Self::ensure_receipt(&ad, &kol, &visitor, referer, &scores, &receipt, height)?;
 
-            socring /= length.saturating_mul(10).saturating_add(1) as i32;
+            let referers = Self::referers(&meta, &visitor, referer)?;
 
-            if socring < 0 {
-                socring = 0;
-            }
+            // 1. scoring visitor
 
-            let socring = socring as u32;
+            let scoring = Self::calculate_score(&ad, &visitor);
 
-            let amount = T::PayoutBase::get().saturating_mul(socring.into());
+            let amount = T::PayoutBase::get().saturating_mul(scoring.into());
 
             if slot.tokens < amount {
                 // if tokens is not enough, swap tokens
@@ -504,199 +1019,1260 @@ pub mod pallet {
                 // swap 10% of current budget, at least cover current payout
                 Self::swap_by_10percent(kol, &meta, &mut slot, amount)?;
 
-                <SlotOf<T>>::insert(&kol, &slot);
+                Self::update_slot(&kol, &slot);
             }
 
             ensure!(slot.tokens >= amount, Error::<T>::InsufficientTokens);
 
-            // 3. influence visitor
-
-            for (tag, score) in scores {
-                ensure!(T::Tags::has_tag(&ad, &tag), Error::<T>::TagNotExists);
-                ensure!(score >= -5 && score <= 5, Error::<T>::ScoreOutOfRange);
-
-                T::Tags::influence(&visitor, &tag, score as i32)?;
-            }
-
-            // 4. payout assets
+            // 2. influence visitor
 
-            let account = Did::<T>::lookup_did(visitor).ok_or(Error::<T>::DidNotExists)?;
+            Self::influence(&visitor, scores)?;
 
-            let award = if let Some(referer) = referer {
-                let rate = meta.reward_rate.into();
-                let award = amount.saturating_mul(rate) / 100u32.into();
+            // 3. payout assets
 
-                let referer = Did::<T>::lookup_did(referer).ok_or(Error::<T>::DidNotExists)?;
+            let (reward, award) =
+                Self::transfer_payout(&meta, slot.nft, &account, &referers, amount)?;
 
-                T::Assets::transfer(slot.nft, &meta.pot, &referer, award, false)?;
-
-                award
-            } else {
-                Zero::zero()
-            };
+            slot.tokens.saturating_reduce(amount);
 
-            let reward = amount.saturating_sub(award);
+            Self::update_slot(&kol, &slot);
 
-            T::Assets::transfer(slot.nft, &meta.pot, &account, reward, false)?;
+            Self::record_payout(&meta, &kol, &slot, &visitor, height);
 
-            slot.tokens.saturating_reduce(amount);
+            Self::record_stats(
+                &ad,
+                &kol,
+                &types::Stats {
+                    paid: reward,
+                    visitors: 1,
+                    awarded: award,
+                    ..Default::default()
+                },
+            );
 
-            <SlotOf<T>>::insert(&kol, &slot);
+            <NonceOf<T>>::insert(&ad, &visitor, receipt.nonce);
 
-            <Payout<T>>::insert(&ad, &visitor, height);
+            let referer = referers.first().map(|(did, _, _)| *did);
 
             Self::deposit_event(Event::Paid(ad, slot.nft, visitor, reward, referer, award));
 
-            // 5. drawback if advertiser does not have enough fees
-
-            let controller = match Magic::<T>::controller(&who) {
-                Some(c) => c,
-                None => who,
-            };
+            // 4. drawback if advertiser does not have enough fees
 
-            let balance = <T as parami_did::Config>::Currency::free_balance(&controller);
-            if balance - <T as parami_did::Config>::Currency::minimum_balance()
-                < T::MinimumFeeBalance::get()
-            {
-                let _ = Self::drawback(&kol, &slot);
-            }
+            Self::ensure_fee_balance(who, &kol, &slot);
 
             Ok(())
         }
-    }
-
-    #[pallet::genesis_config]
-    pub struct GenesisConfig {}
 
-    #[cfg(feature = "std")]
-    impl Default for GenesisConfig {
+        #[pallet::weight(<T as Config>::WeightInfo::pay_batch(
+            items.len() as u32,
+            items.iter().map(|item| item.scores.len() as u32).sum()
+        ))]
+        #[transactional]
+        pub fn pay_batch(
+            origin: OriginFor<T>,
+            ad: HashOf<T>,
+            kol: DidOf<T>,
+            items: Vec<types::PayItem<DidOf<T>, ReceiptOf<T>>>,
+        ) -> DispatchResult {
+            ensure!(!items.is_empty(), Error::<T>::EmptyVisitors);
+            ensure!(
+                items.len() <= T::MaxBatchSize::get() as usize,
+                Error::<T>::TooManyVisitors
+            );
+
+            let (did, who) = T::CallOrigin::ensure_origin(origin)?;
+
+            let height = <frame_system::Pallet<T>>::block_number();
+
+            let (meta, mut slot) = Self::ensure_payable(did, ad, kol, height)?;
+
+            // 1. filter and score visitors
+
+            let mut visited = BTreeSet::new();
+            let mut payable = Vec::new();
+            let mut total: BalanceOf<T> = Zero::zero();
+
+            for item in items {
+                if !Self::is_payable(&meta, &kol, &slot, &item.visitor, height)
+                    || visited.contains(&item.visitor)
+                {
+                    Self::deposit_event(Event::PaySkipped(
+                        ad,
+                        item.visitor,
+                        types::PaySkipReason::Paid,
+                    ));
+                    continue;
+                }
+
+                if item.scores.is_empty() || Self::ensure_scores(&ad, &item.scores).is_err() {
+                    Self::deposit_event(Event::PaySkipped(
+                        ad,
+                        item.visitor,
+                        types::PaySkipReason::InvalidScores,
+                    ));
+                    continue;
+                }
+
+                let account = match Self::ensure_receipt(
+                    &ad,
+                    &kol,
+                    &item.visitor,
+                    item.referer,
+                    &item.scores,
+                    &item.receipt,
+                    height,
+                ) {
+                    Ok(account) => account,
+                    Err(e) => {
+                        let reason = if e == DispatchError::from(Error::<T>::DidNotExists) {
+                            types::PaySkipReason::DidNotExists
+                        } else {
+                            types::PaySkipReason::InvalidReceipt
+                        };
+
+                        Self::deposit_event(Event::PaySkipped(ad, item.visitor, reason));
+                        continue;
+                    }
+                };
+
+                if !Self::is_targeted(&meta, &item.visitor, height) {
+                    Self::deposit_event(Event::PaySkipped(
+                        ad,
+                        item.visitor,
+                        types::PaySkipReason::TargetMismatch,
+                    ));
+                    continue;
+                }
+
+                let referers = match Self::referers(&meta, &item.visitor, item.referer) {
+                    Ok(referers) => referers,
+                    Err(_) => {
+                        Self::deposit_event(Event::PaySkipped(
+                            ad,
+                            item.visitor,
+                            types::PaySkipReason::DidNotExists,
+                        ));
+                        continue;
+                    }
+                };
+
+                let scoring = Self::calculate_score(&ad, &item.visitor);
+
+                let amount = T::PayoutBase::get().saturating_mul(scoring.into());
+
+                total.saturating_accrue(amount);
+
+                visited.insert(item.visitor);
+                payable.push((item, account, referers, amount));
+            }
+
+            // 2. swap tokens once for the whole batch
+
+            if slot.tokens < total {
+                let least = total.saturating_sub(slot.tokens);
+
+                Self::swap_by_10percent(kol, &meta, &mut slot, least)?;
+            }
+
+            ensure!(slot.tokens >= total, Error::<T>::InsufficientTokens);
+
+            // 3. influence visitors and payout assets
+
+            let mut stats: StatsOf<T> = Default::default();
+
+            for (item, account, referers, amount) in payable {
+                if Self::influence(&item.visitor, item.scores).is_err() {
+                    Self::deposit_event(Event::PaySkipped(
+                        ad,
+                        item.visitor,
+                        types::PaySkipReason::InfluenceFailed,
+                    ));
+                    continue;
+                }
+
+                let (reward, award) =
+                    Self::transfer_payout(&meta, slot.nft, &account, &referers, amount)?;
+
+                Self::record_payout(&meta, &kol, &slot, &item.visitor, height);
+
+                stats.paid.saturating_accrue(reward);
+                stats.visitors.saturating_accrue(1);
+                stats.awarded.saturating_accrue(award);
+
+                <NonceOf<T>>::insert(&ad, &item.visitor, item.receipt.nonce);
+
+                Self::deposit_event(Event::Paid(
+                    ad,
+                    slot.nft,
+                    item.visitor,
+                    reward,
+                    referers.first().map(|(did, _, _)| *did),
+                    award,
+                ));
+            }
+
+            slot.tokens.saturating_reduce(total);
+
+            Self::update_slot(&kol, &slot);
+
+            Self::record_stats(&ad, &kol, &stats);
+
+            // 4. drawback if advertiser does not have enough fees
+
+            Self::ensure_fee_balance(who, &kol, &slot);
+
+            Ok(())
+        }
+
+        #[pallet::weight(<T as Config>::WeightInfo::terminate(T::MaxSlotsPerAd::get()))]
+        #[transactional]
+        pub fn terminate(origin: OriginFor<T>, ad: HashOf<T>) -> DispatchResultWithPostInfo {
+            let (did, _) = T::CallOrigin::ensure_origin(origin)?;
+
+            let meta = Self::ensure_owned(did, ad)?;
+
+            ensure!(
+                matches!(
+                    meta.status,
+                    types::AdStatus::Active | types::AdStatus::Paused
+                ),
+                Error::<T>::AlreadyTerminated
+            );
+
+            // 1. drawback all slots
+            // a slot is closed even if its tokens failed to be sold
+
+            let slots = <SlotsOf<T>>::get(&ad).unwrap_or_default();
+            let count = slots.len() as u32;
+            for kol in slots {
+                if let Some(slot) = Self::find_slot(&kol, &ad) {
+                    if Self::drawback(&kol, &slot).is_err() {
+                        Self::drop_slot(&kol, &slot);
+                    }
+                }
+
+                Self::unschedule_slot(&kol, &ad);
+            }
+
+            // 2. refund remain to creator
+
+            let mut meta = <Metadata<T>>::get(&ad).ok_or(Error::<T>::NotExists)?;
+
+            let creator = Did::<T>::meta(&meta.creator).ok_or(Error::<T>::DidNotExists)?;
+
+            Self::refund_unsold(&meta, &creator.account);
+
+            let refunded = meta.remain;
+
+            Self::transfer_budget(meta.asset, &meta.pot, &creator.account, refunded, false)?;
+
+            // 3. clean up
+
+            T::Tags::clr_tag(&ad)?;
+
+            Self::unschedule_ad(&ad);
+
+            meta.remain = Zero::zero();
+            meta.status = types::AdStatus::Terminated;
+
+            <Metadata<T>>::insert(&ad, &meta);
+            <SlotsOf<T>>::remove(&ad);
+            <PausedAt<T>>::remove(&ad);
+
+            Self::unlist_ad(&meta.creator, &ad);
+
+            Self::deposit_event(Event::Refunded {
+                ad,
+                creator: meta.creator,
+                amount: refunded,
+            });
+            Self::deposit_event(Event::Terminated {
+                ad,
+                creator: meta.creator,
+            });
+
+            Ok(Some(<T as Config>::WeightInfo::terminate(count)).into())
+        }
+
+        #[pallet::weight(<T as Config>::WeightInfo::set_capacity())]
+        pub fn set_capacity(origin: OriginFor<T>, capacity: u32) -> DispatchResult {
+            let (kol, _) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            ensure!(
+                capacity > 0 && capacity <= T::MaxSlotsPerKol::get(),
+                Error::<T>::CapacityOutOfRange
+            );
+
+            <CapacityOf<T>>::insert(&kol, capacity);
+
+            Self::deposit_event(Event::CapacityChanged(kol, capacity));
+
+            Ok(())
+        }
+
+        #[pallet::weight(<T as Config>::WeightInfo::pause())]
+        pub fn pause(origin: OriginFor<T>, ad: HashOf<T>) -> DispatchResult {
+            let (did, _) = T::CallOrigin::ensure_origin(origin)?;
+
+            let height = <frame_system::Pallet<T>>::block_number();
+
+            let mut meta = Self::ensure_owned(did, ad)?;
+
+            Self::ensure_active(&meta)?;
+
+            meta.status = types::AdStatus::Paused;
+
+            <Metadata<T>>::insert(&ad, meta);
+            <PausedAt<T>>::insert(&ad, height);
+
+            Self::deposit_event(Event::Paused(ad));
+
+            Ok(())
+        }
+
+        #[pallet::weight(
+            <T as Config>::WeightInfo::resume()
+                .saturating_add(Pallet::<T>::expiry_weight(T::MaxSlotsPerAd::get()))
+        )]
+        #[transactional]
+        pub fn resume(origin: OriginFor<T>, ad: HashOf<T>) -> DispatchResult {
+            let (did, _) = T::CallOrigin::ensure_origin(origin)?;
+
+            let height = <frame_system::Pallet<T>>::block_number();
+
+            let mut meta = Self::ensure_owned(did, ad)?;
+
+            ensure!(
+                meta.status == types::AdStatus::Paused,
+                Error::<T>::NotPaused
+            );
+
+            let paused = <PausedAt<T>>::take(&ad).unwrap_or(height);
+            let paused = height.saturating_sub(paused);
+
+            // 1. extend slots by paused time, up to end time of the advertisement
+
+            if T::ExtendSlotsOnResume::get() && !paused.is_zero() {
+                let endtime = <EndtimeOf<T>>::get(&ad).ok_or(Error::<T>::NotExists)?;
+
+                let slots = <SlotsOf<T>>::get(&ad).unwrap_or_default();
+                for kol in slots {
+                    let deadline = match <DeadlineOf<T>>::get(&kol, &ad) {
+                        Some(deadline) => deadline,
+                        None => continue,
+                    };
+
+                    let extended = deadline.saturating_add(paused);
+                    let extended = if extended > endtime {
+                        endtime
+                    } else {
+                        extended
+                    };
+
+                    if extended > deadline {
+                        Self::unschedule_slot(&kol, &ad);
+                        Self::schedule_slot(&kol, &ad, extended)?;
+                    }
+                }
+            }
+
+            // 2. activate
+
+            meta.status = types::AdStatus::Active;
+
+            <Metadata<T>>::insert(&ad, meta);
+
+            Self::deposit_event(Event::Resumed(ad, paused));
+
+            Ok(())
+        }
+
+        #[pallet::weight(<T as Config>::WeightInfo::set_slot_config(
+            (config.tags.len() + config.blocked.len()) as u32
+        ))]
+        pub fn set_slot_config(origin: OriginFor<T>, config: SlotConfOf<T>) -> DispatchResult {
+            let (kol, _) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            for tag in config.tags.iter() {
+                ensure!(T::Tags::exists(tag), Error::<T>::TagNotExists);
+            }
+
+            <SlotConfigOf<T>>::insert(&kol, config);
+
+            Self::deposit_event(Event::SlotConfigured(kol));
+
+            Ok(())
+        }
+
+        #[pallet::weight(<T as Config>::WeightInfo::evict())]
+        pub fn evict(origin: OriginFor<T>, ad: HashOf<T>) -> DispatchResult {
+            let (kol, _) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let slot = Self::find_slot(&kol, &ad).ok_or(Error::<T>::NotExists)?;
+
+            Self::drawback(&kol, &slot)?;
+
+            Self::deposit_event(Event::Evicted(kol, ad));
+
+            Ok(())
+        }
+
+        #[pallet::weight(<T as Config>::WeightInfo::update_metadata(
+            (creative.landing.len() + creative.cid.len() + creative.mime.len()) as u32
+        ))]
+        pub fn update_metadata(
+            origin: OriginFor<T>,
+            ad: HashOf<T>,
+            creative: CreativeOf<T>,
+        ) -> DispatchResult {
+            let (did, _) = T::CallOrigin::ensure_origin(origin)?;
+
+            let height = <frame_system::Pallet<T>>::block_number();
+
+            let endtime = <EndtimeOf<T>>::get(&ad).ok_or(Error::<T>::NotExists)?;
+            ensure!(endtime > height, Error::<T>::Deadline);
+
+            let mut meta = Self::ensure_owned(did, ad)?;
+
+            Self::ensure_creative(&creative)?;
+
+            meta.creative = creative;
+            meta.revision = meta.revision.saturating_add(1);
+
+            let revision = meta.revision;
+
+            <Metadata<T>>::insert(&ad, meta);
+
+            Self::deposit_event(Event::MetadataUpdated(ad, revision));
+
+            Ok(())
+        }
+
+        #[pallet::weight(<T as Config>::WeightInfo::stake_fragments())]
+        #[transactional]
+        pub fn stake_fragments(
+            origin: OriginFor<T>,
+            nft: AssetOf<T>,
+            #[pallet::compact] amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut pool = <HolderPool<T>>::get(nft);
+            let mut holding = <Holding<T>>::get(nft, &who);
+
+            Self::settle_holding(&pool, &mut holding);
+
+            T::Assets::transfer(nft, &who, &Self::holders_account(nft), amount, false)?;
+
+            pool.staked.saturating_accrue(amount);
+            holding.staked.saturating_accrue(amount);
+
+            <HolderPool<T>>::insert(nft, pool);
+            <Holding<T>>::insert(nft, &who, holding);
+
+            Self::deposit_event(Event::FragmentsStaked(nft, who, amount));
+
+            Ok(())
+        }
+
+        #[pallet::weight(<T as Config>::WeightInfo::unstake_fragments())]
+        #[transactional]
+        pub fn unstake_fragments(
+            origin: OriginFor<T>,
+            nft: AssetOf<T>,
+            #[pallet::compact] amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut pool = <HolderPool<T>>::get(nft);
+            let mut holding = <Holding<T>>::get(nft, &who);
+
+            ensure!(holding.staked >= amount, Error::<T>::InsufficientFragments);
+
+            Self::settle_holding(&pool, &mut holding);
+
+            T::Assets::transfer(nft, &Self::holders_account(nft), &who, amount, false)?;
+
+            pool.staked.saturating_reduce(amount);
+            holding.staked.saturating_reduce(amount);
+
+            <HolderPool<T>>::insert(nft, pool);
+            <Holding<T>>::insert(nft, &who, holding);
+
+            Self::deposit_event(Event::FragmentsUnstaked(nft, who, amount));
+
+            Ok(())
+        }
+
+        #[pallet::weight(<T as Config>::WeightInfo::claim_holder_share())]
+        #[transactional]
+        pub fn claim_holder_share(origin: OriginFor<T>, nft: AssetOf<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let pool = <HolderPool<T>>::get(nft);
+            let mut holding = <Holding<T>>::get(nft, &who);
+
+            Self::settle_holding(&pool, &mut holding);
+
+            let amount = holding.reward;
+
+            T::Assets::transfer(nft, &Self::holders_account(nft), &who, amount, false)?;
+
+            holding.reward = Zero::zero();
+
+            if holding.staked.is_zero() {
+                <Holding<T>>::remove(nft, &who);
+            } else {
+                <Holding<T>>::insert(nft, &who, holding);
+            }
+
+            Self::deposit_event(Event::HolderShareClaimed(nft, who, amount));
+
+            Ok(())
+        }
+    }
+
+    #[pallet::genesis_config]
+    pub struct GenesisConfig {}
+
+    #[cfg(feature = "std")]
+    impl Default for GenesisConfig {
         fn default() -> Self {
             Self {}
         }
     }
 
-    #[pallet::genesis_build]
-    impl<T: Config> GenesisBuild<T> for GenesisConfig {
-        fn build(&self) {}
+    #[pallet::genesis_build]
+    impl<T: Config> GenesisBuild<T> for GenesisConfig {
+        fn build(&self) {
+            <StorageVersion<T>>::set(types::Releases::V1);
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    fn begin_block(now: HeightOf<T>) -> Weight {
+        // the queue of a height never exceeds `MaxExpiredPerBlock`,
+        // so the weight is known before touching any item
+
+        let count = <ExpiryCount<T>>::take(&now);
+
+        let weight = <T as Config>::WeightInfo::on_initialize(count);
+
+        if count == 0 {
+            return weight;
+        }
+
+        // 1. drawback expired slots
+        // a slot is closed even if its tokens failed to be sold
+
+        let slots: Vec<_> = <SlotExpiry<T>>::drain_prefix(&now)
+            .map(|(k, _)| k)
+            .collect();
+        for (kol, ad) in slots {
+            if let Some(slot) = Self::find_slot(&kol, &ad) {
+                if Self::drawback(&kol, &slot).is_err() {
+                    Self::drop_slot(&kol, &slot);
+                }
+            }
+        }
+
+        // 2. refund remain of ended advertisements
+
+        let ads: Vec<_> = <AdExpiry<T>>::drain_prefix(&now).map(|(k, _)| k).collect();
+        for ad in ads {
+            Self::end_ad(ad);
+        }
+
+        weight
+    }
+
+    /// End an advertisement and refund its remain,
+    /// slots still open are refunded when they are closed
+    fn end_ad(ad: HashOf<T>) {
+        let mut meta = match <Metadata<T>>::get(&ad) {
+            Some(meta) => meta,
+            None => return,
+        };
+
+        Self::refund(&mut meta);
+
+        meta.status = types::AdStatus::Ended;
+
+        Self::unlist_ad(&meta.creator, &ad);
+
+        <Metadata<T>>::insert(&ad, meta);
+        <EndtimeOf<T>>::remove(&ad);
+        <PausedAt<T>>::remove(&ad);
+    }
+
+    /// Remove an ad from ongoing advertisements of its creator
+    fn unlist_ad(creator: &DidOf<T>, ad: &HashOf<T>) {
+        let mut ads = match <AdsOf<T>>::get(creator) {
+            Some(ads) => ads.into_inner(),
+            None => return,
+        };
+
+        ads.retain(|x| x != ad);
+
+        if let Ok(ads) = BoundedVec::<_, T::MaxAdsPerAdvertiser>::try_from(ads) {
+            <AdsOf<T>>::insert(creator, ads);
+        }
+    }
+
+    /// Reserve the first height not before `height` with room in the expiry queue,
+    /// up to `MaxExpiryDelay` blocks after it
+    fn reserve_expiry(height: HeightOf<T>) -> Result<HeightOf<T>, DispatchError> {
+        let max = T::MaxExpiredPerBlock::get();
+
+        let mut height = height;
+        for _ in 0..T::MaxExpiryDelay::get() {
+            if <ExpiryCount<T>>::get(&height) < max {
+                break;
+            }
+
+            height = height.saturating_add(One::one());
+        }
+
+        <ExpiryCount<T>>::try_mutate(&height, |count| {
+            ensure!(*count < max, Error::<T>::ExpiryQueueFull);
+
+            *count = count.saturating_add(1);
+
+            Ok(height)
+        })
+    }
+
+    /// Weight of scanning the expiry queue while scheduling `n` items
+    pub(crate) fn expiry_weight(n: u32) -> Weight {
+        let reads = T::MaxExpiryDelay::get().saturating_mul(n);
+
+        T::DbWeight::get().reads(reads as Weight)
+    }
+
+    fn release_expiry(height: HeightOf<T>) {
+        <ExpiryCount<T>>::mutate_exists(&height, |maybe| {
+            *maybe = maybe
+                .and_then(|count| count.checked_sub(1))
+                .filter(|count| *count > 0);
+        });
+    }
+
+    pub(crate) fn schedule_ad(ad: &HashOf<T>, endtime: HeightOf<T>) -> DispatchResult {
+        let endtime = Self::reserve_expiry(endtime)?;
+
+        <EndtimeOf<T>>::insert(ad, endtime);
+        <AdExpiry<T>>::insert(endtime, ad, ());
+
+        Ok(())
+    }
+
+    fn unschedule_ad(ad: &HashOf<T>) {
+        // an end time not yet queued by the migration holds no room
+        if let Some(endtime) = <EndtimeOf<T>>::take(ad) {
+            if <AdExpiry<T>>::take(endtime, ad).is_some() {
+                Self::release_expiry(endtime);
+            }
+        }
+    }
+
+    pub(crate) fn schedule_slot(
+        kol: &DidOf<T>,
+        ad: &HashOf<T>,
+        deadline: HeightOf<T>,
+    ) -> DispatchResult {
+        let deadline = Self::reserve_expiry(deadline)?;
+
+        <DeadlineOf<T>>::insert(kol, ad, deadline);
+        <SlotExpiry<T>>::insert(deadline, (kol, ad), ());
+
+        Ok(())
+    }
+
+    fn unschedule_slot(kol: &DidOf<T>, ad: &HashOf<T>) {
+        // a deadline not yet queued by the migration holds no room
+        if let Some(deadline) = <DeadlineOf<T>>::take(kol, ad) {
+            if <SlotExpiry<T>>::take(deadline, (kol, ad)).is_some() {
+                Self::release_expiry(deadline);
+            }
+        }
+    }
+
+    #[transactional]
+    fn place_bid(
+        did: DidOf<T>,
+        ad: HashOf<T>,
+        kol: DidOf<T>,
+        value: BalanceOf<T>,
+    ) -> DispatchResult {
+        let height = <frame_system::Pallet<T>>::block_number();
+
+        let endtime = <EndtimeOf<T>>::get(&ad).ok_or(Error::<T>::NotExists)?;
+        ensure!(endtime > height, Error::<T>::Deadline);
+
+        let meta = Self::ensure_owned(did, ad)?;
+
+        Self::ensure_active(&meta)?;
+
+        ensure!(meta.remain >= value, Error::<T>::InsufficientBalance);
+
+        let kol_meta = Did::<T>::meta(&kol).ok_or(Error::<T>::NotMinted)?;
+        let nft = kol_meta.nft.ok_or(Error::<T>::NotMinted)?;
+
+        let created = <frame_system::Pallet<T>>::block_number();
+
+        // 1. check slots of kol
+        // a previous bid of this ad is always replaced
+
+        let slots = <SlotOf<T>>::get(&kol);
+        let capacity = <CapacityOf<T>>::get(&kol) as usize;
+
+        let mut evicted: Vec<_> = slots.iter().filter(|s| s.ad == ad).cloned().collect();
+        let mut occupied = slots.len().saturating_sub(evicted.len());
+        let mut outbid = None;
+
+        // budgets may be of different assets, compare them in currency
+        let bid = Self::quote_value(meta.asset, value)?;
+
+        if let Some(config) = <SlotConfigOf<T>>::get(&kol) {
+            ensure!(!config.blocked.contains(&did), Error::<T>::Blocked);
+
+            ensure!(bid >= config.reserve, Error::<T>::BelowReserve);
+
+            ensure!(
+                config.tags.is_empty() || config.tags.iter().any(|tag| T::Tags::has_tag(&ad, tag)),
+                Error::<T>::TagNotAccepted
+            );
+        }
+
+        // a previous slot of this kol is replaced, not added
+        let kols = <SlotsOf<T>>::get(&ad).unwrap_or_default();
+        ensure!(
+            (kols.iter().filter(|k| **k != kol).count() as u32) < T::MaxSlotsPerAd::get(),
+            Error::<T>::TooManySlots
+        );
+
+        // 2. if all slots are used
+        // require the bid policy to accept the bid over the lowest value
+        // and drawback the lowest ad
+
+        let others = slots.iter().filter(|s| s.ad != ad).cloned().collect();
+        for (remain, slot) in Self::rank_slots(others)?.into_iter().rev() {
+            if occupied < capacity {
+                break;
+            }
+
+            ensure!(T::BidPolicy::outbid(bid, remain), Error::<T>::Underbid);
+
+            if outbid.is_none() {
+                outbid = <DeadlineOf<T>>::get(&kol, &slot.ad);
+            }
+
+            evicted.push(slot);
+            occupied -= 1;
+        }
+
+        for slot in evicted {
+            let _ = Self::drawback(&kol, &slot)?;
+        }
+
+        // drawback may refund to this ad, reload it
+        let mut meta = Self::ensure_owned(did, ad)?;
+
+        // 3. update slot
+
+        let lifetime = T::SlotLifetime::get();
+        let slotlife = created.saturating_add(lifetime);
+        let slotlife = T::BidPolicy::deadline(created, slotlife, outbid);
+        let deadline = if slotlife > endtime {
+            endtime
+        } else {
+            slotlife
+        };
+
+        let mut slot = types::Slot {
+            nft,
+            budget: value,
+            remain: value,
+            tokens: Zero::zero(),
+            created,
+            ad,
+        };
+
+        Self::deposit_event(Event::SlotOpened {
+            ad,
+            kol,
+            nft,
+            budget: value,
+        });
+
+        Self::share_revenue(kol, &kol_meta.account, &meta, &mut slot)?;
+
+        if meta.asset == Some(nft) {
+            // budget is already in tokens of the KOL
+            slot.tokens = slot.remain;
+            slot.remain = Zero::zero();
+        } else {
+            Self::swap_by_10percent(kol, &meta, &mut slot, One::one())?;
+        }
+
+        Self::insert_slot(&kol, slot)?;
+
+        Self::schedule_slot(&kol, &ad, deadline)?;
+
+        meta.remain.saturating_reduce(value);
+
+        <Metadata<T>>::insert(&ad, &meta);
+
+        let mut kols = <SlotsOf<T>>::get(&ad).unwrap_or_default().into_inner();
+        kols.push(kol);
+        let kols: BoundedVec<_, T::MaxSlotsPerAd> =
+            kols.try_into().map_err(|_| Error::<T>::TooManySlots)?;
+        <SlotsOf<T>>::insert(&ad, kols);
+
+        Self::deposit_event(Event::Bid(kol, ad, value));
+
+        Ok(())
+    }
+
+    /// Place the highest revealed bid of a finished round,
+    /// lower bids are tried in turn if a higher one is no longer valid
+    fn settle_round(kol: DidOf<T>) {
+        <RoundOf<T>>::remove(&kol);
+
+        // bids may be of different assets, compare them in currency
+        let mut bids: Vec<_> = <CommitmentsOf<T>>::take(&kol)
+            .into_iter()
+            .filter_map(|c| {
+                let value = c.value?;
+                let meta = <Metadata<T>>::get(&c.ad)?;
+                let quote = Self::quote_value(meta.asset, value).ok()?;
+                Some((quote, c.did, c.ad, value))
+            })
+            .collect();
+
+        // the sort is stable, earlier commitments win ties
+        bids.sort_by(|a, b| b.0.cmp(&a.0));
+
+        let winner = bids
+            .into_iter()
+            .find(|(_, did, ad, value)| Self::place_bid(*did, *ad, kol, *value).is_ok())
+            .map(|(_, _, ad, _)| ad);
+
+        Self::deposit_event(Event::BidSettled(kol, winner));
     }
-}
 
-impl<T: Config> Pallet<T> {
-    fn begin_block(now: HeightOf<T>) -> Result<Weight, DispatchError> {
-        use frame_support::traits::Get;
+    #[transactional]
+    fn drawback(kol: &DidOf<T>, slot: &SlotMetaOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+        let meta = <Metadata<T>>::get(slot.ad).ok_or(Error::<T>::NotExists)?;
 
-        let mut read = 0;
-        let mut write = 0;
+        let amount = if slot.tokens.is_zero() {
+            Zero::zero()
+        } else {
+            Self::sell_tokens(&meta, slot.nft, slot.tokens)?
+        };
 
-        let mut amount = 0;
+        Self::close_slot(kol, slot, amount);
 
-        for (kol, ad, deadline) in <DeadlineOf<T>>::iter() {
-            read += 1;
+        Self::deposit_event(Event::End(*kol, slot.ad, amount));
+        Self::deposit_event(Event::SlotClosed {
+            ad: slot.ad,
+            kol: *kol,
+            tokens_sold: slot.tokens,
+            currency_returned: amount,
+            remain_returned: slot.remain,
+        });
 
-            if amount >= 100 {
-                break;
-            }
+        Ok(amount)
+    }
 
-            if deadline > now {
-                continue;
-            }
+    /// Close a slot whose tokens failed to be sold,
+    /// the tokens are recorded to be returned when the advertisement is refunded
+    fn drop_slot(kol: &DidOf<T>, slot: &SlotMetaOf<T>) {
+        <UnsoldOf<T>>::mutate(&slot.ad, slot.nft, |unsold| {
+            unsold.saturating_accrue(slot.tokens)
+        });
 
-            read += 1;
-            let slot = <SlotOf<T>>::get(kol);
-            if let Some(slot) = slot {
-                if slot.ad != ad {
-                    continue;
-                }
+        Self::close_slot(kol, slot, Zero::zero());
+
+        Self::deposit_event(Event::SlotDropped {
+            ad: slot.ad,
+            kol: *kol,
+            tokens: slot.tokens,
+        });
+    }
 
-                read += 2;
-                write += 4;
-                let _ = Self::drawback(&kol, &slot);
+    /// Remove a slot, returning its remain and the currency gained to the advertisement,
+    /// which are refunded to the advertiser if the advertisement already ended
+    fn close_slot(kol: &DidOf<T>, slot: &SlotMetaOf<T>, amount: BalanceOf<T>) {
+        Self::remove_slot(kol, &slot.ad);
 
-                amount += 1;
+        if let Some(kols) = <SlotsOf<T>>::get(slot.ad) {
+            let mut kols = kols.into_inner();
+            kols.retain(|x| x != kol);
+
+            if let Ok(kols) = BoundedVec::<_, T::MaxSlotsPerAd>::try_from(kols) {
+                <SlotsOf<T>>::insert(slot.ad, kols);
             }
         }
 
-        let mut amount = 0;
+        Self::unschedule_slot(kol, &slot.ad);
 
-        for (ad, endtime) in <EndtimeOf<T>>::iter() {
-            read += 1;
+        let mut meta = match <Metadata<T>>::get(slot.ad) {
+            Some(meta) => meta,
+            None => return,
+        };
 
-            if amount >= 100 {
-                break;
-            }
+        meta.remain.saturating_accrue(slot.remain);
+        meta.remain.saturating_accrue(amount);
 
-            if endtime > now {
-                continue;
-            }
+        if meta.status == types::AdStatus::Ended {
+            Self::refund(&mut meta);
+        }
 
-            read += 1;
-            if let Some(slots) = <SlotsOf<T>>::get(ad) {
-                if slots.len() > 0 {
-                    continue;
-                }
-            }
+        <Metadata<T>>::insert(slot.ad, meta);
+    }
 
-            read += 1;
-            let meta = <Metadata<T>>::get(ad);
-            if meta.is_none() {
-                continue;
-            }
-            let mut meta = meta.unwrap();
+    /// Refund remain of an advertisement to its creator,
+    /// remain is kept in pot if refunding failed
+    fn refund(meta: &mut MetaOf<T>) {
+        let creator = match Did::<T>::meta(&meta.creator) {
+            Some(creator) => creator,
+            None => return,
+        };
+
+        Self::refund_unsold(meta, &creator.account);
+
+        let refunded = meta.remain;
+        if Self::transfer_budget(meta.asset, &meta.pot, &creator.account, refunded, false).is_ok() {
+            meta.remain = Zero::zero();
+
+            Self::deposit_event(Event::Refunded {
+                ad: meta.id,
+                creator: meta.creator,
+                amount: refunded,
+            });
+        }
+    }
 
-            let creator = Did::<T>::meta(&meta.creator);
-            if creator.is_none() {
-                continue;
+    /// Return tokens of dropped slots to the advertiser,
+    /// tokens are kept in pot if returning failed
+    fn refund_unsold(meta: &MetaOf<T>, account: &AccountOf<T>) {
+        let unsold: Vec<_> = <UnsoldOf<T>>::iter_prefix(&meta.id).collect();
+        for (nft, tokens) in unsold {
+            // selling may have failed because the pot holds fewer tokens than recorded
+            let tokens = tokens.min(T::Assets::reducible_balance(nft, &meta.pot, false));
+
+            if T::Assets::transfer(nft, &meta.pot, account, tokens, false).is_ok() {
+                <UnsoldOf<T>>::remove(&meta.id, nft);
+
+                Self::deposit_event(Event::UnsoldRefunded {
+                    ad: meta.id,
+                    creator: meta.creator,
+                    nft,
+                    tokens,
+                });
             }
-            let creator = creator.unwrap();
+        }
+    }
 
-            let _ = <T as parami_did::Config>::Currency::transfer(
-                &meta.pot,
-                &creator.account,
-                meta.remain,
-                AllowDeath,
-            );
+    /// Calculate the score of a visitor for an advertisement
+    pub fn calculate_score(ad: &HashOf<T>, visitor: &DidOf<T>) -> u32 {
+        let mut scoring = 5i32;
 
-            meta.remain = Zero::zero();
+        let tags = T::Tags::tags_of(ad);
+        let personas = T::Tags::personas_of(visitor);
+        let length = personas.len();
+        for (tag, score) in personas {
+            let delta = if tags.contains_key(&tag) {
+                score.saturating_mul(10)
+            } else {
+                score
+            };
+            scoring.saturating_accrue(delta);
+        }
 
-            write += 2;
-            <Metadata<T>>::insert(ad, meta);
-            <EndtimeOf<T>>::remove(ad);
+        scoring /= length.saturating_mul(10).saturating_add(1) as i32;
 
-            amount += 1;
+        if scoring < 0 {
+            scoring = 0;
         }
 
-        Ok(T::DbWeight::get().reads_writes(read as Weight, write as Weight))
+        scoring as u32
     }
 
-    fn drawback(kol: &DidOf<T>, slot: &SlotMetaOf<T>) -> Result<BalanceOf<T>, DispatchError> {
-        let mut meta = <Metadata<T>>::get(slot.ad).ok_or(Error::<T>::NotExists)?;
+    /// Get dry-run result of pay
+    ///
+    /// # Returns
+    ///
+    /// tuple of (score, amount, award, swapped)
+    pub fn dryly_pay(
+        ad: HashOf<T>,
+        kol: DidOf<T>,
+        visitor: DidOf<T>,
+        referer: Option<DidOf<T>>,
+    ) -> Result<(u32, BalanceOf<T>, BalanceOf<T>, bool), DispatchError> {
+        let height = <frame_system::Pallet<T>>::block_number();
 
-        let amount = T::Swaps::token_in(
-            meta.pot.clone(), //
-            slot.nft,
-            slot.tokens,
-            One::one(),
-            false,
-        )?;
+        let endtime = <EndtimeOf<T>>::get(&ad).ok_or(Error::<T>::NotExists)?;
+        ensure!(endtime > height, Error::<T>::Deadline);
 
-        meta.remain.saturating_accrue(slot.remain);
-        meta.remain.saturating_accrue(amount);
+        let deadline = <DeadlineOf<T>>::get(&kol, &ad).ok_or(Error::<T>::NotExists)?;
+        ensure!(deadline > height, Error::<T>::Deadline);
 
-        <Metadata<T>>::insert(slot.ad, meta);
+        let meta = <Metadata<T>>::get(&ad).ok_or(Error::<T>::NotExists)?;
+
+        Self::ensure_active(&meta)?;
+
+        let slot = Self::find_slot(&kol, &ad).ok_or(Error::<T>::Underbid)?;
+
+        ensure!(
+            Self::is_payable(&meta, &kol, &slot, &visitor, height),
+            Error::<T>::Paid
+        );
+
+        ensure!(
+            Self::is_targeted(&meta, &visitor, height),
+            Error::<T>::TargetMismatch
+        );
+
+        let scoring = Self::calculate_score(&ad, &visitor);
+
+        let amount = T::PayoutBase::get().saturating_mul(scoring.into());
+
+        let swapped = slot.tokens < amount && meta.asset != Some(slot.nft);
+
+        let referers = Self::referers(&meta, &visitor, referer)?;
+
+        let award = referers
+            .iter()
+            .fold(Zero::zero(), |award: BalanceOf<T>, (_, _, rate)| {
+                award.saturating_add(Self::award_of(amount, *rate))
+            });
+
+        Ok((scoring, amount, award, swapped))
+    }
+
+    /// Get payout statistics of an advertisement
+    pub fn get_stats(ad: HashOf<T>) -> Result<StatsOf<T>, DispatchError> {
+        ensure!(<Metadata<T>>::contains_key(&ad), Error::<T>::NotExists);
+
+        Ok(<Stats<T>>::get(&ad))
+    }
+
+    /// Get payout statistics of an advertisement through a KOL
+    pub fn get_slot_stats(ad: HashOf<T>, kol: DidOf<T>) -> Result<StatsOf<T>, DispatchError> {
+        ensure!(<Metadata<T>>::contains_key(&ad), Error::<T>::NotExists);
+
+        Ok(<SlotStats<T>>::get(&ad, &kol))
+    }
+
+    fn ensure_fee_balance(who: AccountOf<T>, kol: &DidOf<T>, slot: &SlotMetaOf<T>) {
+        let controller = match Magic::<T>::controller(&who) {
+            Some(c) => c,
+            None => who,
+        };
+
+        let balance = <T as parami_did::Config>::Currency::free_balance(&controller);
+        if balance - <T as parami_did::Config>::Currency::minimum_balance()
+            < T::MinimumFeeBalance::get()
+        {
+            let _ = Self::drawback(kol, slot);
+        }
+    }
+
+    fn ensure_payable(
+        did: DidOf<T>,
+        ad: HashOf<T>,
+        kol: DidOf<T>,
+        height: HeightOf<T>,
+    ) -> Result<(MetaOf<T>, SlotMetaOf<T>), DispatchError> {
+        let endtime = <EndtimeOf<T>>::get(&ad).ok_or(Error::<T>::NotExists)?;
+        ensure!(endtime > height, Error::<T>::Deadline);
+
+        let deadline = <DeadlineOf<T>>::get(&kol, &ad).ok_or(Error::<T>::NotExists)?;
+        ensure!(deadline > height, Error::<T>::Deadline);
+
+        let meta = Self::ensure_owned(did, ad)?;
+
+        Self::ensure_active(&meta)?;
 
-        <SlotOf<T>>::remove(kol);
+        // get slot of current ad
+        let slot = Self::find_slot(&kol, &ad).ok_or(Error::<T>::Underbid)?;
 
-        <SlotsOf<T>>::mutate(slot.ad, |maybe| {
-            if let Some(slots) = maybe {
-                slots.retain(|x| *x != *kol);
+        Ok((meta, slot))
+    }
+
+    /// Get the slot of an advertisement in a KOL
+    pub fn find_slot(kol: &DidOf<T>, ad: &HashOf<T>) -> Option<SlotMetaOf<T>> {
+        <SlotOf<T>>::get(kol).iter().find(|s| s.ad == *ad).cloned()
+    }
+
+    fn insert_slot(kol: &DidOf<T>, slot: SlotMetaOf<T>) -> DispatchResult {
+        let value = Self::slot_value(&slot)?;
+
+        // keep slots ranked by value, earlier bids win ties
+        let mut ranked = Self::rank_slots(<SlotOf<T>>::get(kol).into_inner())?;
+        let index = ranked
+            .iter()
+            .position(|(v, _)| *v < value)
+            .unwrap_or(ranked.len());
+        ranked.insert(index, (value, slot));
+
+        let slots: Vec<_> = ranked.into_iter().map(|(_, s)| s).collect();
+        let slots: BoundedVec<_, T::MaxSlotsPerKol> =
+            slots.try_into().map_err(|_| Error::<T>::SlotsFull)?;
+
+        <SlotOf<T>>::insert(kol, slots);
+
+        Ok(())
+    }
+
+    /// Sort slots by value from the highest, keeping the order of equal ones
+    fn rank_slots(
+        slots: Vec<SlotMetaOf<T>>,
+    ) -> Result<Vec<(BalanceOf<T>, SlotMetaOf<T>)>, DispatchError> {
+        let mut ranked = slots
+            .into_iter()
+            .map(|slot| Ok((Self::slot_value(&slot)?, slot)))
+            .collect::<Result<Vec<_>, DispatchError>>()?;
+
+        ranked.sort_by(|a, b| b.0.cmp(&a.0));
+
+        Ok(ranked)
+    }
+
+    /// Value of a slot in currency, the remain of budget with the tokens bought
+    fn slot_value(slot: &SlotMetaOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+        let meta = <Metadata<T>>::get(&slot.ad).ok_or(Error::<T>::NotExists)?;
+
+        let remain = Self::quote_value(meta.asset, slot.remain)?;
+        let tokens = Self::quote_value(Some(slot.nft), slot.tokens)?;
+
+        Ok(remain.saturating_add(tokens))
+    }
+
+    fn update_slot(kol: &DidOf<T>, slot: &SlotMetaOf<T>) {
+        let mut slots = <SlotOf<T>>::get(kol).into_inner();
+
+        if let Some(s) = slots.iter_mut().find(|s| s.ad == slot.ad) {
+            *s = slot.clone();
+        }
+
+        if let Ok(slots) = BoundedVec::<_, T::MaxSlotsPerKol>::try_from(slots) {
+            <SlotOf<T>>::insert(kol, slots);
+        }
+    }
+
+    fn remove_slot(kol: &DidOf<T>, ad: &HashOf<T>) {
+        let mut slots = <SlotOf<T>>::get(kol).into_inner();
+        slots.retain(|s| s.ad != *ad);
+
+        if slots.is_empty() {
+            <SlotOf<T>>::remove(kol);
+        } else if let Ok(slots) = BoundedVec::<_, T::MaxSlotsPerKol>::try_from(slots) {
+            <SlotOf<T>>::insert(kol, slots);
+        }
+    }
+
+    fn ensure_targeting(targeting: &Option<TargetingOf<T>>) -> DispatchResult {
+        if let Some(targeting) = targeting {
+            for (tag, _) in targeting.scores.iter() {
+                ensure!(T::Tags::exists(tag), Error::<T>::TagNotExists);
             }
-        });
+        }
 
-        <DeadlineOf<T>>::remove(kol, slot.ad);
+        Ok(())
+    }
 
-        Self::deposit_event(Event::End(*kol, slot.ad, amount));
+    fn is_payable(
+        meta: &MetaOf<T>,
+        kol: &DidOf<T>,
+        slot: &SlotMetaOf<T>,
+        visitor: &DidOf<T>,
+        height: HeightOf<T>,
+    ) -> bool {
+        use types::Frequency::*;
+
+        let payout = match migrations::payout_of::<T>(&meta.id, visitor) {
+            Some(payout) => payout,
+            None => return true,
+        };
+
+        match meta.frequency {
+            Once => false,
+            Every(blocks) => height.saturating_sub(payout.last) >= blocks,
+            Times(times) => payout.count < times,
+            PerSlot => <SlotPayout<T>>::get(&meta.id, (kol, visitor)) != Some(slot.created),
+        }
+    }
 
-        Ok(amount)
+    fn record_payout(
+        meta: &MetaOf<T>,
+        kol: &DidOf<T>,
+        slot: &SlotMetaOf<T>,
+        visitor: &DidOf<T>,
+        height: HeightOf<T>,
+    ) {
+        let mut payout = migrations::payout_of::<T>(&meta.id, visitor).unwrap_or_default();
+        payout.count = payout.count.saturating_add(1);
+        payout.last = height;
+
+        <Payout<T>>::insert(&meta.id, visitor, payout);
+
+        if meta.frequency == types::Frequency::PerSlot {
+            <SlotPayout<T>>::insert(&meta.id, (kol, visitor), slot.created);
+        }
+    }
+
+    fn record_stats(ad: &HashOf<T>, kol: &DidOf<T>, delta: &StatsOf<T>) {
+        let accrue = |stats: &mut StatsOf<T>| {
+            stats.paid.saturating_accrue(delta.paid);
+            stats.visitors.saturating_accrue(delta.visitors);
+            stats.awarded.saturating_accrue(delta.awarded);
+            stats.tokens.saturating_accrue(delta.tokens);
+            stats.spent.saturating_accrue(delta.spent);
+        };
+
+        <Stats<T>>::mutate(ad, accrue);
+        <SlotStats<T>>::mutate(ad, kol, accrue);
+    }
+
+    fn is_targeted(meta: &MetaOf<T>, visitor: &DidOf<T>, height: HeightOf<T>) -> bool {
+        let targeting = match &meta.targeting {
+            Some(targeting) => targeting,
+            None => return true,
+        };
+
+        for (tag, score) in targeting.scores.iter() {
+            if T::Tags::get_score(visitor, tag) < *score {
+                return false;
+            }
+        }
+
+        for site in targeting.links.iter() {
+            if !T::Links::has_link(visitor, site) {
+                return false;
+            }
+        }
+
+        if !targeting.min_age.is_zero() {
+            match Did::<T>::meta(visitor) {
+                Some(did) if height.saturating_sub(did.created) >= targeting.min_age => {}
+                _ => return false,
+            }
+        }
+
+        true
+    }
+
+    fn ensure_scores(ad: &HashOf<T>, scores: &[(Vec<u8>, i8)]) -> DispatchResult {
+        for (tag, score) in scores {
+            ensure!(T::Tags::has_tag(ad, tag), Error::<T>::TagNotExists);
+            ensure!(*score >= -5 && *score <= 5, Error::<T>::ScoreOutOfRange);
+        }
+
+        Ok(())
     }
 
     fn ensure_owned(did: DidOf<T>, ad: HashOf<T>) -> Result<MetaOf<T>, DispatchError> {
@@ -706,21 +2282,343 @@ impl<T: Config> Pallet<T> {
         Ok(meta)
     }
 
+    /// Get the payload of a view receipt to be signed by a visitor,
+    /// bound to this chain and to the referer and scores paid with it
+    pub fn receipt_payload(
+        ad: &HashOf<T>,
+        kol: &DidOf<T>,
+        visitor: &DidOf<T>,
+        referer: Option<DidOf<T>>,
+        scores: &[(Vec<u8>, i8)],
+        nonce: u64,
+        height: HeightOf<T>,
+    ) -> Vec<u8> {
+        let genesis = <frame_system::Pallet<T>>::block_hash(HeightOf::<T>::zero());
+        let scores = T::Hashing::hash_of(&scores);
+
+        (genesis, ad, kol, visitor, referer, scores, nonce, height).encode()
+    }
+
+    /// Verify the view receipt of a visitor, returning the account of visitor
+    fn ensure_receipt(
+        ad: &HashOf<T>,
+        kol: &DidOf<T>,
+        visitor: &DidOf<T>,
+        referer: Option<DidOf<T>>,
+        scores: &[(Vec<u8>, i8)],
+        receipt: &ReceiptOf<T>,
+        height: HeightOf<T>,
+    ) -> Result<AccountOf<T>, DispatchError> {
+        let account = Did::<T>::lookup_did(*visitor).ok_or(Error::<T>::DidNotExists)?;
+
+        ensure!(receipt.height <= height, Error::<T>::InvalidReceipt);
+
+        ensure!(
+            receipt.nonce > <NonceOf<T>>::get(ad, visitor),
+            Error::<T>::NonceUsed
+        );
+
+        let payload = Self::receipt_payload(
+            ad,
+            kol,
+            visitor,
+            referer,
+            scores,
+            receipt.nonce,
+            receipt.height,
+        );
+
+        // stash accounts are keyless, accept receipts signed by their controllers
+        let signed = |who: &AccountOf<T>| receipt.signature.verify(&payload[..], who);
+        let controller = Magic::<T>::controller(&account);
+
+        ensure!(
+            signed(&account) || controller.map_or(false, |c| signed(&c)),
+            Error::<T>::InvalidReceipt
+        );
+
+        Ok(account)
+    }
+
+    fn ensure_active(meta: &MetaOf<T>) -> DispatchResult {
+        match meta.status {
+            types::AdStatus::Active => Ok(()),
+            types::AdStatus::Paused => Err(Error::<T>::Paused)?,
+            _ => Err(Error::<T>::Deadline)?,
+        }
+    }
+
+    #[transactional]
+    fn influence(visitor: &DidOf<T>, scores: Vec<(Vec<u8>, i8)>) -> DispatchResult {
+        for (tag, score) in scores {
+            T::Tags::influence(visitor, &tag, score as i32)?;
+        }
+
+        Ok(())
+    }
+
+    fn transfer_payout(
+        meta: &MetaOf<T>,
+        nft: AssetOf<T>,
+        account: &AccountOf<T>,
+        referers: &[(DidOf<T>, AccountOf<T>, u16)],
+        amount: BalanceOf<T>,
+    ) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+        let mut award: BalanceOf<T> = Zero::zero();
+
+        for (did, referer, rate) in referers {
+            let value = Self::award_of(amount, *rate);
+
+            T::Assets::transfer(nft, &meta.pot, referer, value, false)?;
+
+            award.saturating_accrue(value);
+
+            Self::deposit_event(Event::Awarded(meta.id, *did, value));
+        }
+
+        let reward = amount.saturating_sub(award);
+
+        T::Assets::transfer(nft, &meta.pot, account, reward, false)?;
+
+        Ok((reward, award))
+    }
+
+    fn award_of(amount: BalanceOf<T>, rate: u16) -> BalanceOf<T> {
+        amount.saturating_mul(rate.into()) / 100u32.into()
+    }
+
+    fn ensure_creative(creative: &CreativeOf<T>) -> DispatchResult {
+        ensure!(cid::is_valid(&creative.cid), Error::<T>::InvalidCid);
+
+        Ok(())
+    }
+
+    fn ensure_reward_split(reward_split: &RewardSplitOf<T>) -> DispatchResult {
+        let total = reward_split
+            .rates
+            .iter()
+            .fold(0u32, |total, rate| total.saturating_add(*rate as u32));
+        ensure!(total <= 100, Error::<T>::RewardSplitOutOfRange);
+
+        Ok(())
+    }
+
+    /// Resolve referers to be awarded, level by level
+    ///
+    /// # Returns
+    ///
+    /// list of (DID, account, rate) of referers
+    fn referers(
+        meta: &MetaOf<T>,
+        visitor: &DidOf<T>,
+        referer: Option<DidOf<T>>,
+    ) -> Result<Vec<(DidOf<T>, AccountOf<T>, u16)>, DispatchError> {
+        let mut current = match meta.reward_split.source {
+            types::RefererSource::Caller => {
+                if let Some(referer) = referer {
+                    ensure!(
+                        Did::<T>::lookup_did(referer).is_some(),
+                        Error::<T>::DidNotExists
+                    );
+                }
+
+                referer
+            }
+            types::RefererSource::Registered => Did::<T>::referrer_of(visitor),
+        };
+
+        let mut referers = Vec::new();
+
+        for rate in meta.reward_split.rates.iter() {
+            let did = match current {
+                Some(did) => did,
+                None => break,
+            };
+
+            let account = match Did::<T>::lookup_did(did) {
+                Some(account) => account,
+                None => break,
+            };
+
+            referers.push((did, account, *rate));
+
+            current = Did::<T>::referrer_of(did);
+        }
+
+        Ok(referers)
+    }
+
+    /// Pay shares of slot budget to the KOL and fragment holders
+    fn share_revenue(
+        kol: DidOf<T>,
+        account: &AccountOf<T>,
+        meta: &MetaOf<T>,
+        slot: &mut SlotMetaOf<T>,
+    ) -> DispatchResult {
+        let amount = T::KolShare::get().mul_floor(slot.budget);
+        if !amount.is_zero() {
+            Self::transfer_budget(meta.asset, &meta.pot, account, amount, false)?;
+
+            slot.remain.saturating_reduce(amount);
+
+            Self::deposit_event(Event::KolShared {
+                ad: slot.ad,
+                kol,
+                amount,
+            });
+        }
+
+        // holders are paid in fragments, kept in budget if none staked
+        let mut pool = <HolderPool<T>>::get(slot.nft);
+        let amount = T::HolderShare::get().mul_floor(slot.budget);
+        if !amount.is_zero() && !pool.staked.is_zero() {
+            let tokens = Self::buy_tokens(meta, slot.nft, amount, One::one())?;
+
+            T::Assets::transfer(
+                slot.nft,
+                &meta.pot,
+                &Self::holders_account(slot.nft),
+                tokens,
+                false,
+            )?;
+
+            let share = FixedU128::saturating_from_rational(
+                tokens.saturated_into::<u128>(),
+                pool.staked.saturated_into::<u128>(),
+            );
+            pool.acc = pool.acc.saturating_add(share.into_inner());
+
+            <HolderPool<T>>::insert(slot.nft, pool);
+
+            slot.remain.saturating_reduce(amount);
+
+            Self::deposit_event(Event::HolderShared {
+                ad: slot.ad,
+                kol,
+                amount,
+                tokens,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Account holding fragments staked by holders of a KOL, with their shares
+    fn holders_account(nft: AssetOf<T>) -> AccountOf<T> {
+        <T as Config>::PalletId::get().into_sub_account((b"holders", nft))
+    }
+
+    /// Settle the holder share accrued to a holding since it was last settled
+    fn settle_holding(pool: &HolderPoolOf<T>, holding: &mut HoldingOf<T>) {
+        let share = FixedU128::from_inner(pool.acc.saturating_sub(holding.acc));
+        let reward = share.saturating_mul_int(holding.staked.saturated_into::<u128>());
+
+        holding.reward.saturating_accrue(reward.saturated_into());
+        holding.acc = pool.acc;
+    }
+
     fn swap_by_10percent(
         kol: DidOf<T>,
         meta: &MetaOf<T>,
         slot: &mut SlotMetaOf<T>,
         least: BalanceOf<T>,
     ) -> DispatchResult {
-        // swap per 10%
-        let amount = slot.budget / 10u32.into();
-        let tokens = T::Swaps::quote_in(meta.pot.clone(), slot.nft, amount, least, false)?;
+        // nothing to swap if budget is in tokens of the KOL
+        if meta.asset == Some(slot.nft) {
+            return Ok(());
+        }
+
+        // swap per 10%, share of KOL may leave less
+        let amount = slot.remain.min(slot.budget / 10u32.into());
+        let tokens = Self::buy_tokens(meta, slot.nft, amount, least)?;
 
         slot.remain.saturating_reduce(amount);
         slot.tokens.saturating_accrue(tokens);
 
+        Self::record_stats(
+            &slot.ad,
+            &kol,
+            &types::Stats {
+                tokens,
+                spent: amount,
+                ..Default::default()
+            },
+        );
+
         Self::deposit_event(Event::SwapTriggered(slot.ad, kol, slot.remain));
+        Self::deposit_event(Event::Swapped {
+            ad: slot.ad,
+            kol,
+            amount,
+            tokens,
+        });
+
+        Ok(())
+    }
+
+    fn transfer_budget(
+        asset: Option<AssetOf<T>>,
+        from: &AccountOf<T>,
+        to: &AccountOf<T>,
+        amount: BalanceOf<T>,
+        keep_alive: bool,
+    ) -> DispatchResult {
+        match asset {
+            Some(asset) => {
+                T::Assets::transfer(asset, from, to, amount, keep_alive)?;
+            }
+            None => {
+                let existence = if keep_alive { KeepAlive } else { AllowDeath };
+                <T as parami_did::Config>::Currency::transfer(from, to, amount, existence)?;
+            }
+        }
 
         Ok(())
     }
+
+    /// Get value of budget in currency
+    fn quote_value(
+        asset: Option<AssetOf<T>>,
+        amount: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        match asset {
+            Some(asset) if !amount.is_zero() => T::Swaps::token_in_dry(asset, amount),
+            Some(_) => Ok(Zero::zero()),
+            None => Ok(amount),
+        }
+    }
+
+    /// Buy tokens of a KOL with budget, through currency if budget is another asset
+    fn buy_tokens(
+        meta: &MetaOf<T>,
+        nft: AssetOf<T>,
+        amount: BalanceOf<T>,
+        least: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let currency = match meta.asset {
+            Some(asset) if asset == nft => return Ok(amount),
+            Some(asset) => T::Swaps::token_in(meta.pot.clone(), asset, amount, One::one(), false)?,
+            None => amount,
+        };
+
+        T::Swaps::quote_in(meta.pot.clone(), nft, currency, least, false)
+    }
+
+    /// Sell tokens of a KOL back into budget
+    fn sell_tokens(
+        meta: &MetaOf<T>,
+        nft: AssetOf<T>,
+        tokens: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        if meta.asset == Some(nft) {
+            return Ok(tokens);
+        }
+
+        let currency = T::Swaps::token_in(meta.pot.clone(), nft, tokens, One::one(), false)?;
+
+        match meta.asset {
+            Some(asset) => T::Swaps::quote_in(meta.pot.clone(), asset, currency, One::one(), false),
+            None => Ok(currency),
+        }
+    }
 }
@@ -0,0 +1,374 @@
+use crate::{
+    cid, types, AccountOf, AdExpiry, AdsOf, BalanceOf, Config, CreativeOf, DeadlineOf, DidOf,
+    EndtimeOf, Event, HashOf, HeightOf, MetaOf, Metadata, Migrating, Pallet, Payout, PayoutOf,
+    SlotExpiry, SlotMetaOf, SlotOf, SlotsOf, StorageVersion,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+    storage::{unhashed, StoragePrefixedMap},
+    traits::Get,
+    weights::Weight,
+    BoundedVec,
+};
+use sp_runtime::{
+    traits::{One, Saturating},
+    DispatchResult,
+};
+use sp_std::prelude::*;
+
+/// Times an item is moved on by `MaxExpiryDelay` blocks
+/// while the expiry queue is full, before it is skipped
+const RETRIES: u32 = 8;
+
+/// A step of the migration takes at most this fraction of the block weight
+const STEP_SHARE: Weight = 5;
+
+/// Metadata of V0
+#[derive(Decode, Encode)]
+struct MetadataV0<A, B, D, H, N> {
+    id: H,
+    creator: D,
+    pot: A,
+    #[codec(compact)]
+    budget: B,
+    #[codec(compact)]
+    remain: B,
+    metadata: Vec<u8>,
+    reward_rate: u16,
+    created: N,
+}
+
+type MetaV0Of<T> = MetadataV0<AccountOf<T>, BalanceOf<T>, DidOf<T>, HashOf<T>, HeightOf<T>>;
+
+/// Migrate storage from V0,
+/// deadlines, end times and payouts are left to `step` in the following blocks
+pub fn migrate<T: Config>() -> Weight {
+    let mut weight = metadata::<T>();
+    weight = weight.saturating_add(slots::<T>());
+    weight = weight.saturating_add(ads::<T>());
+
+    <Migrating<T>>::put((types::Migration::Deadlines(None), 0));
+    <StorageVersion<T>>::set(types::Releases::V1);
+
+    weight.saturating_add(T::DbWeight::get().writes(2))
+}
+
+/// Migrate deadlines, end times and payouts from where the last block stopped,
+/// and report the number of items skipped once done
+pub fn step<T: Config>() -> Weight {
+    use types::Migration::*;
+
+    let (mut stage, mut skipped) = match <Migrating<T>>::get() {
+        Some(migrating) => migrating,
+        None => return T::DbWeight::get().reads(1),
+    };
+
+    let limit = T::BlockWeights::get().max_block / STEP_SHARE;
+    let next = <frame_system::Pallet<T>>::block_number().saturating_add(One::one());
+
+    let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+    // at least one item is migrated per block, however heavy it is
+    loop {
+        weight = weight.saturating_add(T::DbWeight::get().reads(1));
+
+        stage = match stage {
+            Deadlines(last) => {
+                let key = match &last {
+                    Some((kol, ad)) => <DeadlineOf<T>>::hashed_key_for(kol, ad),
+                    None => <DeadlineOf<T>>::final_prefix().to_vec(),
+                };
+
+                match next_key::<(DidOf<T>, HashOf<T>)>(&<DeadlineOf<T>>::final_prefix(), &key) {
+                    Some((kol, ad)) => {
+                        let (w, ok) = deadline::<T>(&kol, &ad, next);
+                        weight = weight.saturating_add(w);
+                        if !ok {
+                            skipped = skipped.saturating_add(1);
+                        }
+
+                        Deadlines(Some((kol, ad)))
+                    }
+                    None => Endtimes(None),
+                }
+            }
+            Endtimes(last) => {
+                let key = match &last {
+                    Some(ad) => <EndtimeOf<T>>::hashed_key_for(ad),
+                    None => <EndtimeOf<T>>::final_prefix().to_vec(),
+                };
+
+                match next_key::<HashOf<T>>(&<EndtimeOf<T>>::final_prefix(), &key) {
+                    Some(ad) => {
+                        let (w, ok) = endtime::<T>(&ad, next);
+                        weight = weight.saturating_add(w);
+                        if !ok {
+                            skipped = skipped.saturating_add(1);
+                        }
+
+                        Endtimes(Some(ad))
+                    }
+                    None => Payouts(None),
+                }
+            }
+            Payouts(last) => {
+                let key = match &last {
+                    Some((ad, visitor)) => <Payout<T>>::hashed_key_for(ad, visitor),
+                    None => <Payout<T>>::final_prefix().to_vec(),
+                };
+
+                match next_key::<(HashOf<T>, DidOf<T>)>(&<Payout<T>>::final_prefix(), &key) {
+                    Some((ad, visitor)) => {
+                        weight = weight.saturating_add(payout::<T>(&ad, &visitor));
+
+                        Payouts(Some((ad, visitor)))
+                    }
+                    None => {
+                        <Migrating<T>>::kill();
+
+                        Pallet::<T>::deposit_event(Event::Migrated(skipped));
+
+                        return weight;
+                    }
+                }
+            }
+        };
+
+        if weight >= limit {
+            break;
+        }
+    }
+
+    <Migrating<T>>::put((stage, skipped));
+
+    weight
+}
+
+/// Payout of a visitor, read as a height if not migrated yet
+pub fn payout_of<T: Config>(ad: &HashOf<T>, visitor: &DidOf<T>) -> Option<PayoutOf<T>> {
+    let raw = unhashed::get_raw(&<Payout<T>>::hashed_key_for(ad, visitor))?;
+
+    decode_exact::<PayoutOf<T>>(&raw)
+        .or_else(|| decode_exact::<HeightOf<T>>(&raw).map(|last| types::Payout { count: 1, last }))
+}
+
+/// Metadata had neither targeting, frequency nor asset of budget,
+/// advertisements without end time have been ended or terminated
+fn metadata<T: Config>() -> Weight {
+    let mut count = 0;
+
+    <Metadata<T>>::translate::<MetaV0Of<T>, _>(|ad, old| {
+        count += 1;
+
+        let status = if <EndtimeOf<T>>::contains_key(&ad) {
+            types::AdStatus::Active
+        } else {
+            types::AdStatus::Ended
+        };
+
+        Some(MetaOf::<T> {
+            id: old.id,
+            creator: old.creator,
+            pot: old.pot,
+            budget: old.budget,
+            remain: old.remain,
+            creative: creative_of::<T>(old.metadata),
+            revision: 0,
+            reward_split: old.reward_rate.into(),
+            created: old.created,
+            targeting: None,
+            frequency: Default::default(),
+            asset: None,
+            status,
+        })
+    });
+
+    T::DbWeight::get().reads_writes((count * 2) as Weight, count as Weight)
+}
+
+/// A KOL had a single slot, wrap it into a ranked list,
+/// and drop KOLs without a slot of the advertisement from its list
+fn slots<T: Config>() -> Weight {
+    let mut read = 0;
+    let mut write = 0;
+
+    <SlotOf<T>>::translate::<SlotMetaOf<T>, _>(|_, slot| {
+        read += 1;
+        write += 1;
+
+        vec![slot].try_into().ok()
+    });
+
+    <SlotsOf<T>>::translate::<Vec<DidOf<T>>, _>(|ad, kols| {
+        read += 1 + kols.len();
+        write += 1;
+
+        let kols = kols
+            .into_iter()
+            .filter(|kol| Pallet::<T>::find_slot(kol, &ad).is_some())
+            .collect();
+
+        latest(kols)
+    });
+
+    T::DbWeight::get().reads_writes(read as Weight, write as Weight)
+}
+
+/// Drop ended advertisements from lists of advertisers
+fn ads<T: Config>() -> Weight {
+    let mut read = 0;
+    let mut write = 0;
+
+    <AdsOf<T>>::translate::<Vec<HashOf<T>>, _>(|_, ads| {
+        read += 1 + ads.len();
+        write += 1;
+
+        let ads = ads
+            .into_iter()
+            .filter(|ad| match <Metadata<T>>::get(ad) {
+                Some(meta) => meta.status == types::AdStatus::Active,
+                None => false,
+            })
+            .collect();
+
+        latest(ads)
+    });
+
+    T::DbWeight::get().reads_writes(read as Weight, write as Weight)
+}
+
+/// Queue the deadline of a slot, and drop deadlines left behind by outbid ads
+fn deadline<T: Config>(kol: &DidOf<T>, ad: &HashOf<T>, next: HeightOf<T>) -> (Weight, bool) {
+    let deadline = match <DeadlineOf<T>>::get(kol, ad) {
+        Some(deadline) => deadline,
+        None => return (T::DbWeight::get().reads(1), true),
+    };
+
+    if Pallet::<T>::find_slot(kol, ad).is_none() {
+        <DeadlineOf<T>>::remove(kol, ad);
+
+        return (T::DbWeight::get().reads_writes(2, 1), true);
+    }
+
+    if <SlotExpiry<T>>::contains_key(deadline, (kol, ad)) {
+        return (T::DbWeight::get().reads(3), true);
+    }
+
+    let (tries, ok) = schedule::<T>(deadline.max(next), |height| {
+        Pallet::<T>::schedule_slot(kol, ad, height)
+    });
+
+    let weight = T::DbWeight::get()
+        .reads_writes(3, 3)
+        .saturating_add(Pallet::<T>::expiry_weight(tries));
+
+    (weight, ok)
+}
+
+/// Queue the end time of an advertisement
+fn endtime<T: Config>(ad: &HashOf<T>, next: HeightOf<T>) -> (Weight, bool) {
+    let endtime = match <EndtimeOf<T>>::get(ad) {
+        Some(endtime) => endtime,
+        None => return (T::DbWeight::get().reads(1), true),
+    };
+
+    if <AdExpiry<T>>::contains_key(endtime, ad) {
+        return (T::DbWeight::get().reads(2), true);
+    }
+
+    let (tries, ok) = schedule::<T>(endtime.max(next), |height| {
+        Pallet::<T>::schedule_ad(ad, height)
+    });
+
+    let weight = T::DbWeight::get()
+        .reads_writes(2, 3)
+        .saturating_add(Pallet::<T>::expiry_weight(tries));
+
+    (weight, ok)
+}
+
+/// A payout was a height, turn it into a record
+/// and prune payouts of ended advertisements
+fn payout<T: Config>(ad: &HashOf<T>, visitor: &DidOf<T>) -> Weight {
+    let key = <Payout<T>>::hashed_key_for(ad, visitor);
+
+    // payouts recorded since the upgrade are records already
+    let last = match unhashed::get_raw(&key).and_then(|raw| decode_exact::<HeightOf<T>>(&raw)) {
+        Some(last) => last,
+        None => return T::DbWeight::get().reads(1),
+    };
+
+    if !<Metadata<T>>::contains_key(ad) || !<EndtimeOf<T>>::contains_key(ad) {
+        unhashed::kill(&key);
+    } else {
+        <Payout<T>>::insert(ad, visitor, types::Payout { count: 1, last });
+    }
+
+    T::DbWeight::get().reads_writes(3, 1)
+}
+
+/// Schedule an item from `height`, moving on by `MaxExpiryDelay` blocks
+/// while the expiry queue is full, returns the number of tries and if it is scheduled
+fn schedule<T: Config>(
+    height: HeightOf<T>,
+    f: impl Fn(HeightOf<T>) -> DispatchResult,
+) -> (u32, bool) {
+    let delay: HeightOf<T> = T::MaxExpiryDelay::get().into();
+
+    let mut height = height;
+    for tries in 1..=RETRIES {
+        if f(height).is_ok() {
+            return (tries, true);
+        }
+
+        height = height.saturating_add(delay);
+    }
+
+    (RETRIES, false)
+}
+
+/// Key following `key` in a map with Identity hashers, decoded from the raw key
+fn next_key<K: Decode>(prefix: &[u8], key: &[u8]) -> Option<K> {
+    let next = sp_io::storage::next_key(key).filter(|next| next.starts_with(prefix))?;
+
+    K::decode(&mut &next[prefix.len()..]).ok()
+}
+
+/// Decode a value taking the whole input
+fn decode_exact<V: Decode>(raw: &[u8]) -> Option<V> {
+    let mut input = raw;
+
+    V::decode(&mut input).ok().filter(|_| input.is_empty())
+}
+
+/// Keep the latest items of a list which fit in the bound
+fn latest<V, S: Get<u32>>(mut list: Vec<V>) -> Option<BoundedVec<V, S>> {
+    let max = S::get() as usize;
+    if list.len() > max {
+        list.drain(..list.len() - max);
+    }
+
+    list.try_into().ok()
+}
+
+/// Metadata was mostly an IPFS link, keep it as CID if valid, or as landing page otherwise
+fn creative_of<T: Config>(metadata: Vec<u8>) -> CreativeOf<T> {
+    let cid = metadata.strip_prefix(&b"ipfs://"[..]).unwrap_or(&metadata);
+
+    let bounded = |mut field: Vec<u8>| {
+        field.truncate(T::MaxMetadataLen::get() as usize);
+        field.try_into().unwrap_or_default()
+    };
+
+    if cid::is_valid(cid) {
+        types::AdCreative {
+            cid: bounded(cid.to_vec()),
+            ..Default::default()
+        }
+    } else {
+        types::AdCreative {
+            landing: bounded(metadata),
+            ..Default::default()
+        }
+    }
+}
@@ -1,11 +1,17 @@
 use crate as parami_ad;
+use crate::policy::{BidPolicy, EnglishAuction, FixedIncrement, SealedBid};
+use codec::{Decode, Encode};
 use frame_support::{parameter_types, traits::GenesisBuild, PalletId};
 use frame_system::{self as system, EnsureRoot};
+use scale_info::TypeInfo;
 use sp_core::{sr25519, H160, H256};
+use sp_keystore::{testing::KeyStore, KeystoreExt};
 use sp_runtime::{
     testing::Header,
-    traits::{BlakeTwo256, Keccak256},
+    traits::{BlakeTwo256, Keccak256, Lazy, Verify},
+    Percent, Permill, RuntimeDebug,
 };
+use std::sync::Arc;
 
 type UncheckedExtrinsic = system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = system::mocking::MockBlock<Test>;
@@ -18,6 +24,11 @@ pub const DID_ALICE: H160 = H160([0xff; 20]);
 pub const DID_BOB: H160 = H160([0xee; 20]);
 pub const DID_CHARLIE: H160 = H160([0xdd; 20]);
 
+pub const POLICY_DEFAULT: u8 = 0;
+pub const POLICY_FIXED_INCREMENT: u8 = 1;
+pub const POLICY_ENGLISH_AUCTION: u8 = 2;
+pub const POLICY_SEALED_BID: u8 = 3;
+
 frame_support::construct_runtime!(
     pub enum Test where
         Block = Block,
@@ -183,7 +194,10 @@ impl parami_nft::Config for Test {
 }
 
 parameter_types! {
+    pub const SwapDefaultFee: Permill = Permill::from_parts(3_000);
+    pub const SwapMaxPathLen: u32 = 8;
     pub const SwapPalletId: PalletId = PalletId(*b"prm/swap");
+    pub const SwapProtocolFeeShare: Permill = Permill::zero();
 }
 
 impl parami_swap::Config for Test {
@@ -191,13 +205,19 @@ impl parami_swap::Config for Test {
     type AssetId = AssetId;
     type Assets = Assets;
     type Currency = Balances;
+    type DefaultFee = SwapDefaultFee;
     type FarmingCurve = ();
+    type ForceOrigin = EnsureRoot<Self::AccountId>;
+    type MaxPathLen = SwapMaxPathLen;
     type PalletId = SwapPalletId;
+    type ProtocolFee = ();
+    type ProtocolFeeShare = SwapProtocolFeeShare;
     type WeightInfo = ();
 }
 
 parameter_types! {
     pub const SubmissionFee: Balance = 1;
+    pub const MaxTagLen: u32 = 32;
 }
 
 impl parami_tag::Config for Test {
@@ -205,6 +225,7 @@ impl parami_tag::Config for Test {
     type Currency = Balances;
     type DecentralizedId = H160;
     type SubmissionFee = SubmissionFee;
+    type MaxTagLen = MaxTagLen;
     type CallOrigin = parami_did::EnsureDid<Self>;
     type ForceOrigin = EnsureRoot<Self::AccountId>;
     type WeightInfo = ();
@@ -214,7 +235,100 @@ parameter_types! {
     pub const AdPalletId: PalletId = PalletId(*b"prm/ad  ");
     pub const AdvertiserMinimumFee: Balance = 1;
     pub const PayoutBase: Balance = 1;
+    pub const MaxMetadataLen: u32 = 128;
     pub const SlotLifetime: BlockNumber = 43200;
+    pub const MaxSlotsPerKol: u32 = 3;
+    pub const MaxAdsPerAdvertiser: u32 = 3;
+    pub const MaxSlotsPerAd: u32 = 3;
+    pub const MaxBidsPerRound: u32 = 2;
+    pub const MaxBatchSize: u32 = 10;
+    pub storage MaxExpiredPerBlock: u32 = 100;
+    pub storage MaxExpiryDelay: u32 = 10;
+    pub const ExtendSlotsOnResume: bool = true;
+    pub const MaxReferralLevels: u32 = 2;
+    pub const MaxTargetingLen: u32 = 4;
+    pub const MaxSlotConfigLen: u32 = 4;
+    pub storage KolShare: Percent = Percent::from_percent(0);
+    pub storage HolderShare: Percent = Percent::from_percent(0);
+    pub const BidIncrement: Balance = 100;
+    pub const SnipingWindow: BlockNumber = 100;
+    pub const SnipingExtension: BlockNumber = 200;
+    pub const CommitPeriod: BlockNumber = 10;
+    pub const RevealPeriod: BlockNumber = 10;
+    pub storage BidPolicyKind: u8 = POLICY_DEFAULT;
+    pub storage LinkedAccounts: Vec<(H160, u8)> = vec![];
+}
+
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum MockSignature {
+    Sr25519(sr25519::Signature),
+    /// Signed by an account if the payload matches
+    Mock(sr25519::Public, Vec<u8>),
+}
+
+impl Verify for MockSignature {
+    type Signer = sr25519::Public;
+
+    fn verify<L: Lazy<[u8]>>(&self, mut msg: L, signer: &sr25519::Public) -> bool {
+        match self {
+            MockSignature::Sr25519(signature) => signature.verify(msg, signer),
+            MockSignature::Mock(account, payload) => account == signer && payload[..] == *msg.get(),
+        }
+    }
+}
+
+impl From<sr25519::Signature> for MockSignature {
+    fn from(signature: sr25519::Signature) -> Self {
+        MockSignature::Sr25519(signature)
+    }
+}
+
+pub struct MockLinks;
+
+impl parami_traits::Links for MockLinks {
+    type DecentralizedId = H160;
+    type AccountType = u8;
+
+    fn has_link(did: &H160, site: &u8) -> bool {
+        LinkedAccounts::get().contains(&(*did, *site))
+    }
+}
+
+type English = EnglishAuction<BidIncrement, SnipingWindow, SnipingExtension>;
+type Sealed = SealedBid<CommitPeriod, RevealPeriod>;
+
+/// Bid policy selected by `BidPolicyKind`, so that all policies share one runtime
+pub struct MockBidPolicy;
+
+impl BidPolicy<Test> for MockBidPolicy {
+    fn outbid(value: Balance, current: Balance) -> bool {
+        match BidPolicyKind::get() {
+            POLICY_FIXED_INCREMENT => {
+                <FixedIncrement<BidIncrement> as BidPolicy<Test>>::outbid(value, current)
+            }
+            POLICY_ENGLISH_AUCTION => <English as BidPolicy<Test>>::outbid(value, current),
+            POLICY_SEALED_BID => <Sealed as BidPolicy<Test>>::outbid(value, current),
+            _ => <() as BidPolicy<Test>>::outbid(value, current),
+        }
+    }
+
+    fn deadline(
+        now: BlockNumber,
+        lifetime: BlockNumber,
+        outbid: Option<BlockNumber>,
+    ) -> BlockNumber {
+        match BidPolicyKind::get() {
+            POLICY_ENGLISH_AUCTION => <English as BidPolicy<Test>>::deadline(now, lifetime, outbid),
+            _ => lifetime,
+        }
+    }
+
+    fn sealed() -> Option<(BlockNumber, BlockNumber)> {
+        match BidPolicyKind::get() {
+            POLICY_SEALED_BID => <Sealed as BidPolicy<Test>>::sealed(),
+            _ => None,
+        }
+    }
 }
 
 impl parami_ad::Config for Test {
@@ -223,9 +337,28 @@ impl parami_ad::Config for Test {
     type MinimumFeeBalance = AdvertiserMinimumFee;
     type PalletId = AdPalletId;
     type PayoutBase = PayoutBase;
+    type MaxMetadataLen = MaxMetadataLen;
     type SlotLifetime = SlotLifetime;
+    type MaxSlotsPerKol = MaxSlotsPerKol;
+    type MaxAdsPerAdvertiser = MaxAdsPerAdvertiser;
+    type MaxSlotsPerAd = MaxSlotsPerAd;
+    type MaxBidsPerRound = MaxBidsPerRound;
+    type MaxBatchSize = MaxBatchSize;
+    type MaxExpiredPerBlock = MaxExpiredPerBlock;
+    type MaxExpiryDelay = MaxExpiryDelay;
+    type ExtendSlotsOnResume = ExtendSlotsOnResume;
+    type MaxReferralLevels = MaxReferralLevels;
+    type MaxTagLen = MaxTagLen;
+    type MaxTargetingLen = MaxTargetingLen;
+    type MaxSlotConfigLen = MaxSlotConfigLen;
+    type KolShare = KolShare;
+    type HolderShare = HolderShare;
+    type BidPolicy = MockBidPolicy;
     type Swaps = Swap;
     type Tags = Tag;
+    type Links = MockLinks;
+    type Signature = MockSignature;
+    type Signer = sr25519::Public;
     type CallOrigin = parami_did::EnsureDid<Self>;
     type ForceOrigin = EnsureRoot<Self::AccountId>;
     type WeightInfo = ();
@@ -262,5 +395,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
     .assimilate_storage(&mut t)
     .unwrap();
 
-    t.into()
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.register_extension(KeystoreExt(Arc::new(KeyStore::new())));
+    ext
 }
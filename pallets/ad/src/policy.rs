@@ -0,0 +1,97 @@
+use crate::{BalanceOf, Config, HeightOf};
+use frame_support::traits::Get;
+use sp_runtime::traits::Saturating;
+use sp_std::marker::PhantomData;
+
+/// Rules of bidding for slots of a KOL
+pub trait BidPolicy<T: Config> {
+    /// Check if a bid of `value` outbids a slot currently worth `current`
+    fn outbid(value: BalanceOf<T>, current: BalanceOf<T>) -> bool;
+
+    /// Get deadline of a slot taken by a bid
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current height
+    /// * `lifetime` - The height when a fresh slot expires
+    /// * `outbid` - The deadline of the slot being outbid, if any
+    fn deadline(
+        _now: HeightOf<T>,
+        lifetime: HeightOf<T>,
+        _outbid: Option<HeightOf<T>>,
+    ) -> HeightOf<T> {
+        lifetime
+    }
+
+    /// Get commit and reveal periods if bids must be sealed
+    fn sealed() -> Option<(HeightOf<T>, HeightOf<T>)> {
+        None
+    }
+}
+
+/// Require a 20% increase of current value
+impl<T: Config> BidPolicy<T> for () {
+    fn outbid(value: BalanceOf<T>, current: BalanceOf<T>) -> bool {
+        value.saturating_mul(100u32.into()) / 120u32.into() > current
+    }
+}
+
+/// Require a fixed minimum increment over current value
+pub struct FixedIncrement<I>(PhantomData<I>);
+
+impl<T: Config, I: Get<BalanceOf<T>>> BidPolicy<T> for FixedIncrement<I> {
+    fn outbid(value: BalanceOf<T>, current: BalanceOf<T>) -> bool {
+        value >= current.saturating_add(I::get())
+    }
+}
+
+/// English auction with a fixed minimum increment
+///
+/// The winner takes over the deadline of the slot being outbid,
+/// which is extended by `E` if the bid arrives within the last `W` blocks.
+pub struct EnglishAuction<I, W, E>(PhantomData<(I, W, E)>);
+
+impl<T, I, W, E> BidPolicy<T> for EnglishAuction<I, W, E>
+where
+    T: Config,
+    I: Get<BalanceOf<T>>,
+    W: Get<HeightOf<T>>,
+    E: Get<HeightOf<T>>,
+{
+    fn outbid(value: BalanceOf<T>, current: BalanceOf<T>) -> bool {
+        value >= current.saturating_add(I::get())
+    }
+
+    fn deadline(
+        now: HeightOf<T>,
+        lifetime: HeightOf<T>,
+        outbid: Option<HeightOf<T>>,
+    ) -> HeightOf<T> {
+        match outbid {
+            Some(deadline) if deadline <= now.saturating_add(W::get()) => {
+                deadline.saturating_add(E::get())
+            }
+            Some(deadline) => deadline,
+            None => lifetime,
+        }
+    }
+}
+
+/// Sealed-bid round, bids are committed in `C` blocks and revealed in the following `R` blocks,
+/// the highest revealed bid is placed when the round is settled
+pub struct SealedBid<C, R>(PhantomData<(C, R)>);
+
+impl<T, C, R> BidPolicy<T> for SealedBid<C, R>
+where
+    T: Config,
+    C: Get<HeightOf<T>>,
+    R: Get<HeightOf<T>>,
+{
+    fn outbid(value: BalanceOf<T>, current: BalanceOf<T>) -> bool {
+        value > current
+    }
+
+    fn sealed() -> Option<(HeightOf<T>, HeightOf<T>)> {
+        Some((C::get(), R::get()))
+    }
+}
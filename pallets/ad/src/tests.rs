@@ -1,10 +1,27 @@
-use crate::{mock::*, AdsOf, Config, DeadlineOf, Did, EndtimeOf, Error, Metadata, SlotOf};
+use crate::{
+    migrations,
+    mock::*,
+    types::{
+        self, AdCreative, AdStatus, Frequency, Migration, PayItem, PaySkipReason, Receipt,
+        RefererSource, RewardSplit, SlotConfig, Stats, Targeting,
+    },
+    weights::WeightInfo,
+    AdExpiry, AdsOf, CapacityOf, CommitmentsOf, Config, CreativeOf, DeadlineOf, Did, EndtimeOf,
+    Error, ExpiryCount, Metadata, Migrating, NonceOf, PausedAt, Payout, RoundOf, SlotExpiry,
+    SlotOf, SlotsOf,
+};
 use frame_support::{
     assert_noop, assert_ok,
-    traits::{Currency, Hooks},
+    storage::unhashed,
+    traits::{fungibles::Mutate, Currency, Hooks},
+    BoundedVec,
 };
 use parami_traits::Tags;
-use sp_core::{sr25519, H160};
+use sp_core::{sr25519, H160, H256};
+use sp_runtime::{
+    traits::{BlakeTwo256, Hash},
+    Percent,
+};
 use sp_std::collections::btree_map::BTreeMap;
 
 #[test]
@@ -21,15 +38,17 @@ fn should_create() {
             hashes.insert(hash, true);
         }
 
-        let metadata = vec![0u8; 64];
+        let creative = creative();
 
         assert_ok!(Ad::create(
             Origin::signed(ALICE),
             50,
             tags,
-            metadata.clone(),
+            creative.clone(),
+            RewardSplit::from(1),
             1,
-            1
+            None,
+            None
         ));
 
         assert_eq!(<AdsOf<Test>>::get(&DID_ALICE).unwrap().len(), 1);
@@ -42,8 +61,9 @@ fn should_create() {
         assert_eq!(meta.creator, DID_ALICE);
         assert_eq!(meta.budget, 50);
         assert_eq!(meta.remain, 50);
-        assert_eq!(meta.metadata, metadata);
-        assert_eq!(meta.reward_rate, 1);
+        assert_eq!(meta.creative, creative);
+        assert_eq!(meta.revision, 0);
+        assert_eq!(meta.reward_split, RewardSplit::from(1));
         assert_eq!(meta.created, 0);
 
         assert_eq!(<EndtimeOf<Test>>::get(&ad), Some(1));
@@ -54,11 +74,114 @@ fn should_create() {
     });
 }
 
+#[test]
+fn should_create_in_same_block() {
+    new_test_ext().execute_with(|| {
+        for budget in [30, 40] {
+            assert_ok!(Ad::create(
+                Origin::signed(ALICE),
+                budget,
+                vec![],
+                creative(),
+                RewardSplit::from(1),
+                1,
+                None,
+                None
+            ));
+        }
+
+        let ads = <AdsOf<Test>>::get(&DID_ALICE).unwrap();
+        assert_eq!(ads.len(), 2);
+        assert_ne!(ads[0], ads[1]);
+
+        assert_eq!(<Metadata<Test>>::get(&ads[0]).unwrap().budget, 30);
+        assert_eq!(<Metadata<Test>>::get(&ads[1]).unwrap().budget, 40);
+
+        assert_eq!(Balances::free_balance(&ALICE), 100 - 30 - 40);
+    });
+}
+
+#[test]
+fn should_bound_ads() {
+    new_test_ext().execute_with(|| {
+        for _ in 0..3 {
+            assert_ok!(Ad::create(
+                Origin::signed(BOB),
+                500,
+                vec![],
+                creative(),
+                RewardSplit::from(1),
+                100,
+                None,
+                None
+            ));
+        }
+
+        assert_noop!(
+            Ad::create(
+                Origin::signed(BOB),
+                500,
+                vec![],
+                creative(),
+                RewardSplit::from(1),
+                100,
+                None,
+                None
+            ),
+            Error::<Test>::TooManyAds
+        );
+
+        let ads = <AdsOf<Test>>::get(&DID_BOB).unwrap();
+
+        // terminated advertisements are unlisted
+
+        assert_ok!(Ad::terminate(Origin::signed(BOB), ads[0]));
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![],
+            creative(),
+            RewardSplit::from(1),
+            100,
+            None,
+            None
+        ));
+
+        // ended advertisements are unlisted
+
+        let endtime = <EndtimeOf<Test>>::get(&ads[1]).unwrap();
+
+        System::set_block_number(endtime);
+        Ad::on_initialize(endtime);
+
+        assert_eq!(
+            <Metadata<Test>>::get(&ads[1]).unwrap().status,
+            AdStatus::Ended
+        );
+        assert!(!<AdsOf<Test>>::get(&DID_BOB).unwrap().contains(&ads[1]));
+
+        assert_noop!(
+            Ad::terminate(Origin::signed(BOB), ads[1]),
+            Error::<Test>::AlreadyTerminated
+        );
+    });
+}
+
 #[test]
 fn should_fail_when_insufficient() {
     new_test_ext().execute_with(|| {
         assert_noop!(
-            Ad::create(Origin::signed(ALICE), 200, vec![], [0u8; 64].into(), 1, 1),
+            Ad::create(
+                Origin::signed(ALICE),
+                200,
+                vec![],
+                creative(),
+                RewardSplit::from(1),
+                1,
+                None,
+                None
+            ),
             pallet_balances::Error::<Test>::InsufficientBalance
         );
     });
@@ -74,29 +197,119 @@ fn should_fail_when_tag_not_exists() {
         ];
 
         assert_noop!(
-            Ad::create(Origin::signed(ALICE), 200, tags, [0u8; 64].into(), 1, 1),
+            Ad::create(
+                Origin::signed(ALICE),
+                200,
+                tags,
+                creative(),
+                RewardSplit::from(1),
+                1,
+                None,
+                None
+            ),
             Error::<Test>::TagNotExists
         );
     });
 }
 
 #[test]
-fn should_update_reward_rate() {
+fn should_update_reward_split() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Ad::create(
+            Origin::signed(ALICE),
+            50,
+            vec![],
+            creative(),
+            RewardSplit::from(1),
+            1,
+            None,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+
+        let split = RewardSplit {
+            source: RefererSource::Registered,
+            rates: vec![20, 10].try_into().unwrap(),
+        };
+
+        assert_ok!(Ad::update_reward_split(
+            Origin::signed(ALICE),
+            ad,
+            split.clone()
+        ));
+
+        assert_eq!(<Metadata<Test>>::get(&ad).unwrap().reward_split, split);
+
+        // referer levels are bounded
+        assert!(BoundedVec::<u16, MaxReferralLevels>::try_from(vec![10, 10, 10]).is_err());
+
+        assert_noop!(
+            Ad::update_reward_split(
+                Origin::signed(ALICE),
+                ad,
+                RewardSplit {
+                    source: RefererSource::Caller,
+                    rates: vec![60, 50].try_into().unwrap(),
+                }
+            ),
+            Error::<Test>::RewardSplitOutOfRange
+        );
+    });
+}
+
+#[test]
+fn should_update_metadata() {
     new_test_ext().execute_with(|| {
         assert_ok!(Ad::create(
             Origin::signed(ALICE),
             50,
             vec![],
-            [0u8; 64].into(),
+            creative(),
+            RewardSplit::from(1),
             1,
-            1
+            None,
+            None
         ));
 
         let ad = <Metadata<Test>>::iter_keys().next().unwrap();
 
-        assert_ok!(Ad::update_reward_rate(Origin::signed(ALICE), ad, 2));
+        let mut creative = creative();
+        creative.cid = b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+            .to_vec()
+            .try_into()
+            .unwrap();
+
+        assert_noop!(
+            Ad::update_metadata(Origin::signed(BOB), ad, creative.clone()),
+            Error::<Test>::NotOwned
+        );
+
+        assert_ok!(Ad::update_metadata(
+            Origin::signed(ALICE),
+            ad,
+            creative.clone()
+        ));
 
-        assert_eq!(<Metadata<Test>>::get(&ad).unwrap().reward_rate, 2);
+        let meta = <Metadata<Test>>::get(&ad).unwrap();
+        assert_eq!(meta.creative, creative);
+        assert_eq!(meta.revision, 1);
+
+        // malformed CIDs
+
+        for cid in [
+            &b"ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"[..],
+            &b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbd0"[..],
+            &b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbz"[..],
+            &b""[..],
+        ] {
+            creative.cid = cid.to_vec().try_into().unwrap();
+
+            assert_noop!(
+                Ad::update_metadata(Origin::signed(ALICE), ad, creative.clone()),
+                Error::<Test>::InvalidCid
+            );
+        }
     });
 }
 
@@ -104,7 +317,11 @@ fn should_update_reward_rate() {
 fn should_fail_when_not_exists_or_not_owned() {
     new_test_ext().execute_with(|| {
         assert_noop!(
-            Ad::update_reward_rate(Origin::signed(ALICE), Default::default(), 2),
+            Ad::update_reward_split(
+                Origin::signed(ALICE),
+                Default::default(),
+                RewardSplit::from(2)
+            ),
             Error::<Test>::NotExists
         );
 
@@ -112,15 +329,17 @@ fn should_fail_when_not_exists_or_not_owned() {
             Origin::signed(ALICE),
             50,
             vec![],
-            [0u8; 64].into(),
+            creative(),
+            RewardSplit::from(1),
             1,
-            1
+            None,
+            None
         ));
 
         let ad = <Metadata<Test>>::iter_keys().next().unwrap();
 
         assert_noop!(
-            Ad::update_reward_rate(Origin::signed(BOB), ad, 2),
+            Ad::update_reward_split(Origin::signed(BOB), ad, RewardSplit::from(2)),
             Error::<Test>::NotOwned
         );
     });
@@ -144,9 +363,11 @@ fn should_update_tags() {
             Origin::signed(ALICE),
             50,
             vec![vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8],],
-            [0u8; 64].into(),
+            creative(),
+            RewardSplit::from(1),
             1,
-            1
+            None,
+            None
         ));
 
         let ad = <Metadata<Test>>::iter_keys().next().unwrap();
@@ -164,9 +385,11 @@ fn should_add_budget() {
             Origin::signed(ALICE),
             50,
             vec![],
-            [0u8; 64].into(),
+            creative(),
+            RewardSplit::from(1),
             1,
-            1
+            None,
+            None
         ));
 
         let ad = <Metadata<Test>>::iter_keys().next().unwrap();
@@ -199,9 +422,11 @@ fn should_bid() {
             Origin::signed(BOB),
             500,
             vec![],
-            [0u8; 64].into(),
-            1,
-            43200
+            creative(),
+            RewardSplit::from(1),
+            43200,
+            None,
+            None
         ));
 
         let ad1 = <Metadata<Test>>::iter_keys().next().unwrap();
@@ -217,9 +442,11 @@ fn should_bid() {
             Origin::signed(CHARLIE),
             600,
             vec![],
-            [0u8; 64].into(),
+            creative(),
+            RewardSplit::from(1),
             1,
-            1
+            None,
+            None
         ));
 
         let ad2 = <Metadata<Test>>::iter_keys().next().unwrap();
@@ -243,7 +470,7 @@ fn should_bid() {
         assert_eq!(<EndtimeOf<Test>>::get(&ad1), Some(43200));
         assert_eq!(<DeadlineOf<Test>>::get(&DID_ALICE, &ad1), Some(43200));
 
-        let maybe_slot = <SlotOf<Test>>::get(&DID_ALICE);
+        let maybe_slot = Ad::find_slot(&DID_ALICE, &ad1);
         assert_ne!(maybe_slot, None);
 
         let meta1 = <Metadata<Test>>::get(&ad1).unwrap();
@@ -272,7 +499,9 @@ fn should_bid() {
         assert_eq!(<DeadlineOf<Test>>::get(&DID_ALICE, &ad1), None);
         assert_eq!(<DeadlineOf<Test>>::get(&DID_ALICE, &ad2), Some(1));
 
-        let maybe_slot = <SlotOf<Test>>::get(&DID_ALICE);
+        assert_eq!(Ad::find_slot(&DID_ALICE, &ad1), None);
+
+        let maybe_slot = Ad::find_slot(&DID_ALICE, &ad2);
         assert_ne!(maybe_slot, None);
 
         let meta1 = <Metadata<Test>>::get(&ad1).unwrap();
@@ -294,7 +523,7 @@ fn should_bid() {
 }
 
 #[test]
-fn should_drawback() {
+fn should_bid_multiple_slots() {
     new_test_ext().execute_with(|| {
         // 1. prepare
 
@@ -306,61 +535,100 @@ fn should_drawback() {
             b"XTT".to_vec()
         ));
 
-        // create ad
+        assert_eq!(<CapacityOf<Test>>::get(&DID_ALICE), 1);
+
+        assert_noop!(
+            Ad::set_capacity(Origin::signed(ALICE), 0),
+            Error::<Test>::CapacityOutOfRange
+        );
+        assert_noop!(
+            Ad::set_capacity(Origin::signed(ALICE), 4),
+            Error::<Test>::CapacityOutOfRange
+        );
+
+        assert_ok!(Ad::set_capacity(Origin::signed(ALICE), 2));
+
+        assert_eq!(<CapacityOf<Test>>::get(&DID_ALICE), 2);
+
+        // create ads
 
         assert_ok!(Ad::create(
             Origin::signed(BOB),
             500,
             vec![],
-            [0u8; 64].into(),
-            1,
-            43200 * 2
+            creative(),
+            RewardSplit::from(1),
+            43200,
+            None,
+            None
         ));
 
-        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
-
-        // bid
-
-        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
-
-        // 2. step in
-
-        System::set_block_number(43200);
+        assert_ok!(Ad::create(
+            Origin::signed(CHARLIE),
+            500,
+            vec![],
+            creative(),
+            RewardSplit::from(1),
+            43200,
+            None,
+            None
+        ));
 
-        Ad::on_initialize(System::block_number());
+        System::set_block_number(1);
 
-        // ensure slot, remain
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![],
+            creative(),
+            RewardSplit::from(1),
+            43200,
+            None,
+            None
+        ));
 
-        assert_eq!(<SlotOf<Test>>::get(&DID_ALICE), None);
+        let ads = <AdsOf<Test>>::get(&DID_BOB).unwrap();
+        let (ad1, ad3) = (ads[0], ads[1]);
+        let ad2 = <AdsOf<Test>>::get(&DID_CHARLIE).unwrap()[0];
 
-        let meta = <Metadata<Test>>::get(&ad).unwrap();
-        assert_eq!(meta.remain, 497);
+        // 2. fill both slots, a lower bid is accepted while a slot is free
 
-        assert_eq!(Balances::free_balance(&meta.pot), meta.remain);
-        assert_eq!(Assets::balance(0, &meta.pot), 0);
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad1, DID_ALICE, 400));
+        assert_ok!(Ad::bid(Origin::signed(CHARLIE), ad2, DID_ALICE, 300));
 
-        // 3. step in
+        let slots = <SlotOf<Test>>::get(&DID_ALICE);
+        assert_eq!(slots.len(), 2);
+        assert_eq!(slots[0].ad, ad1);
+        assert_eq!(slots[1].ad, ad2);
 
-        System::set_block_number(43200 * 2);
+        // 3. all slots are used, outbid the lowest one
 
-        Ad::on_initialize(System::block_number());
+        assert_noop!(
+            Ad::bid(Origin::signed(BOB), ad3, DID_ALICE, 300),
+            Error::<Test>::Underbid
+        );
 
-        // ensure remain
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad3, DID_ALICE, 400));
 
-        let meta = <Metadata<Test>>::get(&ad).unwrap();
+        let slots = <SlotOf<Test>>::get(&DID_ALICE);
+        assert_eq!(slots.len(), 2);
+        assert_eq!(slots[0].ad, ad1);
+        assert_eq!(slots[1].ad, ad3);
 
-        assert_eq!(meta.remain, 0);
-        assert_eq!(Balances::free_balance(&meta.pot), meta.remain);
+        assert_eq!(Ad::find_slot(&DID_ALICE, &ad2), None);
+        assert_eq!(<DeadlineOf<Test>>::get(&DID_ALICE, &ad2), None);
+        assert_eq!(<SlotsOf<Test>>::get(&ad2).unwrap().len(), 0);
+        assert_eq!(<DeadlineOf<Test>>::get(&DID_ALICE, &ad1), Some(43200));
+        assert_eq!(<DeadlineOf<Test>>::get(&DID_ALICE, &ad3), Some(43200));
 
-        assert_eq!(
-            Balances::free_balance(&BOB),
-            3_000_000 - 2_000_100 - 500 + 497
-        );
+        let meta2 = <Metadata<Test>>::get(&ad2).unwrap();
+        assert_eq!(Balances::free_balance(&meta2.pot), meta2.remain);
+        assert_eq!(Assets::balance(0, &meta2.pot), 0);
     });
 }
 
 #[test]
-fn should_pay() {
+fn should_enforce_slot_config() {
     new_test_ext().execute_with(|| {
         // 1. prepare
 
@@ -374,108 +642,2246 @@ fn should_pay() {
             b"XTT".to_vec()
         ));
 
-        // create ad
-
         assert_ok!(Ad::create(
             Origin::signed(BOB),
             500,
             vec![b"Test".to_vec()],
-            [0u8; 64].into(),
-            1,
-            1
+            creative(),
+            RewardSplit::from(1),
+            43200,
+            None,
+            None
         ));
+        let ad1 = Ad::ads_of(&DID_BOB).unwrap()[0];
 
-        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
-        let meta = <Metadata<Test>>::get(&ad).unwrap();
+        assert_ok!(Ad::create(
+            Origin::signed(CHARLIE),
+            600,
+            vec![],
+            creative(),
+            RewardSplit::from(1),
+            43200,
+            None,
+            None
+        ));
+        let ad2 = Ad::ads_of(&DID_CHARLIE).unwrap()[0];
 
-        // bid
+        // 2. configure slot
 
-        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+        let mut config = SlotConfig {
+            reserve: 100,
+            tags: vec![b"Unknown".to_vec().try_into().unwrap()]
+                .try_into()
+                .unwrap(),
+            blocked: vec![DID_CHARLIE].try_into().unwrap(),
+        };
 
-        // 2. pay
+        assert_noop!(
+            Ad::set_slot_config(Origin::signed(ALICE), config.clone()),
+            Error::<Test>::TagNotExists
+        );
 
-        assert_ok!(Ad::pay(
-            Origin::signed(BOB),
-            ad,
-            DID_ALICE,
-            DID_CHARLIE,
-            vec![(b"Test".to_vec(), 5)],
-            None
-        ));
+        config.tags = vec![b"Test".to_vec().try_into().unwrap()]
+            .try_into()
+            .unwrap();
 
-        let slot = <SlotOf<Test>>::get(&DID_ALICE).unwrap();
-        assert_eq!(Assets::balance(0, &meta.pot), slot.tokens);
-        assert_eq!(slot.remain, 400 - 40);
-        assert_eq!(slot.tokens, 19 - 5);
+        assert_ok!(Ad::set_slot_config(Origin::signed(ALICE), config.clone()));
 
-        assert_eq!(Assets::balance(0, &CHARLIE), 5);
+        assert_eq!(Ad::slot_config_of(&DID_ALICE), Some(config.clone()));
 
-        assert_eq!(Tag::get_score(&DID_CHARLIE, b"Test".to_vec()), 5);
+        // 3. bid
 
         assert_noop!(
-            Ad::pay(
-                Origin::signed(BOB),
-                ad,
-                DID_ALICE,
-                DID_CHARLIE,
-                vec![(b"Test".to_vec(), 5)],
-                None
-            ),
-            Error::<Test>::Paid
+            Ad::bid(Origin::signed(CHARLIE), ad2, DID_ALICE, 480),
+            Error::<Test>::Blocked
+        );
+
+        config.blocked = Default::default();
+        assert_ok!(Ad::set_slot_config(Origin::signed(ALICE), config));
+
+        assert_noop!(
+            Ad::bid(Origin::signed(CHARLIE), ad2, DID_ALICE, 480),
+            Error::<Test>::TagNotAccepted
+        );
+
+        assert_noop!(
+            Ad::bid(Origin::signed(BOB), ad1, DID_ALICE, 50),
+            Error::<Test>::BelowReserve
+        );
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad1, DID_ALICE, 400));
+
+        // 4. evict
+
+        assert_noop!(
+            Ad::evict(Origin::signed(BOB), ad1),
+            Error::<Test>::NotExists
+        );
+
+        assert_ok!(Ad::evict(Origin::signed(ALICE), ad1));
+
+        assert_eq!(Ad::find_slot(&DID_ALICE, &ad1), None);
+        assert_eq!(<DeadlineOf<Test>>::get(&DID_ALICE, &ad1), None);
+        assert_eq!(<SlotsOf<Test>>::get(&ad1).unwrap().len(), 0);
+
+        let meta1 = <Metadata<Test>>::get(&ad1).unwrap();
+        assert_eq!(Balances::free_balance(&meta1.pot), meta1.remain);
+
+        assert_noop!(
+            Ad::evict(Origin::signed(ALICE), ad1),
+            Error::<Test>::NotExists
         );
     });
 }
 
+fn prepare_policy_test(policy: u8, budget: u128) -> (H256, H256) {
+    BidPolicyKind::set(&policy);
+
+    assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+    assert_ok!(Nft::mint(
+        Origin::signed(ALICE),
+        b"Test Token".to_vec(),
+        b"XTT".to_vec()
+    ));
+
+    assert_ok!(Ad::create(
+        Origin::signed(BOB),
+        budget,
+        vec![],
+        creative(),
+        RewardSplit::from(1),
+        43200 * 2,
+        None,
+        None
+    ));
+
+    assert_ok!(Ad::create(
+        Origin::signed(CHARLIE),
+        budget,
+        vec![],
+        creative(),
+        RewardSplit::from(1),
+        43200 * 2,
+        None,
+        None
+    ));
+
+    let ad1 = <AdsOf<Test>>::get(&DID_BOB).unwrap()[0];
+    let ad2 = <AdsOf<Test>>::get(&DID_CHARLIE).unwrap()[0];
+
+    (ad1, ad2)
+}
+
 #[test]
-fn should_auto_swap_when_swapped_token_used_up() {
+fn should_bid_with_fixed_increment() {
     new_test_ext().execute_with(|| {
-        // 1. prepare
+        let (ad1, ad2) = prepare_policy_test(POLICY_FIXED_INCREMENT, 600);
 
-        assert_ok!(Tag::create(Origin::signed(ALICE), b"Test".to_vec()));
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad1, DID_ALICE, 400));
 
-        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+        // slot of ad1 is worth 397, a 20% increase is not enough
 
-        assert_ok!(Nft::mint(
-            Origin::signed(ALICE),
-            b"Test Token".to_vec(),
-            b"XTT".to_vec()
-        ));
+        assert_noop!(
+            Ad::bid(Origin::signed(CHARLIE), ad2, DID_ALICE, 480),
+            Error::<Test>::Underbid
+        );
 
-        // create ad
+        assert_ok!(Ad::bid(Origin::signed(CHARLIE), ad2, DID_ALICE, 497));
 
-        assert_ok!(Ad::create(
-            Origin::signed(BOB),
-            500,
-            vec![b"Test".to_vec()],
-            [0u8; 64].into(),
-            1,
-            1
-        ));
+        assert_eq!(Ad::find_slot(&DID_ALICE, &ad1), None);
+        assert_ne!(Ad::find_slot(&DID_ALICE, &ad2), None);
+    });
+}
 
-        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+#[test]
+fn should_extend_deadline_in_english_auction() {
+    new_test_ext().execute_with(|| {
+        let (ad1, ad2) = prepare_policy_test(POLICY_ENGLISH_AUCTION, 1000);
 
-        // bid
+        System::set_block_number(1);
 
-        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad1, DID_ALICE, 400));
 
-        // 2. pay to 9 users, 5 tokens each
-        let viewer_dids = make_dids(9u8);
-        for viewer_did in &viewer_dids {
-            assert_ok!(Ad::pay(
-                Origin::signed(BOB),
-                ad,
-                DID_ALICE,
-                *viewer_did,
+        assert_eq!(<DeadlineOf<Test>>::get(&DID_ALICE, &ad1), Some(43201));
+
+        // outbid early, the winner takes over current deadline
+
+        System::set_block_number(1000);
+
+        assert_noop!(
+            Ad::bid(Origin::signed(CHARLIE), ad2, DID_ALICE, 480),
+            Error::<Test>::Underbid
+        );
+
+        assert_ok!(Ad::bid(Origin::signed(CHARLIE), ad2, DID_ALICE, 500));
+
+        assert_eq!(<DeadlineOf<Test>>::get(&DID_ALICE, &ad1), None);
+        assert_eq!(<DeadlineOf<Test>>::get(&DID_ALICE, &ad2), Some(43201));
+
+        // outbid within the sniping window, the deadline is extended
+
+        System::set_block_number(43150);
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad1, DID_ALICE, 700));
+
+        assert_eq!(<DeadlineOf<Test>>::get(&DID_ALICE, &ad2), None);
+        assert_eq!(<DeadlineOf<Test>>::get(&DID_ALICE, &ad1), Some(43401));
+    });
+}
+
+#[test]
+fn should_bid_sealed() {
+    new_test_ext().execute_with(|| {
+        let (ad1, ad2) = prepare_policy_test(POLICY_SEALED_BID, 600);
+
+        let commitment1 =
+            BlakeTwo256::hash_of(&(DID_BOB, ad1, DID_ALICE, 400u128, b"salt1".to_vec()));
+        let commitment2 =
+            BlakeTwo256::hash_of(&(DID_CHARLIE, ad2, DID_ALICE, 420u128, b"salt2".to_vec()));
+
+        System::set_block_number(1);
+
+        // 1. open bid is not allowed
+
+        assert_noop!(
+            Ad::bid(Origin::signed(BOB), ad1, DID_ALICE, 400),
+            Error::<Test>::Sealed
+        );
+
+        // 2. commit
+
+        assert_noop!(
+            Ad::commit_bid(Origin::signed(CHARLIE), ad1, DID_ALICE, commitment1),
+            Error::<Test>::NotOwned
+        );
+
+        assert_ok!(Ad::commit_bid(
+            Origin::signed(BOB),
+            ad1,
+            DID_ALICE,
+            commitment1
+        ));
+
+        assert_eq!(<RoundOf<Test>>::get(&DID_ALICE), Some(1));
+
+        System::set_block_number(5);
+
+        assert_ok!(Ad::commit_bid(
+            Origin::signed(CHARLIE),
+            ad2,
+            DID_ALICE,
+            commitment2
+        ));
+
+        assert_eq!(<CommitmentsOf<Test>>::get(&DID_ALICE).len(), 2);
+
+        assert_noop!(
+            Ad::reveal_bid(Origin::signed(BOB), ad1, DID_ALICE, 400, b"salt1".to_vec()),
+            Error::<Test>::RevealClosed
+        );
+
+        // 3. reveal, nothing is placed until the round is settled
+
+        System::set_block_number(11);
+
+        assert_noop!(
+            Ad::commit_bid(Origin::signed(BOB), ad1, DID_ALICE, commitment1),
+            Error::<Test>::CommitClosed
+        );
+
+        assert_noop!(
+            Ad::reveal_bid(Origin::signed(BOB), ad1, DID_ALICE, 500, b"salt1".to_vec()),
+            Error::<Test>::InvalidCommitment
+        );
+
+        // commitments are bound to the advertiser
+
+        assert_noop!(
+            Ad::reveal_bid(
+                Origin::signed(CHARLIE),
+                ad1,
+                DID_ALICE,
+                400,
+                b"salt1".to_vec()
+            ),
+            Error::<Test>::InvalidCommitment
+        );
+
+        assert_ok!(Ad::reveal_bid(
+            Origin::signed(CHARLIE),
+            ad2,
+            DID_ALICE,
+            420,
+            b"salt2".to_vec()
+        ));
+
+        assert_ok!(Ad::reveal_bid(
+            Origin::signed(BOB),
+            ad1,
+            DID_ALICE,
+            400,
+            b"salt1".to_vec()
+        ));
+
+        assert_eq!(Ad::find_slot(&DID_ALICE, &ad1), None);
+        assert_eq!(Ad::find_slot(&DID_ALICE, &ad2), None);
+
+        assert_noop!(
+            Ad::settle_bid(Origin::signed(BOB), DID_ALICE),
+            Error::<Test>::RevealOpen
+        );
+
+        // 4. the highest bid wins, regardless of reveal order
+
+        System::set_block_number(21);
+
+        assert_noop!(
+            Ad::commit_bid(Origin::signed(BOB), ad1, DID_ALICE, commitment1),
+            Error::<Test>::NotSettled
+        );
+
+        assert_ok!(Ad::settle_bid(Origin::signed(BOB), DID_ALICE));
+
+        assert_eq!(Ad::find_slot(&DID_ALICE, &ad1), None);
+        assert_ne!(Ad::find_slot(&DID_ALICE, &ad2), None);
+
+        assert_eq!(<RoundOf<Test>>::get(&DID_ALICE), None);
+        assert!(<CommitmentsOf<Test>>::get(&DID_ALICE).is_empty());
+
+        assert!(System::events()
+            .iter()
+            .any(|r| r.event == Event::Ad(crate::Event::BidSettled(DID_ALICE, Some(ad2)))));
+
+        // 5. a new round starts, a revealed bid which is no longer valid is skipped
+
+        let commitment1 =
+            BlakeTwo256::hash_of(&(DID_BOB, ad1, DID_ALICE, 700u128, b"salt1".to_vec()));
+
+        assert_ok!(Ad::commit_bid(
+            Origin::signed(BOB),
+            ad1,
+            DID_ALICE,
+            commitment1
+        ));
+
+        assert_eq!(<RoundOf<Test>>::get(&DID_ALICE), Some(21));
+
+        System::set_block_number(31);
+
+        assert_ok!(Ad::reveal_bid(
+            Origin::signed(BOB),
+            ad1,
+            DID_ALICE,
+            700,
+            b"salt1".to_vec()
+        ));
+
+        System::set_block_number(41);
+
+        assert_ok!(Ad::settle_bid(Origin::signed(CHARLIE), DID_ALICE));
+
+        assert_eq!(Ad::find_slot(&DID_ALICE, &ad1), None);
+        assert_ne!(Ad::find_slot(&DID_ALICE, &ad2), None);
+
+        assert!(System::events()
+            .iter()
+            .any(|r| r.event == Event::Ad(crate::Event::BidSettled(DID_ALICE, None))));
+    });
+}
+
+#[test]
+fn should_drawback() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        // create ad
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![],
+            creative(),
+            RewardSplit::from(1),
+            43200 * 2,
+            None,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+
+        // bid
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        // 2. step in
+
+        System::set_block_number(43200);
+
+        Ad::on_initialize(System::block_number());
+
+        // ensure slot, remain
+
+        assert_eq!(<SlotOf<Test>>::get(&DID_ALICE).len(), 0);
+
+        let meta = <Metadata<Test>>::get(&ad).unwrap();
+        assert_eq!(meta.remain, 497);
+
+        assert_eq!(Balances::free_balance(&meta.pot), meta.remain);
+        assert_eq!(Assets::balance(0, &meta.pot), 0);
+
+        // 3. step in
+
+        System::set_block_number(43200 * 2);
+
+        Ad::on_initialize(System::block_number());
+
+        // ensure remain
+
+        let meta = <Metadata<Test>>::get(&ad).unwrap();
+
+        assert_eq!(meta.remain, 0);
+        assert_eq!(Balances::free_balance(&meta.pot), meta.remain);
+
+        assert_eq!(
+            Balances::free_balance(&BOB),
+            3_000_000 - 2_000_100 - 500 + 497
+        );
+    });
+}
+
+#[test]
+fn should_expire_in_queue() {
+    new_test_ext().execute_with(|| {
+        MaxExpiredPerBlock::set(&1);
+
+        // 1. ads ending at the same height are queued in subsequent blocks
+
+        let mut ads = vec![];
+        for i in 1..=3 {
+            System::set_block_number(i);
+
+            assert_ok!(Ad::create(
+                Origin::signed(BOB),
+                500,
+                vec![],
+                creative(),
+                RewardSplit::from(1),
+                100,
+                None,
+                None
+            ));
+
+            ads.push(*<AdsOf<Test>>::get(&DID_BOB).unwrap().last().unwrap());
+        }
+
+        assert_eq!(<EndtimeOf<Test>>::get(&ads[0]), Some(100));
+        assert_eq!(<EndtimeOf<Test>>::get(&ads[1]), Some(101));
+        assert_eq!(<EndtimeOf<Test>>::get(&ads[2]), Some(102));
+
+        // 2. terminate releases the queue
+
+        assert_ok!(Ad::terminate(Origin::signed(BOB), ads[2]));
+
+        assert_eq!(<ExpiryCount<Test>>::get(102), 0);
+        assert_eq!(<AdExpiry<Test>>::get(102, &ads[2]), None);
+
+        // 3. only due items are touched
+
+        System::set_block_number(100);
+
+        assert_eq!(
+            Ad::on_initialize(System::block_number()),
+            <Test as Config>::WeightInfo::on_initialize(1)
+        );
+
+        assert_eq!(<Metadata<Test>>::get(&ads[0]).unwrap().remain, 0);
+        assert_eq!(<Metadata<Test>>::get(&ads[1]).unwrap().remain, 500);
+
+        System::set_block_number(101);
+
+        Ad::on_initialize(System::block_number());
+
+        assert_eq!(<Metadata<Test>>::get(&ads[1]).unwrap().remain, 0);
+        assert_eq!(<EndtimeOf<Test>>::get(&ads[1]), None);
+        assert_eq!(<ExpiryCount<Test>>::get(101), 0);
+
+        assert_eq!(
+            Ad::on_initialize(102),
+            <Test as Config>::WeightInfo::on_initialize(0)
+        );
+
+        // 4. items are not queued beyond the maximum delay
+
+        MaxExpiryDelay::set(&1);
+
+        for _ in 0..2 {
+            assert_ok!(Ad::create(
+                Origin::signed(BOB),
+                500,
+                vec![],
+                creative(),
+                RewardSplit::from(1),
+                200,
+                None,
+                None
+            ));
+        }
+
+        assert_eq!(<ExpiryCount<Test>>::get(200), 1);
+        assert_eq!(<ExpiryCount<Test>>::get(201), 1);
+
+        assert_noop!(
+            Ad::create(
+                Origin::signed(BOB),
+                500,
+                vec![],
+                creative(),
+                RewardSplit::from(1),
+                200,
+                None,
+                None
+            ),
+            Error::<Test>::ExpiryQueueFull
+        );
+    });
+}
+
+#[test]
+fn should_migrate_across_blocks() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![],
+            creative(),
+            RewardSplit::from(1),
+            100,
+            None,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        // 2. leave the deadline, end time and payout as in V0,
+        // with the expiry queue full up to the maximum delay
+
+        let deadline = <DeadlineOf<Test>>::get(&DID_ALICE, &ad).unwrap();
+        let endtime = <EndtimeOf<Test>>::get(&ad).unwrap();
+
+        <SlotExpiry<Test>>::remove(deadline, (DID_ALICE, ad));
+        <AdExpiry<Test>>::remove(endtime, ad);
+
+        MaxExpiredPerBlock::set(&1);
+
+        let delay = MaxExpiryDelay::get() as u64;
+        for height in deadline.min(endtime)..=deadline.max(endtime) + delay {
+            <ExpiryCount<Test>>::insert(height, 1);
+        }
+
+        unhashed::put(&<Payout<Test>>::hashed_key_for(&ad, &DID_CHARLIE), &1u64);
+
+        assert_eq!(<Payout<Test>>::get(&ad, &DID_CHARLIE), None);
+        assert_eq!(
+            migrations::payout_of::<Test>(&ad, &DID_CHARLIE),
+            Some(types::Payout { count: 1, last: 1 })
+        );
+
+        <Migrating<Test>>::put((Migration::Deadlines(None), 0));
+
+        // 3. migrate
+
+        Ad::on_initialize(System::block_number());
+
+        assert_eq!(<Migrating<Test>>::get(), None);
+
+        let queued = <DeadlineOf<Test>>::get(&DID_ALICE, &ad).unwrap();
+        assert!(queued > deadline + delay);
+        assert!(<SlotExpiry<Test>>::contains_key(queued, (DID_ALICE, ad)));
+
+        let queued = <EndtimeOf<Test>>::get(&ad).unwrap();
+        assert!(queued > endtime + delay);
+        assert!(<AdExpiry<Test>>::contains_key(queued, ad));
+
+        assert_eq!(
+            <Payout<Test>>::get(&ad, &DID_CHARLIE),
+            Some(types::Payout { count: 1, last: 1 })
+        );
+
+        assert!(System::events()
+            .iter()
+            .any(|r| r.event == Event::Ad(crate::Event::Migrated(0))));
+    });
+}
+
+#[test]
+fn should_drop_slot_failed_to_drawback() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![],
+            creative(),
+            RewardSplit::from(1),
+            43200 * 2,
+            None,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        // 2. tokens of the slot can no longer be sold
+
+        let slot = Ad::find_slot(&DID_ALICE, &ad).unwrap();
+        let meta = <Metadata<Test>>::get(&ad).unwrap();
+
+        assert_ok!(<Assets as Mutate<_>>::burn_from(0, &meta.pot, 1));
+
+        // 3. the slot is closed at its deadline anyway
+
+        System::set_block_number(43200);
+
+        Ad::on_initialize(System::block_number());
+
+        assert_eq!(<SlotOf<Test>>::get(&DID_ALICE).len(), 0);
+        assert_eq!(<SlotsOf<Test>>::get(&ad).unwrap().len(), 0);
+        assert_eq!(<DeadlineOf<Test>>::get(&DID_ALICE, &ad), None);
+
+        assert_eq!(
+            <Metadata<Test>>::get(&ad).unwrap().remain,
+            meta.remain + slot.remain
+        );
+
+        assert!(System::events().iter().any(|r| r.event
+            == Event::Ad(crate::Event::SlotDropped {
+                ad,
+                kol: DID_ALICE,
+                tokens: slot.tokens,
+            })));
+
+        assert_eq!(Ad::unsold_of(&ad, 0), slot.tokens);
+
+        // 4. tokens left unsold are returned with the remain
+
+        assert_ok!(Ad::terminate(Origin::signed(BOB), ad));
+
+        assert_eq!(Ad::unsold_of(&ad, 0), 0);
+
+        assert_eq!(Balances::free_balance(&meta.pot), 0);
+        assert_eq!(Assets::balance(0, &meta.pot), 0);
+
+        assert!(System::events().iter().any(|r| r.event
+            == Event::Ad(crate::Event::UnsoldRefunded {
+                ad,
+                creator: DID_BOB,
+                nft: 0,
+                tokens: slot.tokens - 1,
+            })));
+    });
+}
+
+#[test]
+fn should_terminate_with_slot_failed_to_drawback() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![],
+            creative(),
+            RewardSplit::from(1),
+            43200 * 2,
+            None,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        // 2. tokens of the slot can no longer be sold
+
+        let slot = Ad::find_slot(&DID_ALICE, &ad).unwrap();
+        let meta = <Metadata<Test>>::get(&ad).unwrap();
+
+        assert_ok!(<Assets as Mutate<_>>::burn_from(0, &meta.pot, 1));
+
+        // 3. terminating drops the slot instead of failing
+
+        assert_ok!(Ad::terminate(Origin::signed(BOB), ad));
+
+        assert_eq!(<SlotOf<Test>>::get(&DID_ALICE).len(), 0);
+        assert_eq!(<SlotsOf<Test>>::get(&ad), None);
+        assert_eq!(
+            <Metadata<Test>>::get(&ad).unwrap().status,
+            AdStatus::Terminated
+        );
+
+        assert_eq!(Balances::free_balance(&meta.pot), 0);
+        assert_eq!(Assets::balance(0, &meta.pot), 0);
+
+        assert!(System::events().iter().any(|r| r.event
+            == Event::Ad(crate::Event::SlotDropped {
+                ad,
+                kol: DID_ALICE,
+                tokens: slot.tokens,
+            })));
+    });
+}
+
+#[test]
+fn should_refund_slot_closed_after_end() {
+    new_test_ext().execute_with(|| {
+        MaxExpiredPerBlock::set(&1);
+
+        // 1. prepare
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![],
+            creative(),
+            RewardSplit::from(1),
+            100,
+            None,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+
+        // deadline of the slot is queued after end time of the ad
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        assert_eq!(<DeadlineOf<Test>>::get(&DID_ALICE, &ad), Some(101));
+
+        // 2. the ad ends with its slot still open
+
+        System::set_block_number(100);
+
+        Ad::on_initialize(System::block_number());
+
+        let meta = <Metadata<Test>>::get(&ad).unwrap();
+        assert_eq!(meta.status, AdStatus::Ended);
+        assert_eq!(meta.remain, 0);
+        assert_eq!(<EndtimeOf<Test>>::get(&ad), None);
+        assert_eq!(<ExpiryCount<Test>>::get(101), 1);
+
+        assert_ne!(Ad::find_slot(&DID_ALICE, &ad), None);
+
+        // 3. the slot is refunded when closed
+
+        System::set_block_number(101);
+
+        Ad::on_initialize(System::block_number());
+
+        assert_eq!(Ad::find_slot(&DID_ALICE, &ad), None);
+
+        let meta = <Metadata<Test>>::get(&ad).unwrap();
+        assert_eq!(meta.remain, 0);
+        assert_eq!(Balances::free_balance(&meta.pot), 0);
+
+        assert_eq!(
+            Balances::free_balance(&BOB),
+            3_000_000 - 2_000_100 - 500 + 497
+        );
+    });
+}
+
+#[test]
+fn should_terminate() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        // create ad
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![],
+            creative(),
+            RewardSplit::from(1),
+            43200 * 2,
+            None,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+        let meta = <Metadata<Test>>::get(&ad).unwrap();
+
+        // bid
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        // 2. terminate
+
+        assert_noop!(
+            Ad::terminate(Origin::signed(CHARLIE), ad),
+            Error::<Test>::NotOwned
+        );
+
+        assert_ok!(Ad::terminate(Origin::signed(BOB), ad));
+
+        // ensure slot, metadata, remain
+
+        assert_eq!(<SlotOf<Test>>::get(&DID_ALICE).len(), 0);
+        assert_eq!(<DeadlineOf<Test>>::get(&DID_ALICE, &ad), None);
+        assert_eq!(<EndtimeOf<Test>>::get(&ad), None);
+        assert_eq!(<SlotsOf<Test>>::get(&ad), None);
+        assert_eq!(<AdsOf<Test>>::get(&DID_BOB).unwrap().len(), 0);
+
+        let meta = <Metadata<Test>>::get(&ad).unwrap();
+        assert_eq!(meta.status, AdStatus::Terminated);
+        assert_eq!(meta.remain, 0);
+
+        assert_noop!(
+            Ad::terminate(Origin::signed(BOB), ad),
+            Error::<Test>::AlreadyTerminated
+        );
+
+        assert_eq!(Balances::free_balance(&meta.pot), 0);
+        assert_eq!(Assets::balance(0, &meta.pot), 0);
+
+        assert_eq!(
+            Balances::free_balance(&BOB),
+            3_000_000 - 2_000_100 - 500 + 497
+        );
+    });
+}
+
+#[test]
+fn should_bid_with_token_budget() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+
+        assert_ok!(Tag::create(Origin::signed(ALICE), b"Test".to_vec()));
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        System::set_block_number(5);
+
+        assert_ok!(Nft::claim(Origin::signed(BOB), DID_ALICE));
+
+        let tokens = Assets::balance(0, &BOB);
+
+        // create ad funded by tokens of the KOL
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            1000,
+            vec![b"Test".to_vec()],
+            creative(),
+            RewardSplit::from(1),
+            43200,
+            None,
+            Some(0)
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+        let meta = <Metadata<Test>>::get(&ad).unwrap();
+
+        assert_eq!(meta.asset, Some(0));
+        assert_eq!(Assets::balance(0, &BOB), tokens - 1000);
+        assert_eq!(Assets::balance(0, &meta.pot), 1000);
+
+        // 2. bid without swapping
+
+        let balance = Balances::free_balance(&meta.pot);
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        let slot = Ad::find_slot(&DID_ALICE, &ad).unwrap();
+        assert_eq!(slot.tokens, 400);
+        assert_eq!(slot.remain, 0);
+
+        assert_eq!(<Metadata<Test>>::get(&ad).unwrap().remain, 600);
+        assert_eq!(Balances::free_balance(&meta.pot), balance);
+
+        // 3. pay from tokens
+
+        assert_ok!(Ad::pay(
+            Origin::signed(BOB),
+            ad,
+            DID_ALICE,
+            DID_CHARLIE,
+            vec![(b"Test".to_vec(), 5)],
+            None,
+            receipt(ad, DID_ALICE, DID_CHARLIE, None)
+        ));
+
+        let paid = Assets::balance(0, &CHARLIE);
+        assert!(paid > 0);
+
+        let slot = Ad::find_slot(&DID_ALICE, &ad).unwrap();
+        assert_eq!(slot.tokens, 400 - paid);
+
+        // 4. terminate refunds tokens
+
+        assert_ok!(Ad::terminate(Origin::signed(BOB), ad));
+
+        assert_eq!(Assets::balance(0, &meta.pot), 0);
+        assert_eq!(Assets::balance(0, &BOB), tokens - paid);
+    });
+}
+
+#[test]
+fn should_rank_slots_by_value() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        System::set_block_number(5);
+
+        assert_ok!(Nft::claim(Origin::signed(BOB), DID_ALICE));
+
+        assert_ok!(Ad::set_capacity(Origin::signed(ALICE), 2));
+
+        // ad of BOB funded by tokens of the KOL, ads of CHARLIE by currency
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            1000,
+            vec![],
+            creative(),
+            RewardSplit::from(1),
+            43200,
+            None,
+            Some(0)
+        ));
+
+        for _ in 0..2 {
+            assert_ok!(Ad::create(
+                Origin::signed(CHARLIE),
+                1000,
+                vec![],
+                creative(),
+                RewardSplit::from(1),
+                43200,
+                None,
+                None
+            ));
+        }
+
+        let ad1 = <AdsOf<Test>>::get(&DID_BOB).unwrap()[0];
+        let ads = <AdsOf<Test>>::get(&DID_CHARLIE).unwrap();
+        let (ad2, ad3) = (ads[0], ads[1]);
+
+        // 2. a budget of fewer tokens is worth more in currency
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad1, DID_ALICE, 100));
+        assert_ok!(Ad::bid(Origin::signed(CHARLIE), ad2, DID_ALICE, 150));
+
+        let slots = <SlotOf<Test>>::get(&DID_ALICE);
+        assert_eq!(slots[0].ad, ad1);
+        assert_eq!(slots[1].ad, ad2);
+
+        // 3. the lowest slot by value is evicted
+
+        assert_ok!(Ad::bid(Origin::signed(CHARLIE), ad3, DID_ALICE, 200));
+
+        assert_ne!(Ad::find_slot(&DID_ALICE, &ad1), None);
+        assert_eq!(Ad::find_slot(&DID_ALICE, &ad2), None);
+        assert_ne!(Ad::find_slot(&DID_ALICE, &ad3), None);
+    });
+}
+
+#[test]
+fn should_pause_and_resume() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+
+        assert_ok!(Tag::create(Origin::signed(ALICE), b"Test".to_vec()));
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        // create ad
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![b"Test".to_vec()],
+            creative(),
+            RewardSplit::from(1),
+            43200 * 2,
+            None,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        let deadline = <DeadlineOf<Test>>::get(&DID_ALICE, &ad).unwrap();
+
+        let pay = || {
+            Ad::pay(
+                Origin::signed(BOB),
+                ad,
+                DID_ALICE,
+                DID_CHARLIE,
+                vec![(b"Test".to_vec(), 5)],
+                None,
+                receipt(ad, DID_ALICE, DID_CHARLIE, None),
+            )
+        };
+
+        // 2. pause
+
+        System::set_block_number(10);
+
+        assert_noop!(
+            Ad::pause(Origin::signed(CHARLIE), ad),
+            Error::<Test>::NotOwned
+        );
+        assert_noop!(
+            Ad::resume(Origin::signed(BOB), ad),
+            Error::<Test>::NotPaused
+        );
+
+        assert_ok!(Ad::pause(Origin::signed(BOB), ad));
+
+        assert_eq!(<Metadata<Test>>::get(&ad).unwrap().status, AdStatus::Paused);
+        assert_ne!(Ad::find_slot(&DID_ALICE, &ad), None);
+
+        assert_noop!(Ad::pause(Origin::signed(BOB), ad), Error::<Test>::Paused);
+        assert_noop!(pay(), Error::<Test>::Paused);
+        assert_noop!(
+            Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 50),
+            Error::<Test>::Paused
+        );
+
+        // 3. resume, slot is extended by paused time
+
+        System::set_block_number(110);
+
+        assert_ok!(Ad::resume(Origin::signed(BOB), ad));
+
+        assert_eq!(<Metadata<Test>>::get(&ad).unwrap().status, AdStatus::Active);
+        assert_eq!(
+            <DeadlineOf<Test>>::get(&DID_ALICE, &ad),
+            Some(deadline + 100)
+        );
+        assert_eq!(<PausedAt<Test>>::get(&ad), None);
+
+        assert_ok!(pay());
+    });
+}
+
+#[test]
+fn should_pay() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+
+        assert_ok!(Tag::create(Origin::signed(ALICE), b"Test".to_vec()));
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        // create ad
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![b"Test".to_vec()],
+            creative(),
+            RewardSplit::from(1),
+            1,
+            None,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+        let meta = <Metadata<Test>>::get(&ad).unwrap();
+
+        // bid
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        // 2. pay
+
+        assert_ok!(Ad::pay(
+            Origin::signed(BOB),
+            ad,
+            DID_ALICE,
+            DID_CHARLIE,
+            vec![(b"Test".to_vec(), 5)],
+            None,
+            receipt(ad, DID_ALICE, DID_CHARLIE, None)
+        ));
+
+        let slot = Ad::find_slot(&DID_ALICE, &ad).unwrap();
+        assert_eq!(Assets::balance(0, &meta.pot), slot.tokens);
+        assert_eq!(slot.remain, 400 - 40);
+        assert_eq!(slot.tokens, 19 - 5);
+
+        assert_eq!(Assets::balance(0, &CHARLIE), 5);
+
+        assert_eq!(Tag::get_score(&DID_CHARLIE, b"Test".to_vec()), 5);
+
+        assert_noop!(
+            Ad::pay(
+                Origin::signed(BOB),
+                ad,
+                DID_ALICE,
+                DID_CHARLIE,
+                vec![(b"Test".to_vec(), 5)],
+                None,
+                receipt(ad, DID_ALICE, DID_CHARLIE, None)
+            ),
+            Error::<Test>::Paid
+        );
+    });
+}
+
+#[test]
+fn should_record_stats() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+
+        assert_ok!(Tag::create(Origin::signed(ALICE), b"Test".to_vec()));
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![b"Test".to_vec()],
+            creative(),
+            RewardSplit::from(1),
+            1,
+            None,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        assert_eq!(Ad::get_stats(ad), Ok(Default::default()));
+
+        // 2. pay
+
+        assert_ok!(Ad::pay(
+            Origin::signed(BOB),
+            ad,
+            DID_ALICE,
+            DID_CHARLIE,
+            vec![(b"Test".to_vec(), 5)],
+            None,
+            receipt(ad, DID_ALICE, DID_CHARLIE, None)
+        ));
+
+        let stats = Stats {
+            paid: 5,
+            visitors: 1,
+            awarded: 0,
+            tokens: 19,
+            spent: 40,
+        };
+
+        assert_eq!(Ad::get_stats(ad), Ok(stats.clone()));
+        assert_eq!(Ad::get_slot_stats(ad, DID_ALICE), Ok(stats));
+        assert_eq!(Ad::get_slot_stats(ad, DID_BOB), Ok(Default::default()));
+
+        assert_noop!(Ad::get_stats(Default::default()), Error::<Test>::NotExists);
+    });
+}
+
+#[test]
+fn should_pay_registered_referers() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        // 1. prepare
+
+        assert_ok!(Tag::create(Origin::signed(ALICE), b"Test".to_vec()));
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        // register visitor invited by DAVE, who was invited by BOB
+
+        let dave = sr25519::Public([4; 32]);
+        let eve = sr25519::Public([5; 32]);
+
+        Balances::make_free_balance_be(&dave, 100);
+        Balances::make_free_balance_be(&eve, 100);
+
+        assert_ok!(Did::<Test>::register(Origin::signed(dave), Some(DID_BOB)));
+        let did_dave = Did::<Test>::did_of(&dave).unwrap();
+
+        assert_ok!(Did::<Test>::register(Origin::signed(eve), Some(did_dave)));
+        let did_eve = Did::<Test>::did_of(&eve).unwrap();
+
+        // create ad
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![b"Test".to_vec()],
+            creative(),
+            RewardSplit {
+                source: RefererSource::Registered,
+                rates: vec![20, 40].try_into().unwrap(),
+            },
+            43200,
+            None,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        // 2. pay, referer given by caller is ignored
+
+        assert_eq!(
+            Ad::dryly_pay(ad, DID_ALICE, did_eve, Some(DID_CHARLIE)),
+            Ok((5, 5, 3, false))
+        );
+
+        assert_ok!(Ad::pay(
+            Origin::signed(BOB),
+            ad,
+            DID_ALICE,
+            did_eve,
+            vec![(b"Test".to_vec(), 5)],
+            Some(DID_CHARLIE),
+            receipt(ad, DID_ALICE, did_eve, Some(DID_CHARLIE))
+        ));
+
+        assert_eq!(Assets::balance(0, &eve), 5 - 1 - 2);
+        assert_eq!(Assets::balance(0, &dave), 1);
+        assert_eq!(Assets::balance(0, &BOB), 2);
+        assert_eq!(Assets::balance(0, &CHARLIE), 0);
+
+        let events = System::events();
+        assert!(events.iter().any(
+            |r| r.event == Event::Ad(crate::Event::Paid(ad, 0, did_eve, 2, Some(did_dave), 3))
+        ));
+        assert!(events
+            .iter()
+            .any(|r| r.event == Event::Ad(crate::Event::Awarded(ad, DID_BOB, 2))));
+    });
+}
+
+#[test]
+fn should_pay_targeted() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+
+        assert_ok!(Tag::create(Origin::signed(ALICE), b"Test".to_vec()));
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        // create ad
+
+        assert_noop!(
+            Ad::create(
+                Origin::signed(BOB),
+                500,
+                vec![b"Test".to_vec()],
+                creative(),
+                RewardSplit::from(1),
+                43200,
+                Some(Targeting {
+                    scores: vec![(b"Unknown".to_vec().try_into().unwrap(), 3)]
+                        .try_into()
+                        .unwrap(),
+                    links: Default::default(),
+                    min_age: 0,
+                }),
+                None
+            ),
+            Error::<Test>::TagNotExists
+        );
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![b"Test".to_vec()],
+            creative(),
+            RewardSplit::from(1),
+            43200,
+            Some(Targeting {
+                scores: vec![(b"Test".to_vec().try_into().unwrap(), 3)]
+                    .try_into()
+                    .unwrap(),
+                links: vec![1].try_into().unwrap(),
+                min_age: 10,
+            }),
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        // 2. visitor does not match
+
+        let pay = || {
+            Ad::pay(
+                Origin::signed(BOB),
+                ad,
+                DID_ALICE,
+                DID_CHARLIE,
+                vec![(b"Test".to_vec(), 5)],
+                None,
+                receipt(ad, DID_ALICE, DID_CHARLIE, None),
+            )
+        };
+
+        assert_noop!(pay(), Error::<Test>::TargetMismatch);
+
+        assert_ok!(Tag::influence(&DID_CHARLIE, b"Test".to_vec(), 3));
+
+        assert_noop!(pay(), Error::<Test>::TargetMismatch);
+
+        LinkedAccounts::set(&vec![(DID_CHARLIE, 1)]);
+
+        assert_noop!(pay(), Error::<Test>::TargetMismatch);
+
+        // 3. visitor matches once old enough
+
+        System::set_block_number(10);
+
+        assert_ok!(pay());
+
+        // (5 + 3 * 10) / (1 * 10 + 1)
+        assert_eq!(Assets::balance(0, &CHARLIE), 3);
+
+        // 4. update targeting
+
+        assert_noop!(
+            Ad::update_targeting(Origin::signed(CHARLIE), ad, None),
+            Error::<Test>::NotOwned
+        );
+
+        assert_ok!(Ad::update_targeting(Origin::signed(BOB), ad, None));
+
+        assert_eq!(<Metadata<Test>>::get(&ad).unwrap().targeting, None);
+    });
+}
+
+#[test]
+fn should_verify_receipt() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+
+        assert_ok!(Tag::create(Origin::signed(ALICE), b"Test".to_vec()));
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        // create ad
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![b"Test".to_vec()],
+            creative(),
+            RewardSplit::from(1),
+            43200,
+            None,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        assert_ok!(Ad::update_frequency(
+            Origin::signed(BOB),
+            ad,
+            Frequency::Times(2)
+        ));
+
+        System::set_block_number(1);
+
+        let pay = |receipt| {
+            Ad::pay(
+                Origin::signed(BOB),
+                ad,
+                DID_ALICE,
+                DID_CHARLIE,
+                vec![(b"Test".to_vec(), 5)],
+                None,
+                receipt,
+            )
+        };
+
+        // 2. signed by another account
+
+        let scores = vec![(b"Test".to_vec(), 5)];
+        let payload = |kol, visitor, referer, scores: &[(Vec<u8>, i8)], height| {
+            Ad::receipt_payload(&ad, &kol, &visitor, referer, scores, 1, height)
+        };
+
+        let mut forged = receipt(ad, DID_ALICE, DID_CHARLIE, None);
+        forged.signature =
+            MockSignature::Mock(BOB, payload(DID_ALICE, DID_CHARLIE, None, &scores, 1));
+        assert_noop!(pay(forged), Error::<Test>::InvalidReceipt);
+
+        // 3. signed for another kol
+
+        let mut forged = receipt(ad, DID_ALICE, DID_CHARLIE, None);
+        forged.signature =
+            MockSignature::Mock(CHARLIE, payload(DID_BOB, DID_CHARLIE, None, &scores, 1));
+        assert_noop!(pay(forged), Error::<Test>::InvalidReceipt);
+
+        // 4. viewed in the future
+
+        let mut forged = receipt(ad, DID_ALICE, DID_CHARLIE, None);
+        forged.height = 2;
+        forged.signature =
+            MockSignature::Mock(CHARLIE, payload(DID_ALICE, DID_CHARLIE, None, &scores, 2));
+        assert_noop!(pay(forged), Error::<Test>::InvalidReceipt);
+
+        // 5. signed for another visitor
+
+        let mut forged = receipt(ad, DID_ALICE, DID_CHARLIE, None);
+        forged.signature =
+            MockSignature::Mock(CHARLIE, payload(DID_ALICE, DID_BOB, None, &scores, 1));
+        assert_noop!(pay(forged), Error::<Test>::InvalidReceipt);
+
+        // 6. signed with another referer
+
+        let mut forged = receipt(ad, DID_ALICE, DID_CHARLIE, None);
+        forged.signature = MockSignature::Mock(
+            CHARLIE,
+            payload(DID_ALICE, DID_CHARLIE, Some(DID_BOB), &scores, 1),
+        );
+        assert_noop!(pay(forged), Error::<Test>::InvalidReceipt);
+
+        // 7. signed with other scores
+
+        let mut forged = receipt(ad, DID_ALICE, DID_CHARLIE, None);
+        forged.signature = MockSignature::Mock(
+            CHARLIE,
+            payload(DID_ALICE, DID_CHARLIE, None, &[(b"Test".to_vec(), 4)], 1),
+        );
+        assert_noop!(pay(forged), Error::<Test>::InvalidReceipt);
+
+        // 8. replayed
+
+        let valid = receipt(ad, DID_ALICE, DID_CHARLIE, None);
+
+        assert_ok!(pay(valid.clone()));
+
+        assert_eq!(<NonceOf<Test>>::get(&ad, &DID_CHARLIE), 1);
+
+        assert_noop!(pay(valid), Error::<Test>::NonceUsed);
+
+        assert_ok!(pay(receipt(ad, DID_ALICE, DID_CHARLIE, None)));
+
+        assert_eq!(<NonceOf<Test>>::get(&ad, &DID_CHARLIE), 2);
+    });
+}
+
+#[test]
+fn should_pay_with_frequency() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+
+        assert_ok!(Tag::create(Origin::signed(ALICE), b"Test".to_vec()));
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        // create ad
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            1000,
+            vec![b"Test".to_vec()],
+            creative(),
+            RewardSplit::from(1),
+            43200,
+            None,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        let pay = || {
+            Ad::pay(
+                Origin::signed(BOB),
+                ad,
+                DID_ALICE,
+                DID_CHARLIE,
+                vec![(b"Test".to_vec(), 5)],
+                None,
+                receipt(ad, DID_ALICE, DID_CHARLIE, None),
+            )
+        };
+
+        // 2. once by default
+
+        assert_ok!(pay());
+
+        assert_noop!(pay(), Error::<Test>::Paid);
+
+        // 3. limited times
+
+        assert_noop!(
+            Ad::update_frequency(Origin::signed(CHARLIE), ad, Frequency::Times(2)),
+            Error::<Test>::NotOwned
+        );
+
+        assert_ok!(Ad::update_frequency(
+            Origin::signed(BOB),
+            ad,
+            Frequency::Times(2)
+        ));
+
+        assert_ok!(pay());
+
+        assert_noop!(pay(), Error::<Test>::Paid);
+
+        let payout = <Payout<Test>>::get(&ad, DID_CHARLIE).unwrap();
+        assert_eq!(payout.count, 2);
+
+        // 4. every 10 blocks
+
+        assert_ok!(Ad::update_frequency(
+            Origin::signed(BOB),
+            ad,
+            Frequency::Every(10)
+        ));
+
+        assert_noop!(pay(), Error::<Test>::Paid);
+
+        let height = System::block_number();
+
+        System::set_block_number(height + 10);
+
+        assert_ok!(pay());
+
+        assert_noop!(pay(), Error::<Test>::Paid);
+
+        // 5. once per slot
+
+        assert_ok!(Ad::update_frequency(
+            Origin::signed(BOB),
+            ad,
+            Frequency::PerSlot
+        ));
+
+        assert_ok!(pay());
+
+        assert_noop!(pay(), Error::<Test>::Paid);
+
+        System::set_block_number(height + 11);
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        assert_ok!(pay());
+
+        assert_noop!(pay(), Error::<Test>::Paid);
+
+        assert_eq!(<Payout<Test>>::get(&ad, DID_CHARLIE).unwrap().count, 5);
+    });
+}
+
+#[test]
+fn should_dryly_pay() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+
+        assert_ok!(Tag::create(Origin::signed(ALICE), b"Test".to_vec()));
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        // create ad
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![b"Test".to_vec()],
+            creative(),
+            RewardSplit::from(20),
+            1,
+            None,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+
+        assert_noop!(
+            Ad::dryly_pay(ad, DID_ALICE, DID_CHARLIE, None),
+            Error::<Test>::NotExists
+        );
+
+        // bid
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        // 2. dry-run
+
+        assert_eq!(
+            Ad::dryly_pay(ad, DID_ALICE, DID_CHARLIE, Some(DID_BOB)),
+            Ok((5, 5, 1, false))
+        );
+
+        // 3. pay and compare
+
+        assert_ok!(Ad::pay(
+            Origin::signed(BOB),
+            ad,
+            DID_ALICE,
+            DID_CHARLIE,
+            vec![(b"Test".to_vec(), 5)],
+            Some(DID_BOB),
+            receipt(ad, DID_ALICE, DID_CHARLIE, Some(DID_BOB))
+        ));
+
+        assert_eq!(Assets::balance(0, &CHARLIE), 5 - 1);
+        assert_eq!(Assets::balance(0, &BOB), 1);
+
+        assert_noop!(
+            Ad::dryly_pay(ad, DID_ALICE, DID_CHARLIE, None),
+            Error::<Test>::Paid
+        );
+    });
+}
+
+#[test]
+fn should_pay_batch() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        // 1. prepare
+
+        assert_ok!(Tag::create(Origin::signed(ALICE), b"Test".to_vec()));
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        // create ad
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![b"Test".to_vec()],
+            creative(),
+            RewardSplit::from(1),
+            43200,
+            None,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+
+        // bid
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        assert_ok!(Ad::pay(
+            Origin::signed(BOB),
+            ad,
+            DID_ALICE,
+            DID_CHARLIE,
+            vec![(b"Test".to_vec(), 5)],
+            None,
+            receipt(ad, DID_ALICE, DID_CHARLIE, None)
+        ));
+
+        // 2. pay batch
+
+        let unknown = H160([0x11; 20]);
+
+        let viewer_dids = make_dids(4u8);
+        let invalid = viewer_dids[3];
+        let viewer_dids = &viewer_dids[..3];
+        let mut items: Vec<_> = viewer_dids
+            .iter()
+            .map(|did| PayItem {
+                visitor: *did,
+                scores: vec![(b"Test".to_vec(), 5)],
+                referer: None,
+                receipt: receipt(ad, DID_ALICE, *did, None),
+            })
+            .collect();
+        items.push(PayItem {
+            visitor: DID_CHARLIE,
+            scores: vec![(b"Test".to_vec(), 5)],
+            referer: None,
+            receipt: receipt(ad, DID_ALICE, DID_CHARLIE, None),
+        });
+        items.push(PayItem {
+            visitor: unknown,
+            scores: vec![(b"Test".to_vec(), 5)],
+            referer: None,
+            receipt: receipt(ad, DID_ALICE, unknown, None),
+        });
+        items.push(PayItem {
+            visitor: invalid,
+            scores: vec![(b"Test".to_vec(), 6)],
+            referer: None,
+            receipt: receipt(ad, DID_ALICE, invalid, None),
+        });
+
+        assert_noop!(
+            Ad::pay_batch(Origin::signed(BOB), ad, DID_ALICE, vec![]),
+            Error::<Test>::EmptyVisitors
+        );
+
+        assert_noop!(
+            Ad::pay_batch(
+                Origin::signed(BOB),
+                ad,
+                DID_ALICE,
+                [items.clone(), items.clone()].concat()
+            ),
+            Error::<Test>::TooManyVisitors
+        );
+
+        assert_ok!(Ad::pay_batch(Origin::signed(BOB), ad, DID_ALICE, items));
+
+        // ensure: payouts, events, slot
+
+        for i in 0..3u8 {
+            let account = sr25519::Public([i + 20; 32]);
+            assert_eq!(Assets::balance(0, &account), 5);
+        }
+
+        for did in viewer_dids {
+            let payout = <Payout<Test>>::get(&ad, did).unwrap();
+            assert_eq!((payout.count, payout.last), (1, 1));
+        }
+        assert_eq!(<Payout<Test>>::get(&ad, &unknown), None);
+        assert_eq!(<Payout<Test>>::get(&ad, &invalid), None);
+        assert_eq!(Assets::balance(0, &sr25519::Public([23; 32])), 0);
+
+        let events = System::events();
+        assert!(events.iter().any(|r| r.event
+            == Event::Ad(crate::Event::PaySkipped(
+                ad,
+                DID_CHARLIE,
+                PaySkipReason::Paid
+            ))));
+        assert!(events.iter().any(|r| r.event
+            == Event::Ad(crate::Event::PaySkipped(
+                ad,
+                unknown,
+                PaySkipReason::DidNotExists
+            ))));
+        assert!(events.iter().any(|r| r.event
+            == Event::Ad(crate::Event::PaySkipped(
+                ad,
+                invalid,
+                PaySkipReason::InvalidScores
+            ))));
+
+        let meta = <Metadata<Test>>::get(&ad).unwrap();
+        let slot = Ad::find_slot(&DID_ALICE, &ad).unwrap();
+        assert_eq!(Assets::balance(0, &meta.pot), slot.tokens);
+        assert_eq!(slot.remain, 400 - 40 * 2);
+    });
+}
+
+#[test]
+fn should_balance_pot_from_events() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        // 1. prepare
+
+        assert_ok!(Tag::create(Origin::signed(ALICE), b"Test".to_vec()));
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        // 2. create, bid, pay and add budget
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![b"Test".to_vec()],
+            creative(),
+            RewardSplit::from(20),
+            10,
+            None,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+        let meta = <Metadata<Test>>::get(&ad).unwrap();
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        assert_ok!(Ad::pay(
+            Origin::signed(BOB),
+            ad,
+            DID_ALICE,
+            DID_CHARLIE,
+            vec![(b"Test".to_vec(), 5)],
+            Some(DID_BOB),
+            receipt(ad, DID_ALICE, DID_CHARLIE, Some(DID_BOB))
+        ));
+
+        assert_ok!(Ad::add_budget(Origin::signed(BOB), ad, 50));
+
+        // tokens of the slot run out, triggering another swap
+
+        let items = make_dids(3u8)
+            .into_iter()
+            .map(|did| PayItem {
+                visitor: did,
+                scores: vec![(b"Test".to_vec(), 5)],
+                referer: None,
+                receipt: receipt(ad, DID_ALICE, did, None),
+            })
+            .collect();
+        assert_ok!(Ad::pay_batch(Origin::signed(BOB), ad, DID_ALICE, items));
+
+        assert_eq!(
+            pot_ledger(ad),
+            (
+                Balances::free_balance(&meta.pot) as i128,
+                Assets::balance(0, &meta.pot) as i128
+            )
+        );
+
+        // 3. close slot and refund on end time
+
+        System::set_block_number(10);
+        Ad::on_initialize(10);
+
+        assert_eq!(<Metadata<Test>>::get(&ad).unwrap().remain, 0);
+
+        assert_eq!(
+            pot_ledger(ad),
+            (
+                Balances::free_balance(&meta.pot) as i128,
+                Assets::balance(0, &meta.pot) as i128
+            )
+        );
+        assert_eq!(pot_ledger(ad), (0, 0));
+    });
+}
+
+#[test]
+fn should_share_revenue() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        KolShare::set(&Percent::from_percent(10));
+
+        // 1. prepare
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![],
+            creative(),
+            RewardSplit::from(1),
+            43200,
+            None,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+        let meta = <Metadata<Test>>::get(&ad).unwrap();
+
+        let balance = Balances::free_balance(&ALICE);
+
+        // 2. bid
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        assert_eq!(Balances::free_balance(&ALICE), balance + 40);
+
+        let slot = Ad::find_slot(&DID_ALICE, &ad).unwrap();
+        assert_eq!(slot.budget, 400);
+        assert_eq!(slot.remain, 400 - 40 - 40);
+        assert_eq!(Assets::balance(0, &meta.pot), slot.tokens);
+
+        let events = System::events();
+        assert!(events.iter().any(|r| r.event
+            == Event::Ad(crate::Event::KolShared {
+                ad,
+                kol: DID_ALICE,
+                amount: 40
+            })));
+
+        assert_eq!(
+            pot_ledger(ad),
+            (
+                Balances::free_balance(&meta.pot) as i128,
+                Assets::balance(0, &meta.pot) as i128
+            )
+        );
+    });
+}
+
+#[test]
+fn should_share_to_holders() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        HolderShare::set(&Percent::from_percent(10));
+
+        // 1. prepare
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![],
+            creative(),
+            RewardSplit::from(1),
+            43200,
+            None,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+        let meta = <Metadata<Test>>::get(&ad).unwrap();
+
+        // 2. holders stake fragments, 3:1
+
+        assert_ok!(Assets::mint_into(0, &BOB, 100));
+        assert_ok!(Assets::mint_into(0, &CHARLIE, 300));
+
+        let bob = Assets::balance(0, &BOB);
+
+        assert_ok!(Ad::stake_fragments(Origin::signed(BOB), 0, 100));
+        assert_ok!(Ad::stake_fragments(Origin::signed(CHARLIE), 0, 300));
+
+        assert_eq!(Ad::holder_pool_of(0).staked, 400);
+        assert_eq!(Assets::balance(0, &CHARLIE), 0);
+
+        assert_noop!(
+            Ad::unstake_fragments(Origin::signed(CHARLIE), 0, 301),
+            Error::<Test>::InsufficientFragments
+        );
+
+        // 3. bid, buying fragments for holders
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        let slot = Ad::find_slot(&DID_ALICE, &ad).unwrap();
+        assert_eq!(slot.remain, 400 - 40 - 40);
+        assert_eq!(Assets::balance(0, &meta.pot), slot.tokens);
+
+        let tokens = System::events()
+            .into_iter()
+            .find_map(|r| match r.event {
+                Event::Ad(crate::Event::HolderShared {
+                    ad: id,
+                    kol: DID_ALICE,
+                    amount: 40,
+                    tokens,
+                }) if id == ad => Some(tokens),
+                _ => None,
+            })
+            .unwrap();
+        assert!(tokens > 0);
+
+        assert_eq!(
+            pot_ledger(ad),
+            (
+                Balances::free_balance(&meta.pot) as i128,
+                Assets::balance(0, &meta.pot) as i128
+            )
+        );
+
+        // 4. holders claim pro rata
+
+        assert_ok!(Ad::claim_holder_share(Origin::signed(CHARLIE), 0));
+        assert_eq!(Assets::balance(0, &CHARLIE), tokens * 3 / 4);
+
+        assert_ok!(Ad::unstake_fragments(Origin::signed(BOB), 0, 100));
+        assert_ok!(Ad::claim_holder_share(Origin::signed(BOB), 0));
+        assert_eq!(Assets::balance(0, &BOB), bob + tokens / 4);
+
+        assert_eq!(Ad::holder_pool_of(0).staked, 300);
+        assert_eq!(Ad::holding_of(0, &BOB).staked, 0);
+
+        // claimed once only
+
+        assert_ok!(Ad::claim_holder_share(Origin::signed(CHARLIE), 0));
+        assert_eq!(Assets::balance(0, &CHARLIE), tokens * 3 / 4);
+    });
+}
+
+#[test]
+fn should_auto_swap_when_swapped_token_used_up() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+
+        assert_ok!(Tag::create(Origin::signed(ALICE), b"Test".to_vec()));
+
+        assert_ok!(Nft::back(Origin::signed(BOB), DID_ALICE, 2_000_100u128));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        // create ad
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            500,
+            vec![b"Test".to_vec()],
+            creative(),
+            RewardSplit::from(1),
+            1,
+            None,
+            None
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+
+        // bid
+
+        assert_ok!(Ad::bid(Origin::signed(BOB), ad, DID_ALICE, 400));
+
+        // 2. pay to 9 users, 5 tokens each
+        let viewer_dids = make_dids(9u8);
+        for viewer_did in &viewer_dids {
+            assert_ok!(Ad::pay(
+                Origin::signed(BOB),
+                ad,
+                DID_ALICE,
+                *viewer_did,
                 vec![(b"Test".to_vec(), 5)],
-                None
+                None,
+                receipt(ad, DID_ALICE, *viewer_did, None)
             ));
         }
 
-        let slot = <SlotOf<Test>>::get(&DID_ALICE).unwrap();
+        let slot = Ad::find_slot(&DID_ALICE, &ad).unwrap();
         assert_eq!(slot.remain, 400 - 40 * 3);
     });
 }
 
+fn creative() -> CreativeOf<Test> {
+    AdCreative {
+        landing: b"https://parami.io".to_vec().try_into().unwrap(),
+        cid: b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"
+            .to_vec()
+            .try_into()
+            .unwrap(),
+        content_hash: BlakeTwo256::hash(b"creative"),
+        mime: b"image/png".to_vec().try_into().unwrap(),
+    }
+}
+
+/// Receipt of a visitor viewing with the scores paid throughout tests
+fn receipt(
+    ad: H256,
+    kol: H160,
+    visitor: H160,
+    referer: Option<H160>,
+) -> Receipt<u64, MockSignature> {
+    let account = Did::<Test>::meta(&visitor)
+        .map(|meta| meta.account)
+        .unwrap_or_default();
+
+    let nonce = Ad::nonce_of(&ad, &visitor) + 1;
+    let height = System::block_number();
+
+    let scores = vec![(b"Test".to_vec(), 5)];
+    let payload = Ad::receipt_payload(&ad, &kol, &visitor, referer, &scores, nonce, height);
+
+    Receipt {
+        nonce,
+        height,
+        signature: MockSignature::Mock(account, payload),
+    }
+}
+
+/// Balances of currency and tokens in pot of an advertisement,
+/// reconstructed from events only
+fn pot_ledger(ad: H256) -> (i128, i128) {
+    let mut currency = 0i128;
+    let mut tokens = 0i128;
+
+    for record in System::events() {
+        match record.event {
+            Event::Ad(crate::Event::Deposited(id, _, value)) if id == ad => {
+                currency += value as i128;
+            }
+            Event::Ad(crate::Event::Swapped {
+                ad: id,
+                amount,
+                tokens: bought,
+                ..
+            }) if id == ad => {
+                currency -= amount as i128;
+                tokens += bought as i128;
+            }
+            Event::Ad(crate::Event::Paid(id, _, _, reward, _, award)) if id == ad => {
+                tokens -= (reward + award) as i128;
+            }
+            Event::Ad(crate::Event::SlotClosed {
+                ad: id,
+                tokens_sold,
+                currency_returned,
+                ..
+            }) if id == ad => {
+                tokens -= tokens_sold as i128;
+                currency += currency_returned as i128;
+            }
+            Event::Ad(crate::Event::Refunded { ad: id, amount, .. })
+            | Event::Ad(crate::Event::KolShared { ad: id, amount, .. })
+            | Event::Ad(crate::Event::HolderShared { ad: id, amount, .. })
+                if id == ad =>
+            {
+                currency -= amount as i128;
+            }
+            _ => {}
+        }
+    }
+
+    (currency, tokens)
+}
+
 fn make_dids(num: u8) -> Vec<H160> {
     let mut res: Vec<H160> = Vec::new();
     for i in 0..num {
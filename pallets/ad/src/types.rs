@@ -1,13 +1,37 @@
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 
-#[derive(Clone, Decode, Default, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[derive(
+    Clone,
+    Copy,
+    Decode,
+    Encode,
+    Eq,
+    MaxEncodedLen,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    RuntimeDebug,
+    TypeInfo,
+)]
+pub enum Releases {
+    V0,
+    V1,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V0
+    }
+}
+
+#[derive(Clone, Decode, Default, Encode, Eq, MaxEncodedLen, PartialEq, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct Metadata<A, B, D, H, N> {
+pub struct Metadata<A, B, D, H, L, N, R, S, T, V> {
     pub id: H,
     pub creator: D,
     pub pot: A,
@@ -15,12 +39,144 @@ pub struct Metadata<A, B, D, H, N> {
     pub budget: B,
     #[codec(compact)]
     pub remain: B,
-    pub metadata: Vec<u8>,
-    pub reward_rate: u16,
+    pub creative: AdCreative<H, V>,
+    /// number of times creative updated
+    pub revision: u32,
+    pub reward_split: RewardSplit<R>,
     pub created: N,
+    pub targeting: Option<Targeting<L, N, S>>,
+    pub frequency: Frequency<N>,
+    /// asset of budget, native currency if none
+    pub asset: Option<T>,
+    pub status: AdStatus,
 }
 
-#[derive(Clone, Decode, Default, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[derive(Clone, Decode, Default, Encode, Eq, MaxEncodedLen, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AdCreative<H, V> {
+    /// URL of landing page
+    pub landing: V,
+    /// IPFS CID of content, in text form
+    pub cid: V,
+    /// hash of content
+    pub content_hash: H,
+    /// MIME type of content
+    pub mime: V,
+}
+
+#[derive(Clone, Copy, Decode, Encode, Eq, MaxEncodedLen, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum AdStatus {
+    /// Advertisement can be bid and paid
+    Active,
+    /// Advertisement is paused by advertiser, slots are kept
+    Paused,
+    /// End time of advertisement reached
+    Ended,
+    /// Advertisement terminated by advertiser
+    Terminated,
+}
+
+impl Default for AdStatus {
+    fn default() -> Self {
+        AdStatus::Active
+    }
+}
+
+#[derive(Clone, Copy, Decode, Encode, Eq, MaxEncodedLen, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum RefererSource {
+    /// Referer is given by the caller of pay
+    Caller,
+    /// Referer is the inviter recorded on DID registration of visitor
+    Registered,
+}
+
+impl Default for RefererSource {
+    fn default() -> Self {
+        RefererSource::Caller
+    }
+}
+
+#[derive(Clone, Decode, Default, Encode, Eq, MaxEncodedLen, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RewardSplit<R> {
+    pub source: RefererSource,
+    /// percentages of payout awarded to referers, from the direct referer upwards
+    /// through inviters recorded on DID registration
+    pub rates: R,
+}
+
+impl<R: TryFrom<Vec<u16>> + Default> From<u16> for RewardSplit<R> {
+    /// Award a single referer given by the caller
+    fn from(rate: u16) -> Self {
+        RewardSplit {
+            source: RefererSource::Caller,
+            rates: R::try_from(vec![rate]).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Decode, Encode, Eq, MaxEncodedLen, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Frequency<N> {
+    /// Pay a visitor once per advertisement
+    Once,
+    /// Pay a visitor once per given blocks
+    Every(N),
+    /// Pay a visitor at most given times per advertisement
+    Times(u32),
+    /// Pay a visitor once per slot of a KOL
+    PerSlot,
+}
+
+impl<N> Default for Frequency<N> {
+    fn default() -> Self {
+        Frequency::Once
+    }
+}
+
+#[derive(Clone, Decode, Encode, Eq, MaxEncodedLen, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum Migration<D, H> {
+    Deadlines(Option<(D, H)>),
+    Endtimes(Option<H>),
+    Payouts(Option<(H, D)>),
+}
+
+#[derive(Clone, Decode, Default, Encode, Eq, MaxEncodedLen, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Payout<N> {
+    pub count: u32,
+    pub last: N,
+}
+
+#[derive(Clone, Decode, Default, Encode, Eq, MaxEncodedLen, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Stats<B> {
+    /// tokens paid to visitors, referral awards excluded
+    pub paid: B,
+    /// number of payouts to visitors
+    pub visitors: u32,
+    /// tokens awarded to referers
+    pub awarded: B,
+    /// tokens bought with budget for payouts
+    pub tokens: B,
+    /// budget spent on buying tokens for payouts
+    pub spent: B,
+}
+
+#[derive(Clone, Decode, Default, Encode, Eq, MaxEncodedLen, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Targeting<L, N, S> {
+    /// minimum persona scores of tags
+    pub scores: S,
+    /// required linked account types
+    pub links: L,
+    /// minimum age of DID, in blocks
+    pub min_age: N,
+}
+
+#[derive(Clone, Decode, Default, Encode, Eq, MaxEncodedLen, PartialEq, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Slot<B, H, N, T> {
     pub nft: T,
@@ -33,3 +189,88 @@ pub struct Slot<B, H, N, T> {
     pub created: N,
     pub ad: H,
 }
+
+#[derive(Clone, Decode, Default, Encode, Eq, MaxEncodedLen, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SlotConfig<B, D, T> {
+    /// minimum bid, valued in currency
+    #[codec(compact)]
+    pub reserve: B,
+    /// accepted advertisements carry at least one of these tags, any if empty
+    pub tags: T,
+    /// advertisers not allowed to bid
+    pub blocked: D,
+}
+
+#[derive(Clone, Decode, Encode, Eq, MaxEncodedLen, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Commitment<B, D, H> {
+    /// advertiser who committed the bid
+    pub did: D,
+    pub ad: H,
+    /// hash of (advertiser, ad, kol, value, salt)
+    pub hash: H,
+    /// value of the bid, once revealed
+    pub value: Option<B>,
+}
+
+#[derive(Clone, Decode, Default, Encode, Eq, MaxEncodedLen, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct HolderPool<B> {
+    /// fragments staked by holders
+    #[codec(compact)]
+    pub staked: B,
+    /// holder share accumulated per staked fragment, the inner of a `FixedU128`
+    pub acc: u128,
+}
+
+#[derive(Clone, Decode, Default, Encode, Eq, MaxEncodedLen, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Holding<B> {
+    /// fragments staked by the holder
+    #[codec(compact)]
+    pub staked: B,
+    /// accumulated share per staked fragment when last settled
+    pub acc: u128,
+    /// share settled but not claimed yet
+    #[codec(compact)]
+    pub reward: B,
+}
+
+#[derive(Clone, Decode, Default, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PayItem<D, R> {
+    pub visitor: D,
+    pub scores: Vec<(Vec<u8>, i8)>,
+    pub referer: Option<D>,
+    pub receipt: R,
+}
+
+#[derive(Clone, Decode, Default, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Receipt<N, S> {
+    /// nonce of visitor for the advertisement, must be greater than the last used one
+    pub nonce: u64,
+    /// height when the advertisement was viewed
+    pub height: N,
+    /// signature of visitor over
+    /// (genesis hash, ad, kol, visitor, referer, hash of scores, nonce, height)
+    pub signature: S,
+}
+
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PaySkipReason {
+    /// Visitor was already paid
+    Paid,
+    /// DID of visitor or referer does not exist
+    DidNotExists,
+    /// Visitor does not match targeting of the advertisement
+    TargetMismatch,
+    /// View receipt is invalid or replayed
+    InvalidReceipt,
+    /// Scores are empty, out of range or of tags not in the advertisement
+    InvalidScores,
+    /// Scores failed to influence visitor
+    InfluenceFailed,
+}
@@ -15,13 +15,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Autogenerated weights for parami_ad
+//! Weights for parami_ad
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2021-12-20, STEPS: `2`, REPEAT: 1000, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+//! NOT GENERATED: entries added since the benchmark of 2021-12-20 are hand-written placeholders,
+//! estimated from the storage reads and writes of each call.
+//! Regenerate this file with the command below before release.
 
-// Executed Command:
+// Command to regenerate:
 // ./target/release/parami
 // benchmark
 // --chain=dev
@@ -44,11 +44,28 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for parami_ad.
 pub trait WeightInfo {
     fn create(m: u32, n: u32, ) -> Weight;
-    fn update_reward_rate() -> Weight;
+    fn update_reward_split() -> Weight;
     fn update_tags(n: u32, ) -> Weight;
+    fn update_targeting(n: u32, ) -> Weight;
+    fn update_frequency() -> Weight;
     fn add_budget() -> Weight;
     fn bid() -> Weight;
     fn pay(n: u32, ) -> Weight;
+    fn pay_batch(n: u32, m: u32, ) -> Weight;
+    fn terminate(n: u32, ) -> Weight;
+    fn set_capacity() -> Weight;
+    fn commit_bid() -> Weight;
+    fn reveal_bid() -> Weight;
+    fn settle_bid(n: u32, ) -> Weight;
+    fn on_initialize(n: u32, ) -> Weight;
+    fn pause() -> Weight;
+    fn resume() -> Weight;
+    fn set_slot_config(n: u32, ) -> Weight;
+    fn evict() -> Weight;
+    fn update_metadata(m: u32, ) -> Weight;
+    fn stake_fragments() -> Weight;
+    fn unstake_fragments() -> Weight;
+    fn claim_holder_share() -> Weight;
 }
 
 /// Weights for parami_ad using the Substrate node and recommended hardware.
@@ -77,7 +94,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     // Storage: Balances Reserves (r:1 w:0)
     // Storage: Ad DeadlineOf (r:1 w:0)
     // Storage: Ad Metadata (r:1 w:1)
-    fn update_reward_rate() -> Weight {
+    fn update_reward_split() -> Weight {
         (25_000_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
             .saturating_add(T::DbWeight::get().writes(1 as Weight))
@@ -98,6 +115,28 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     }
     // Storage: Did DidOf (r:1 w:0)
     // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:1)
+    // Storage: Tag Metadata (r:1 w:0)
+    fn update_targeting(n: u32, ) -> Weight {
+        (27_000_000 as Weight)
+            // Standard Error: 2_000
+            .saturating_add((3_120_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:1)
+    fn update_frequency() -> Weight {
+        (24_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
     // Storage: Ad DeadlineOf (r:1 w:0)
     // Storage: Ad Metadata (r:1 w:1)
     // Storage: System Account (r:1 w:1)
@@ -134,15 +173,218 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     // Storage: Assets Asset (r:1 w:1)
     // Storage: Assets Account (r:2 w:2)
     // Storage: System Account (r:1 w:1)
+    // Storage: Ad Stats (r:1 w:1)
+    // Storage: Ad SlotStats (r:1 w:1)
     fn pay(n: u32, ) -> Weight {
         (67_758_000 as Weight)
             // Standard Error: 0
             .saturating_add((6_722_000 as Weight).saturating_mul(n as Weight))
-            .saturating_add(T::DbWeight::get().reads(12 as Weight))
+            .saturating_add(T::DbWeight::get().reads(14 as Weight))
             .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
-            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
             .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
     }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad DeadlineOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:0)
+    // Storage: Ad SlotOf (r:1 w:1)
+    // Storage: Tag TagsOf (r:1 w:0)
+    // Storage: Ad Payout (r:1 w:1)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Tag PersonasOf (r:1 w:1)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Ad Stats (r:1 w:1)
+    // Storage: Ad SlotStats (r:1 w:1)
+    fn pay_batch(n: u32, m: u32, ) -> Weight {
+        (48_215_000 as Weight)
+            // Standard Error: 12_000
+            .saturating_add((41_370_000 as Weight).saturating_mul(n as Weight))
+            // Standard Error: 1_000
+            .saturating_add((6_689_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(T::DbWeight::get().reads(10 as Weight))
+            .saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(m as Weight)))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(m as Weight)))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad Metadata (r:2 w:2)
+    // Storage: Ad SlotsOf (r:2 w:2)
+    // Storage: Ad SlotOf (r:1 w:1)
+    // Storage: Swap Metadata (r:1 w:0)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:3 w:3)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Tag TagsOf (r:0 w:1)
+    // Storage: Ad DeadlineOf (r:1 w:1)
+    // Storage: Ad UnsoldOf (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:0 w:1)
+    // Storage: Ad AdsOf (r:1 w:1)
+    fn terminate(n: u32, ) -> Weight {
+        (31_000_000 as Weight)
+            // Standard Error: 14_000
+            .saturating_add((90_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().reads((10 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes((10 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Ad CapacityOf (r:0 w:1)
+    fn set_capacity() -> Weight {
+        (18_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:0)
+    // Storage: Ad RoundOf (r:1 w:1)
+    // Storage: Ad CommitmentsOf (r:1 w:1)
+    fn commit_bid() -> Weight {
+        (34_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Ad RoundOf (r:1 w:0)
+    // Storage: Ad CommitmentsOf (r:1 w:1)
+    fn reveal_bid() -> Weight {
+        (28_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Ad RoundOf (r:1 w:1)
+    // Storage: Ad CommitmentsOf (r:1 w:1)
+    // Storage: Ad Metadata (r:1 w:0)
+    // Storage: Swap Metadata (r:1 w:0)
+    fn settle_bid(n: u32, ) -> Weight {
+        (24_000_000 as Weight)
+            // Standard Error: 10_000
+            .saturating_add((101_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().reads((15 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((9 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Ad ExpiryCount (r:1 w:1)
+    // Storage: Ad SlotExpiry (r:1 w:1)
+    // Storage: Ad SlotOf (r:1 w:1)
+    // Storage: Ad Metadata (r:1 w:1)
+    // Storage: Swap Metadata (r:1 w:0)
+    // Storage: System Account (r:2 w:2)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: Ad SlotsOf (r:1 w:1)
+    // Storage: Ad DeadlineOf (r:1 w:1)
+    // Storage: Ad AdExpiry (r:1 w:0)
+    fn on_initialize(n: u32, ) -> Weight {
+        (3_000_000 as Weight)
+            // Standard Error: 12_000
+            .saturating_add((96_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().reads((10 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((10 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:1)
+    // Storage: Ad PausedAt (r:0 w:1)
+    fn pause() -> Weight {
+        (21_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:1)
+    // Storage: Ad PausedAt (r:1 w:1)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad SlotsOf (r:1 w:0)
+    // Storage: Ad DeadlineOf (r:10 w:10)
+    // Storage: Ad ExpiryCount (r:20 w:20)
+    // Storage: Ad SlotExpiry (r:0 w:20)
+    fn resume() -> Weight {
+        (146_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(36 as Weight))
+            .saturating_add(T::DbWeight::get().writes(52 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Tag Metadata (r:1000 w:0)
+    // Storage: Ad SlotConfigOf (r:0 w:1)
+    fn set_slot_config(n: u32, ) -> Weight {
+        (20_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((3_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Ad SlotOf (r:1 w:1)
+    // Storage: Ad Metadata (r:1 w:1)
+    // Storage: Assets Asset (r:2 w:2)
+    // Storage: Assets Account (r:3 w:3)
+    // Storage: System Account (r:2 w:2)
+    // Storage: Swap Metadata (r:1 w:1)
+    // Storage: Ad SlotsOf (r:1 w:1)
+    // Storage: Ad DeadlineOf (r:1 w:1)
+    // Storage: Ad SlotExpiry (r:0 w:1)
+    // Storage: Ad ExpiryCount (r:1 w:1)
+    fn evict() -> Weight {
+        (98_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(14 as Weight))
+            .saturating_add(T::DbWeight::get().writes(14 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:1)
+    fn update_metadata(m: u32, ) -> Weight {
+        (26_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((2_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Ad HolderPool (r:1 w:1)
+    // Storage: Ad Holding (r:1 w:1)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    fn stake_fragments() -> Weight {
+        (52_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    // Storage: Ad HolderPool (r:1 w:1)
+    // Storage: Ad Holding (r:1 w:1)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    fn unstake_fragments() -> Weight {
+        (52_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    // Storage: Ad HolderPool (r:1 w:0)
+    // Storage: Ad Holding (r:1 w:1)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    fn claim_holder_share() -> Weight {
+        (48_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -170,7 +412,7 @@ impl WeightInfo for () {
     // Storage: Balances Reserves (r:1 w:0)
     // Storage: Ad DeadlineOf (r:1 w:0)
     // Storage: Ad Metadata (r:1 w:1)
-    fn update_reward_rate() -> Weight {
+    fn update_reward_split() -> Weight {
         (25_000_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(4 as Weight))
             .saturating_add(RocksDbWeight::get().writes(1 as Weight))
@@ -191,6 +433,28 @@ impl WeightInfo for () {
     }
     // Storage: Did DidOf (r:1 w:0)
     // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:1)
+    // Storage: Tag Metadata (r:1 w:0)
+    fn update_targeting(n: u32, ) -> Weight {
+        (27_000_000 as Weight)
+            // Standard Error: 2_000
+            .saturating_add((3_120_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:1)
+    fn update_frequency() -> Weight {
+        (24_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
     // Storage: Ad DeadlineOf (r:1 w:0)
     // Storage: Ad Metadata (r:1 w:1)
     // Storage: System Account (r:1 w:1)
@@ -227,13 +491,216 @@ impl WeightInfo for () {
     // Storage: Assets Asset (r:1 w:1)
     // Storage: Assets Account (r:2 w:2)
     // Storage: System Account (r:1 w:1)
+    // Storage: Ad Stats (r:1 w:1)
+    // Storage: Ad SlotStats (r:1 w:1)
     fn pay(n: u32, ) -> Weight {
         (67_758_000 as Weight)
             // Standard Error: 0
             .saturating_add((6_722_000 as Weight).saturating_mul(n as Weight))
-            .saturating_add(RocksDbWeight::get().reads(12 as Weight))
+            .saturating_add(RocksDbWeight::get().reads(14 as Weight))
             .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
-            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
             .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
     }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad DeadlineOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:0)
+    // Storage: Ad SlotOf (r:1 w:1)
+    // Storage: Tag TagsOf (r:1 w:0)
+    // Storage: Ad Payout (r:1 w:1)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Tag PersonasOf (r:1 w:1)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Ad Stats (r:1 w:1)
+    // Storage: Ad SlotStats (r:1 w:1)
+    fn pay_batch(n: u32, m: u32, ) -> Weight {
+        (48_215_000 as Weight)
+            // Standard Error: 12_000
+            .saturating_add((41_370_000 as Weight).saturating_mul(n as Weight))
+            // Standard Error: 1_000
+            .saturating_add((6_689_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(RocksDbWeight::get().reads(10 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(m as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(m as Weight)))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad Metadata (r:2 w:2)
+    // Storage: Ad SlotsOf (r:2 w:2)
+    // Storage: Ad SlotOf (r:1 w:1)
+    // Storage: Swap Metadata (r:1 w:0)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:3 w:3)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Tag TagsOf (r:0 w:1)
+    // Storage: Ad DeadlineOf (r:1 w:1)
+    // Storage: Ad UnsoldOf (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:0 w:1)
+    // Storage: Ad AdsOf (r:1 w:1)
+    fn terminate(n: u32, ) -> Weight {
+        (31_000_000 as Weight)
+            // Standard Error: 14_000
+            .saturating_add((90_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((10 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((10 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Ad CapacityOf (r:0 w:1)
+    fn set_capacity() -> Weight {
+        (18_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:0)
+    // Storage: Ad RoundOf (r:1 w:1)
+    // Storage: Ad CommitmentsOf (r:1 w:1)
+    fn commit_bid() -> Weight {
+        (34_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Ad RoundOf (r:1 w:0)
+    // Storage: Ad CommitmentsOf (r:1 w:1)
+    fn reveal_bid() -> Weight {
+        (28_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Ad RoundOf (r:1 w:1)
+    // Storage: Ad CommitmentsOf (r:1 w:1)
+    // Storage: Ad Metadata (r:1 w:0)
+    // Storage: Swap Metadata (r:1 w:0)
+    fn settle_bid(n: u32, ) -> Weight {
+        (24_000_000 as Weight)
+            // Standard Error: 10_000
+            .saturating_add((101_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((15 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((9 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Ad ExpiryCount (r:1 w:1)
+    // Storage: Ad SlotExpiry (r:1 w:1)
+    // Storage: Ad SlotOf (r:1 w:1)
+    // Storage: Ad Metadata (r:1 w:1)
+    // Storage: Swap Metadata (r:1 w:0)
+    // Storage: System Account (r:2 w:2)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: Ad SlotsOf (r:1 w:1)
+    // Storage: Ad DeadlineOf (r:1 w:1)
+    // Storage: Ad AdExpiry (r:1 w:0)
+    fn on_initialize(n: u32, ) -> Weight {
+        (3_000_000 as Weight)
+            // Standard Error: 12_000
+            .saturating_add((96_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((10 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((10 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:1)
+    // Storage: Ad PausedAt (r:0 w:1)
+    fn pause() -> Weight {
+        (21_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:1)
+    // Storage: Ad PausedAt (r:1 w:1)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad SlotsOf (r:1 w:0)
+    // Storage: Ad DeadlineOf (r:10 w:10)
+    // Storage: Ad ExpiryCount (r:20 w:20)
+    // Storage: Ad SlotExpiry (r:0 w:20)
+    fn resume() -> Weight {
+        (146_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(36 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(52 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Tag Metadata (r:1000 w:0)
+    // Storage: Ad SlotConfigOf (r:0 w:1)
+    fn set_slot_config(n: u32, ) -> Weight {
+        (20_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((3_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Ad SlotOf (r:1 w:1)
+    // Storage: Ad Metadata (r:1 w:1)
+    // Storage: Assets Asset (r:2 w:2)
+    // Storage: Assets Account (r:3 w:3)
+    // Storage: System Account (r:2 w:2)
+    // Storage: Swap Metadata (r:1 w:1)
+    // Storage: Ad SlotsOf (r:1 w:1)
+    // Storage: Ad DeadlineOf (r:1 w:1)
+    // Storage: Ad SlotExpiry (r:0 w:1)
+    // Storage: Ad ExpiryCount (r:1 w:1)
+    fn evict() -> Weight {
+        (98_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(14 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(14 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:1)
+    fn update_metadata(m: u32, ) -> Weight {
+        (26_000_000 as Weight)
+            // Standard Error: 0
+            .saturating_add((2_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Ad HolderPool (r:1 w:1)
+    // Storage: Ad Holding (r:1 w:1)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    fn stake_fragments() -> Weight {
+        (52_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    // Storage: Ad HolderPool (r:1 w:1)
+    // Storage: Ad Holding (r:1 w:1)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    fn unstake_fragments() -> Weight {
+        (52_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    // Storage: Ad HolderPool (r:1 w:0)
+    // Storage: Ad Holding (r:1 w:1)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    fn claim_holder_share() -> Weight {
+        (48_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
 }
@@ -6,34 +6,40 @@ pub use types::*;
 #[rustfmt::skip]
 pub mod weights;
 
-// #[cfg(test)]
-// mod mock;
+#[cfg(test)]
+mod mock;
 
-// #[cfg(test)]
-// mod tests;
+#[cfg(test)]
+mod tests;
 
 // #[cfg(feature = "runtime-benchmarks")]
 // mod benchmarking;
 
 mod types;
 
-use codec::EncodeLike;
+use codec::{Encode, EncodeLike};
 use frame_support::{
     dispatch::DispatchResult,
     ensure,
+    storage::{unhashed, StoragePrefixedMap},
     traits::{EnsureOrigin, Get},
     weights::GetDispatchInfo,
-    PalletId, Parameter,
+    Blake2_256, BoundedVec, PalletId, Parameter, StorageHasher,
 };
 use frame_system::{ensure_root, ensure_signed};
 use sp_core::U256;
-use sp_runtime::traits::{AccountIdConversion, Dispatchable};
+use sp_runtime::traits::{AccountIdConversion, Dispatchable, Hash};
 use sp_std::prelude::*;
 
 use weights::WeightInfo;
 
 const DEFAULT_RELAYER_THRESHOLD: u32 = 1;
 
+type VotesOf<T> = ProposalVotes<
+    BoundedVec<<T as frame_system::Config>::AccountId, <T as Config>::MaxRelayers>,
+    <T as frame_system::Config>::BlockNumber,
+>;
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -70,12 +76,21 @@ pub mod pallet {
         #[pallet::constant]
         type ProposalLifetime: Get<Self::BlockNumber>;
 
+        /// The maximum number of relayers, which bounds the votes of a proposal
+        #[pallet::constant]
+        type MaxRelayers: Get<u32>;
+
+        /// The maximum length of a method name registered for a resource ID
+        #[pallet::constant]
+        type MaxResourceLen: Get<u32>;
+
         /// Weight information for extrinsics in this pallet
         type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::generate_storage_info]
     pub struct Pallet<T>(_);
 
     /// All whitelisted chains and their respective transaction counts
@@ -107,7 +122,7 @@ pub mod pallet {
     pub(super) type RelayerCount<T: Config> = StorageValue<_, u32, ValueQuery>;
 
     /// All known proposals.
-    /// The key is the hash of the call and the deposit ID, to ensure it's unique.
+    /// The key is the deposit ID and the hash of the call, to ensure it's unique.
     #[pallet::storage]
     #[pallet::getter(fn get_votes)]
     pub(super) type Votes<T: Config> = StorageDoubleMap<
@@ -115,8 +130,8 @@ pub mod pallet {
         Blake2_256,
         ChainId,
         Blake2_256,
-        (DepositNonce, T::Proposal),
-        ProposalVotes<T::AccountId, T::BlockNumber>,
+        (DepositNonce, T::Hash),
+        VotesOf<T>,
         OptionQuery,
     >;
 
@@ -124,7 +139,11 @@ pub mod pallet {
     #[pallet::storage]
     #[pallet::getter(fn get_resources)]
     pub(super) type Resources<T: Config> =
-        StorageMap<_, Blake2_256, ResourceId, Vec<u8>, OptionQuery>;
+        StorageMap<_, Blake2_256, ResourceId, BoundedVec<u8, T::MaxResourceLen>, OptionQuery>;
+
+    /// Storage version of the pallet
+    #[pallet::storage]
+    pub(crate) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -158,7 +177,29 @@ pub mod pallet {
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if <StorageVersion<T>>::get() == Releases::V1 {
+                return 0;
+            }
+
+            // votes were keyed by the call, which can not be recovered from the opaque hasher,
+            // so they are kept and re-keyed by the hash of the call once touched again,
+            // completed ones keep guarding their deposit nonces against replay
+
+            let mut count = 0;
+
+            <Resources<T>>::translate::<Vec<u8>, _>(|_, method| {
+                count += 1;
+
+                method.try_into().ok()
+            });
+
+            <StorageVersion<T>>::set(Releases::V1);
+
+            T::DbWeight::get().reads_writes(count as Weight + 1, count as Weight + 1)
+        }
+    }
 
     #[pallet::error]
     pub enum Error<T> {
@@ -192,6 +233,12 @@ pub mod pallet {
         ProposalAlreadyComplete,
         /// Lifetime of proposal has been exceeded
         ProposalExpired,
+        /// Relayer set is full
+        TooManyRelayers,
+        /// Proposal has more votes than relayers allowed
+        TooManyVotes,
+        /// Method name of resource is too long
+        ResourceTooLong,
     }
 
     #[pallet::call]
@@ -365,7 +412,9 @@ pub mod pallet {
     // The build of genesis for the pallet.
     #[pallet::genesis_build]
     impl<T: Config> GenesisBuild<T> for GenesisConfig {
-        fn build(&self) {}
+        fn build(&self) {
+            <StorageVersion<T>>::set(Releases::V1);
+        }
     }
 }
 
@@ -426,6 +475,8 @@ impl<T: Config> Pallet<T> {
 
     /// Register a method for a resource Id, enabling associated transfers
     pub fn register_resource(id: ResourceId, method: Vec<u8>) -> DispatchResult {
+        let method: BoundedVec<_, _> =
+            method.try_into().map_err(|_| Error::<T>::ResourceTooLong)?;
         <Resources<T>>::insert(id, method);
         Ok(())
     }
@@ -456,6 +507,10 @@ impl<T: Config> Pallet<T> {
             !Self::is_relayer(&relayer),
             Error::<T>::RelayerAlreadyExists
         );
+        ensure!(
+            Self::get_relayer_count() < T::MaxRelayers::get(),
+            Error::<T>::TooManyRelayers
+        );
         <Relayers<T>>::insert(&relayer, true);
         <RelayerCount<T>>::mutate(|i| *i += 1);
 
@@ -474,6 +529,36 @@ impl<T: Config> Pallet<T> {
 
     // *** Proposal voting and execution methods ***
 
+    /// Gets the votes of a proposal, re-keying the votes stored by the call before V1
+    fn votes_of(src_id: ChainId, nonce: DepositNonce, prop: &T::Proposal) -> Option<VotesOf<T>> {
+        let key = (nonce, T::Hashing::hash_of(prop));
+        if let Some(votes) = <Votes<T>>::get(src_id, key) {
+            return Some(votes);
+        }
+
+        let mut legacy = <Votes<T>>::final_prefix().to_vec();
+        legacy.extend(Blake2_256::hash(&src_id.encode()));
+        legacy.extend(Blake2_256::hash(&(nonce, prop).encode()));
+
+        let votes = unhashed::take::<ProposalVotes<Vec<T::AccountId>, T::BlockNumber>>(&legacy)?;
+
+        let bound = |mut voters: Vec<T::AccountId>| {
+            voters.truncate(T::MaxRelayers::get() as usize);
+            BoundedVec::try_from(voters).unwrap_or_default()
+        };
+
+        let votes = ProposalVotes {
+            votes_for: bound(votes.votes_for),
+            votes_against: bound(votes.votes_against),
+            status: votes.status,
+            expiry: votes.expiry,
+        };
+
+        <Votes<T>>::insert(src_id, key, &votes);
+
+        Some(votes)
+    }
+
     /// Commits a vote for a proposal. If the proposal doesn't exist it will be created.
     fn commit_vote(
         who: T::AccountId,
@@ -483,7 +568,8 @@ impl<T: Config> Pallet<T> {
         in_favour: bool,
     ) -> DispatchResult {
         let now = <frame_system::Pallet<T>>::block_number();
-        let mut votes = match <Votes<T>>::get(src_id, (nonce, prop.clone())) {
+        let key = (nonce, T::Hashing::hash_of(&prop));
+        let mut votes = match Self::votes_of(src_id, nonce, &prop) {
             Some(v) => v,
             None => {
                 let mut v = ProposalVotes::default();
@@ -498,14 +584,18 @@ impl<T: Config> Pallet<T> {
         ensure!(!votes.has_voted(&who), Error::<T>::RelayerAlreadyVoted);
 
         if in_favour {
-            votes.votes_for.push(who.clone());
+            let mut voters = votes.votes_for.into_inner();
+            voters.push(who.clone());
+            votes.votes_for = voters.try_into().map_err(|_| Error::<T>::TooManyVotes)?;
             Self::deposit_event(Event::VoteFor(src_id, nonce, who.clone()));
         } else {
-            votes.votes_against.push(who.clone());
+            let mut voters = votes.votes_against.into_inner();
+            voters.push(who.clone());
+            votes.votes_against = voters.try_into().map_err(|_| Error::<T>::TooManyVotes)?;
             Self::deposit_event(Event::VoteAgainst(src_id, nonce, who.clone()));
         }
 
-        <Votes<T>>::insert(src_id, (nonce, prop.clone()), votes.clone());
+        <Votes<T>>::insert(src_id, key, votes);
 
         Ok(())
     }
@@ -516,14 +606,15 @@ impl<T: Config> Pallet<T> {
         src_id: ChainId,
         prop: Box<T::Proposal>,
     ) -> DispatchResult {
-        if let Some(mut votes) = <Votes<T>>::get(src_id, (nonce, prop.clone())) {
+        let key = (nonce, T::Hashing::hash_of(&prop));
+        if let Some(mut votes) = Self::votes_of(src_id, nonce, &prop) {
             let now = <frame_system::Pallet<T>>::block_number();
             ensure!(!votes.is_complete(), Error::<T>::ProposalAlreadyComplete);
             ensure!(!votes.is_expired(now), Error::<T>::ProposalExpired);
 
             let status =
                 votes.try_to_complete(Self::get_relayer_threshold(), Self::get_relayer_count());
-            <Votes<T>>::insert(src_id, (nonce, prop.clone()), votes.clone());
+            <Votes<T>>::insert(src_id, key, votes);
 
             match status {
                 ProposalStatus::Approved => Self::finalize_execution(src_id, nonce, prop),
@@ -101,11 +101,12 @@ parameter_types! {
     pub const MaximumBlockLength: u32 = 2 * 1024;
     pub const AvailableBlockRatio: Perbill = Perbill::one();
     pub const MaxLocks: u32 = 100;
+    pub const MaxReserves: u32 = 50;
 }
 
 // Implement FRAME system pallet configuration trait for the mock runtime
 impl frame_system::Config for MockRuntime {
-    type BaseCallFilter = ();
+    type BaseCallFilter = frame_support::traits::Everything;
     type Origin = Origin;
     type Call = Call;
     type Index = u64;
@@ -143,7 +144,9 @@ impl pallet_balances::Config for MockRuntime {
     type ExistentialDeposit = ExistentialDeposit;
     type AccountStore = System;
     type WeightInfo = ();
-    type MaxLocks = ();
+    type MaxLocks = MaxLocks;
+    type MaxReserves = MaxReserves;
+    type ReserveIdentifier = [u8; 8];
 }
 
 // Parameterize chainbridge pallet
@@ -151,6 +154,8 @@ parameter_types! {
     pub const MockChainId: ChainId = 5;
     pub const ChainBridgePalletId: PalletId = PalletId(*b"chnbrdge");
     pub const ProposalLifetime: u64 = 10;
+    pub const MaxRelayers: u32 = 3;
+    pub const MaxResourceLen: u32 = 32;
 }
 
 // Implement chainbridge pallet configuration trait for the mock runtime
@@ -161,6 +166,8 @@ impl pallet_chainbridge::Config for MockRuntime {
     type PalletId = ChainBridgePalletId;
     type AdminOrigin = EnsureSignedBy<TestUserId, u64>;
     type ProposalLifetime = ProposalLifetime;
+    type MaxRelayers = MaxRelayers;
+    type MaxResourceLen = MaxResourceLen;
     type WeightInfo = MockWeightInfo;
 }
 
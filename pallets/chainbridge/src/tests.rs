@@ -1,6 +1,11 @@
 use crate::{self as pallet_chainbridge, mock::*, *};
 
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_err, assert_noop, assert_ok};
+use sp_runtime::traits::BlakeTwo256;
+
+fn voters(voters: Vec<u64>) -> BoundedVec<u64, MaxRelayers> {
+    voters.try_into().unwrap()
+}
 
 #[test]
 fn derive_ids() {
@@ -14,14 +19,14 @@ fn derive_ids() {
         0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x21, 0x60, 0x5f, 0x71, 0x84, 0x5f,
         0x37, 0x2a, 0x9e, 0xd8, 0x42, 0x53, 0xd2, 0xd0, 0x24, 0xb7, 0xb1, 0x09, 0x99, 0xf4, chain,
     ];
-    assert_eq!(r_id, expected);
+    assert_eq!(r_id, ResourceId::from(expected));
 }
 
 #[test]
 fn complete_proposal_approved() {
     let mut prop = ProposalVotes {
-        votes_for: vec![1, 2],
-        votes_against: vec![3],
+        votes_for: voters(vec![1, 2]),
+        votes_against: voters(vec![3]),
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
     };
@@ -33,8 +38,8 @@ fn complete_proposal_approved() {
 #[test]
 fn complete_proposal_rejected() {
     let mut prop = ProposalVotes {
-        votes_for: vec![1],
-        votes_against: vec![2, 3],
+        votes_for: voters(vec![1]),
+        votes_against: voters(vec![2, 3]),
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
     };
@@ -46,8 +51,8 @@ fn complete_proposal_rejected() {
 #[test]
 fn complete_proposal_bad_threshold() {
     let mut prop = ProposalVotes {
-        votes_for: vec![1, 2],
-        votes_against: vec![],
+        votes_for: voters(vec![1, 2]),
+        votes_against: voters(vec![]),
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
     };
@@ -56,8 +61,8 @@ fn complete_proposal_bad_threshold() {
     assert_eq!(prop.status, ProposalStatus::Initiated);
 
     let mut prop = ProposalVotes {
-        votes_for: vec![],
-        votes_against: vec![1, 2],
+        votes_for: voters(vec![]),
+        votes_against: voters(vec![1, 2]),
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
     };
@@ -71,7 +76,7 @@ fn setup_resources() {
     TestExternalitiesBuilder::default()
         .build()
         .execute_with(|| {
-            let id: ResourceId = [1; 32];
+            let id = ResourceId::repeat_byte(1);
             let method = "Pallet.do_something".as_bytes().to_vec();
             let method2 = "Pallet.do_somethingElse".as_bytes().to_vec();
 
@@ -80,14 +85,20 @@ fn setup_resources() {
                 id,
                 method.clone()
             ));
-            assert_eq!(ChainBridge::get_resources(id), Some(method));
+            assert_eq!(
+                ChainBridge::get_resources(id).map(|m| m.into_inner()),
+                Some(method)
+            );
 
             assert_ok!(ChainBridge::set_resource(
                 Origin::root(),
                 id,
                 method2.clone()
             ));
-            assert_eq!(ChainBridge::get_resources(id), Some(method2));
+            assert_eq!(
+                ChainBridge::get_resources(id).map(|m| m.into_inner()),
+                Some(method2)
+            );
 
             assert_ok!(ChainBridge::remove_resource(Origin::root(), id));
             assert_eq!(ChainBridge::get_resources(id), None);
@@ -110,11 +121,9 @@ fn whitelist_chain() {
                 Error::<MockRuntime>::InvalidChainId
             );
 
-            assert_events(vec![mock::Event::pallet_chainbridge(pallet::Event::<
-                MockRuntime,
-            >::ChainWhitelisted(
-                0
-            ))]);
+            assert_events(vec![mock::Event::ChainBridge(
+                pallet::Event::<MockRuntime>::ChainWhitelisted(0),
+            )]);
         })
 }
 
@@ -132,12 +141,10 @@ fn set_get_threshold() {
             assert_eq!(<RelayerThreshold<MockRuntime>>::get(), 5);
 
             assert_events(vec![
-                mock::Event::pallet_chainbridge(
-                    pallet::Event::<MockRuntime>::RelayerThresholdChanged(TEST_THRESHOLD),
-                ),
-                mock::Event::pallet_chainbridge(
-                    pallet::Event::<MockRuntime>::RelayerThresholdChanged(5),
-                ),
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::RelayerThresholdChanged(
+                    TEST_THRESHOLD,
+                )),
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::RelayerThresholdChanged(5)),
             ]);
         })
 }
@@ -149,7 +156,7 @@ fn asset_transfer_success() {
         .execute_with(|| {
             let dest_id = 2;
             let to = vec![2];
-            let resource_id = [1; 32];
+            let resource_id = ResourceId::repeat_byte(1);
             let metadata = vec![];
             let amount = 100;
             let token_id = vec![1, 2, 3, 4];
@@ -168,10 +175,10 @@ fn asset_transfer_success() {
             ));
 
             assert_events(vec![
-                mock::Event::pallet_chainbridge(pallet::Event::<MockRuntime>::ChainWhitelisted(
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::ChainWhitelisted(
                     dest_id.clone(),
                 )),
-                mock::Event::pallet_chainbridge(pallet::Event::<MockRuntime>::FungibleTransfer(
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::FungibleTransfer(
                     dest_id.clone(),
                     1,
                     resource_id.clone(),
@@ -187,30 +194,30 @@ fn asset_transfer_success() {
                 to.clone(),
                 metadata.clone()
             ));
-            assert_events(vec![mock::Event::pallet_chainbridge(pallet::Event::<
-                MockRuntime,
-            >::NonFungibleTransfer(
-                dest_id.clone(),
-                2,
-                resource_id.clone(),
-                token_id,
-                to.clone(),
-                metadata.clone(),
-            ))]);
+            assert_events(vec![mock::Event::ChainBridge(
+                pallet::Event::<MockRuntime>::NonFungibleTransfer(
+                    dest_id.clone(),
+                    2,
+                    resource_id.clone(),
+                    token_id,
+                    to.clone(),
+                    metadata.clone(),
+                ),
+            )]);
 
             assert_ok!(ChainBridge::transfer_generic(
                 dest_id.clone(),
                 resource_id.clone(),
                 metadata.clone()
             ));
-            assert_events(vec![mock::Event::pallet_chainbridge(pallet::Event::<
-                MockRuntime,
-            >::GenericTransfer(
-                dest_id.clone(),
-                3,
-                resource_id,
-                metadata,
-            ))]);
+            assert_events(vec![mock::Event::ChainBridge(
+                pallet::Event::<MockRuntime>::GenericTransfer(
+                    dest_id.clone(),
+                    3,
+                    resource_id,
+                    metadata,
+                ),
+            )]);
         })
 }
 
@@ -221,17 +228,15 @@ fn asset_transfer_invalid_chain() {
         .execute_with(|| {
             let chain_id = 2;
             let bad_dest_id = 3;
-            let resource_id = [4; 32];
+            let resource_id = ResourceId::repeat_byte(4);
 
             assert_ok!(ChainBridge::whitelist_chain(
                 Origin::root(),
                 chain_id.clone()
             ));
-            assert_events(vec![mock::Event::pallet_chainbridge(pallet::Event::<
-                MockRuntime,
-            >::ChainWhitelisted(
-                chain_id.clone()
-            ))]);
+            assert_events(vec![mock::Event::ChainBridge(
+                pallet::Event::<MockRuntime>::ChainWhitelisted(chain_id.clone()),
+            )]);
 
             assert_noop!(
                 ChainBridge::transfer_fungible(
@@ -289,24 +294,64 @@ fn add_remove_relayer() {
             );
             assert_eq!(ChainBridge::get_relayer_count(), 2);
             assert_events(vec![
-                mock::Event::pallet_chainbridge(pallet::Event::<MockRuntime>::RelayerAdded(
-                    RELAYER_A,
-                )),
-                mock::Event::pallet_chainbridge(pallet::Event::<MockRuntime>::RelayerAdded(
-                    RELAYER_B,
-                )),
-                mock::Event::pallet_chainbridge(pallet::Event::<MockRuntime>::RelayerAdded(
-                    RELAYER_C,
-                )),
-                mock::Event::pallet_chainbridge(pallet::Event::<MockRuntime>::RelayerRemoved(
-                    RELAYER_B,
-                )),
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::RelayerAdded(RELAYER_A)),
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::RelayerAdded(RELAYER_B)),
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::RelayerAdded(RELAYER_C)),
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::RelayerRemoved(RELAYER_B)),
             ]);
         })
 }
 
+#[test]
+fn add_relayer_over_limit() {
+    TestExternalitiesBuilder::default()
+        .build()
+        .execute_with(|| {
+            assert_ok!(ChainBridge::add_relayer(Origin::root(), RELAYER_A));
+            assert_ok!(ChainBridge::add_relayer(Origin::root(), RELAYER_B));
+            assert_ok!(ChainBridge::add_relayer(Origin::root(), RELAYER_C));
+            assert_eq!(ChainBridge::get_relayer_count(), MaxRelayers::get());
+
+            assert_noop!(
+                ChainBridge::add_relayer(Origin::root(), 0x5),
+                Error::<MockRuntime>::TooManyRelayers
+            );
+
+            // a seat is freed
+            assert_ok!(ChainBridge::remove_relayer(Origin::root(), RELAYER_B));
+            assert_ok!(ChainBridge::add_relayer(Origin::root(), 0x5));
+            assert_eq!(ChainBridge::get_relayer_count(), MaxRelayers::get());
+        })
+}
+
+#[test]
+fn set_resource_too_long() {
+    TestExternalitiesBuilder::default()
+        .build()
+        .execute_with(|| {
+            let id = ResourceId::repeat_byte(1);
+            let max = MaxResourceLen::get() as usize;
+
+            assert_noop!(
+                ChainBridge::set_resource(Origin::root(), id, vec![b'a'; max + 1]),
+                Error::<MockRuntime>::ResourceTooLong
+            );
+            assert_eq!(ChainBridge::get_resources(id), None);
+
+            assert_ok!(ChainBridge::set_resource(
+                Origin::root(),
+                id,
+                vec![b'a'; max]
+            ));
+            assert_eq!(
+                ChainBridge::get_resources(id).map(|m| m.into_inner()),
+                Some(vec![b'a'; max])
+            );
+        })
+}
+
 fn make_proposal(r: Vec<u8>) -> mock::Call {
-    mock::Call::System(frame_system::Call::remark(r))
+    mock::Call::System(frame_system::Call::remark { remark: r })
 }
 
 #[test]
@@ -328,10 +373,11 @@ fn create_sucessful_proposal() {
                 r_id,
                 Box::new(proposal.clone())
             ));
-            let prop = ChainBridge::get_votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+            let prop =
+                ChainBridge::get_votes(src_id, (prop_id, BlakeTwo256::hash_of(&proposal))).unwrap();
             let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![],
+                votes_for: voters(vec![RELAYER_A]),
+                votes_against: voters(vec![]),
                 status: ProposalStatus::Initiated,
                 expiry: ProposalLifetime::get() + 1,
             };
@@ -345,10 +391,11 @@ fn create_sucessful_proposal() {
                 r_id,
                 Box::new(proposal.clone())
             ));
-            let prop = ChainBridge::get_votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+            let prop =
+                ChainBridge::get_votes(src_id, (prop_id, BlakeTwo256::hash_of(&proposal))).unwrap();
             let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![RELAYER_B],
+                votes_for: voters(vec![RELAYER_A]),
+                votes_against: voters(vec![RELAYER_B]),
                 status: ProposalStatus::Initiated,
                 expiry: ProposalLifetime::get() + 1,
             };
@@ -362,28 +409,29 @@ fn create_sucessful_proposal() {
                 r_id,
                 Box::new(proposal.clone())
             ));
-            let prop = ChainBridge::get_votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+            let prop =
+                ChainBridge::get_votes(src_id, (prop_id, BlakeTwo256::hash_of(&proposal))).unwrap();
             let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A, RELAYER_C],
-                votes_against: vec![RELAYER_B],
+                votes_for: voters(vec![RELAYER_A, RELAYER_C]),
+                votes_against: voters(vec![RELAYER_B]),
                 status: ProposalStatus::Approved,
                 expiry: ProposalLifetime::get() + 1,
             };
             assert_eq!(prop, expected);
             assert_events(vec![
-                mock::Event::pallet_chainbridge(pallet::Event::<MockRuntime>::VoteFor(
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::VoteFor(
                     src_id, prop_id, RELAYER_A,
                 )),
-                mock::Event::pallet_chainbridge(pallet::Event::<MockRuntime>::VoteAgainst(
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::VoteAgainst(
                     src_id, prop_id, RELAYER_B,
                 )),
-                mock::Event::pallet_chainbridge(pallet::Event::<MockRuntime>::VoteFor(
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::VoteFor(
                     src_id, prop_id, RELAYER_C,
                 )),
-                mock::Event::pallet_chainbridge(pallet::Event::<MockRuntime>::ProposalApproved(
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::ProposalApproved(
                     src_id, prop_id,
                 )),
-                mock::Event::pallet_chainbridge(pallet::Event::<MockRuntime>::ProposalSucceeded(
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::ProposalSucceeded(
                     src_id, prop_id,
                 )),
             ]);
@@ -409,10 +457,11 @@ fn create_unsucessful_proposal() {
                 r_id,
                 Box::new(proposal.clone())
             ));
-            let prop = ChainBridge::get_votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+            let prop =
+                ChainBridge::get_votes(src_id, (prop_id, BlakeTwo256::hash_of(&proposal))).unwrap();
             let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![],
+                votes_for: voters(vec![RELAYER_A]),
+                votes_against: voters(vec![]),
                 status: ProposalStatus::Initiated,
                 expiry: ProposalLifetime::get() + 1,
             };
@@ -426,10 +475,11 @@ fn create_unsucessful_proposal() {
                 r_id,
                 Box::new(proposal.clone())
             ));
-            let prop = ChainBridge::get_votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+            let prop =
+                ChainBridge::get_votes(src_id, (prop_id, BlakeTwo256::hash_of(&proposal))).unwrap();
             let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![RELAYER_B],
+                votes_for: voters(vec![RELAYER_A]),
+                votes_against: voters(vec![RELAYER_B]),
                 status: ProposalStatus::Initiated,
                 expiry: ProposalLifetime::get() + 1,
             };
@@ -443,10 +493,11 @@ fn create_unsucessful_proposal() {
                 r_id,
                 Box::new(proposal.clone())
             ));
-            let prop = ChainBridge::get_votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+            let prop =
+                ChainBridge::get_votes(src_id, (prop_id, BlakeTwo256::hash_of(&proposal))).unwrap();
             let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![RELAYER_B, RELAYER_C],
+                votes_for: voters(vec![RELAYER_A]),
+                votes_against: voters(vec![RELAYER_B, RELAYER_C]),
                 status: ProposalStatus::Rejected,
                 expiry: ProposalLifetime::get() + 1,
             };
@@ -459,16 +510,16 @@ fn create_unsucessful_proposal() {
             );
 
             assert_events(vec![
-                mock::Event::pallet_chainbridge(pallet::Event::<MockRuntime>::VoteFor(
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::VoteFor(
                     src_id, prop_id, RELAYER_A,
                 )),
-                mock::Event::pallet_chainbridge(pallet::Event::<MockRuntime>::VoteAgainst(
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::VoteAgainst(
                     src_id, prop_id, RELAYER_B,
                 )),
-                mock::Event::pallet_chainbridge(pallet::Event::<MockRuntime>::VoteAgainst(
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::VoteAgainst(
                     src_id, prop_id, RELAYER_C,
                 )),
-                mock::Event::pallet_chainbridge(pallet::Event::<MockRuntime>::ProposalRejected(
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::ProposalRejected(
                     src_id, prop_id,
                 )),
             ]);
@@ -494,10 +545,11 @@ fn execute_after_threshold_change() {
                 r_id,
                 Box::new(proposal.clone())
             ));
-            let prop = ChainBridge::get_votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+            let prop =
+                ChainBridge::get_votes(src_id, (prop_id, BlakeTwo256::hash_of(&proposal))).unwrap();
             let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![],
+                votes_for: voters(vec![RELAYER_A]),
+                votes_against: voters(vec![]),
                 status: ProposalStatus::Initiated,
                 expiry: ProposalLifetime::get() + 1,
             };
@@ -514,10 +566,11 @@ fn execute_after_threshold_change() {
                 Box::new(proposal.clone())
             ));
 
-            let prop = ChainBridge::get_votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+            let prop =
+                ChainBridge::get_votes(src_id, (prop_id, BlakeTwo256::hash_of(&proposal))).unwrap();
             let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![],
+                votes_for: voters(vec![RELAYER_A]),
+                votes_against: voters(vec![]),
                 status: ProposalStatus::Approved,
                 expiry: ProposalLifetime::get() + 1,
             };
@@ -530,16 +583,14 @@ fn execute_after_threshold_change() {
             );
 
             assert_events(vec![
-                mock::Event::pallet_chainbridge(pallet::Event::<MockRuntime>::VoteFor(
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::VoteFor(
                     src_id, prop_id, RELAYER_A,
                 )),
-                mock::Event::pallet_chainbridge(
-                    pallet::Event::<MockRuntime>::RelayerThresholdChanged(1),
-                ),
-                mock::Event::pallet_chainbridge(pallet::Event::<MockRuntime>::ProposalApproved(
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::RelayerThresholdChanged(1)),
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::ProposalApproved(
                     src_id, prop_id,
                 )),
-                mock::Event::pallet_chainbridge(pallet::Event::<MockRuntime>::ProposalSucceeded(
+                mock::Event::ChainBridge(pallet::Event::<MockRuntime>::ProposalSucceeded(
                     src_id, prop_id,
                 )),
             ]);
@@ -565,10 +616,11 @@ fn proposal_expires() {
                 r_id,
                 Box::new(proposal.clone())
             ));
-            let prop = ChainBridge::get_votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+            let prop =
+                ChainBridge::get_votes(src_id, (prop_id, BlakeTwo256::hash_of(&proposal))).unwrap();
             let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![],
+                votes_for: voters(vec![RELAYER_A]),
+                votes_against: voters(vec![]),
                 status: ProposalStatus::Initiated,
                 expiry: ProposalLifetime::get() + 1,
             };
@@ -590,10 +642,11 @@ fn proposal_expires() {
             );
 
             // Proposal state should remain unchanged
-            let prop = ChainBridge::get_votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+            let prop =
+                ChainBridge::get_votes(src_id, (prop_id, BlakeTwo256::hash_of(&proposal))).unwrap();
             let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![],
+                votes_for: voters(vec![RELAYER_A]),
+                votes_against: voters(vec![]),
                 status: ProposalStatus::Initiated,
                 expiry: ProposalLifetime::get() + 1,
             };
@@ -609,19 +662,78 @@ fn proposal_expires() {
                 ),
                 Error::<MockRuntime>::ProposalExpired
             );
-            let prop = ChainBridge::get_votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+            let prop =
+                ChainBridge::get_votes(src_id, (prop_id, BlakeTwo256::hash_of(&proposal))).unwrap();
             let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![],
+                votes_for: voters(vec![RELAYER_A]),
+                votes_against: voters(vec![]),
                 status: ProposalStatus::Initiated,
                 expiry: ProposalLifetime::get() + 1,
             };
             assert_eq!(prop, expected);
 
-            assert_events(vec![mock::Event::pallet_chainbridge(pallet::Event::<
-                MockRuntime,
-            >::VoteFor(
-                src_id, prop_id, RELAYER_A,
-            ))]);
+            assert_events(vec![mock::Event::ChainBridge(
+                pallet::Event::<MockRuntime>::VoteFor(src_id, prop_id, RELAYER_A),
+            )]);
+        })
+}
+
+#[test]
+fn legacy_votes_guard_replay() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    TestExternalitiesBuilder::default()
+        .build_with(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let prop_id = 1;
+            let proposal = make_proposal(vec![10]);
+
+            // votes of a completed proposal, keyed by the call before V1
+            let mut key = Votes::<MockRuntime>::final_prefix().to_vec();
+            key.extend(Blake2_256::hash(&src_id.encode()));
+            key.extend(Blake2_256::hash(&(prop_id, &proposal).encode()));
+
+            let legacy = ProposalVotes {
+                votes_for: vec![RELAYER_A, RELAYER_B],
+                votes_against: vec![],
+                status: ProposalStatus::Approved,
+                expiry: ProposalLifetime::get() + 1,
+            };
+            unhashed::put(&key, &legacy);
+
+            // replaying the deposit is rejected, and the votes are re-keyed
+            assert_err!(
+                ChainBridge::acknowledge_proposal(
+                    Origin::signed(RELAYER_C),
+                    prop_id,
+                    src_id,
+                    r_id,
+                    Box::new(proposal.clone())
+                ),
+                Error::<MockRuntime>::ProposalAlreadyComplete
+            );
+
+            assert_eq!(unhashed::get_raw(&key), None);
+
+            let prop =
+                ChainBridge::get_votes(src_id, (prop_id, BlakeTwo256::hash_of(&proposal))).unwrap();
+            let expected = ProposalVotes {
+                votes_for: voters(vec![RELAYER_A, RELAYER_B]),
+                votes_against: voters(vec![]),
+                status: ProposalStatus::Approved,
+                expiry: ProposalLifetime::get() + 1,
+            };
+            assert_eq!(prop, expected);
+
+            assert_noop!(
+                ChainBridge::eval_vote_state(
+                    Origin::signed(RELAYER_C),
+                    prop_id,
+                    src_id,
+                    Box::new(proposal.clone())
+                ),
+                Error::<MockRuntime>::ProposalAlreadyComplete
+            );
         })
 }
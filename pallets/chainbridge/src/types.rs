@@ -1,29 +1,53 @@
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::BoundedVec;
 use scale_info::TypeInfo;
 use sp_core::H256;
 use sp_runtime::RuntimeDebug;
-use sp_std::prelude::*;
 
 pub type ChainId = u8;
 pub type DepositNonce = u64;
 pub type ResourceId = H256;
 
-#[derive(Clone, Copy, Decode, Encode, Eq, Ord, PartialEq, PartialOrd, RuntimeDebug, TypeInfo)]
+#[derive(Clone, Copy, Decode, Encode, Eq, MaxEncodedLen, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum Releases {
+    V0,
+    V1,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V0
+    }
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Decode,
+    Encode,
+    Eq,
+    MaxEncodedLen,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    RuntimeDebug,
+    TypeInfo,
+)]
 pub enum ProposalStatus {
     Initiated,
     Approved,
     Rejected,
 }
 
-#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
-pub struct ProposalVotes<AccountId, BlockNumber> {
-    pub votes_for: Vec<AccountId>,
-    pub votes_against: Vec<AccountId>,
+#[derive(Clone, Decode, Encode, Eq, MaxEncodedLen, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ProposalVotes<Voters, BlockNumber> {
+    pub votes_for: Voters,
+    pub votes_against: Voters,
     pub status: ProposalStatus,
     pub expiry: BlockNumber,
 }
 
-impl<A: PartialEq, B: PartialOrd + Default> ProposalVotes<A, B> {
+impl<A: PartialEq, B: PartialOrd + Default, S> ProposalVotes<BoundedVec<A, S>, B> {
     /// Attempts to mark the proposal as approve or rejected.
     /// Returns true if the status changes from active.
     pub(crate) fn try_to_complete(&mut self, threshold: u32, total: u32) -> ProposalStatus {
@@ -55,11 +79,11 @@ impl<A: PartialEq, B: PartialOrd + Default> ProposalVotes<A, B> {
 }
 
 // Implement default trait for the proposal votes structure.
-impl<AccountId, BlockNumber: Default> Default for ProposalVotes<AccountId, BlockNumber> {
+impl<Voters: Default, BlockNumber: Default> Default for ProposalVotes<Voters, BlockNumber> {
     fn default() -> Self {
         Self {
-            votes_for: vec![],
-            votes_against: vec![],
+            votes_for: Default::default(),
+            votes_against: Default::default(),
             status: ProposalStatus::Initiated,
             expiry: BlockNumber::default(),
         }
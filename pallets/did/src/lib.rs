@@ -23,6 +23,7 @@ use frame_support::{
     PalletId,
 };
 use parami_did_utils::derive_storage_key;
+use parami_primitives::derive;
 use scale_info::TypeInfo;
 use sp_runtime::{
     traits::{
@@ -57,7 +58,8 @@ pub mod pallet {
             + AtLeast32BitUnsigned
             + Default
             + Bounded
-            + Copy;
+            + Copy
+            + MaxEncodedLen;
 
         /// The reservable currency trait
         type Currency: NamedReservableCurrency<AccountOf<Self>, ReserveIdentifier = [u8; 8]>;
@@ -95,6 +97,7 @@ pub mod pallet {
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::generate_storage_info]
     pub struct Pallet<T>(_);
 
     /// The metadata of a DID.
@@ -113,6 +116,11 @@ pub mod pallet {
     pub(super) type ReferrerOf<T: Config> =
         StorageMap<_, Identity, T::DecentralizedId, T::DecentralizedId>;
 
+    /// Nonce of DIDs derived from an account
+    #[pallet::storage]
+    #[pallet::getter(fn id_nonce_of)]
+    pub(super) type IdNonceOf<T: Config> = StorageMap<_, Blake2_256, AccountOf<T>, u64, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -158,12 +166,10 @@ pub mod pallet {
 
             let created = <frame_system::Pallet<T>>::block_number();
 
-            // TODO: use a HMAC-based algorithm.
-            let mut raw = <AccountOf<T>>::encode(&who);
-            let mut ord = T::BlockNumber::encode(&created);
-            raw.append(&mut ord);
+            let nonce = Self::id_nonce_of(&who);
+            <IdNonceOf<T>>::insert(&who, nonce.saturating_add(1));
 
-            let did = <T as Config>::Hashing::hash(&raw);
+            let did = derive::id::<<T as Config>::Hashing, _>(&T::PalletId::get().0, &who, nonce);
             let did = Self::truncate(&did);
 
             // 2. deposit
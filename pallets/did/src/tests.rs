@@ -113,6 +113,23 @@ fn should_reassign() {
     });
 }
 
+#[test]
+fn should_reassign_in_same_block() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Did::register(Origin::signed(BOB), None));
+
+        let did = <DidOf<Test>>::get(&BOB).unwrap();
+
+        assert_ok!(Did::revoke(Origin::signed(BOB)));
+
+        assert_ok!(Did::register(Origin::signed(BOB), None));
+
+        assert_ne!(<DidOf<Test>>::get(&BOB), Some(did));
+
+        assert_eq!(<Metadata<Test>>::get(&did).unwrap().revoked, true);
+    });
+}
+
 #[test]
 fn should_transfer() {
     new_test_ext().execute_with(|| {
@@ -15,7 +15,7 @@ benchmarks! {
     }
 
     link_sociality {
-        let n in 0 .. 1000;
+        let n in 0 .. T::MaxLinkLen::get();
 
         let caller: T::AccountId = whitelisted_caller();
 
@@ -46,7 +46,7 @@ benchmarks! {
         let signature = [0u8; 65];
     }: _(RawOrigin::Signed(caller), types::AccountType::Unknown, address.clone(), signature)
     verify {
-        assert_eq!(<LinksOf<T>>::get(&did, &types::AccountType::Unknown), Some(address));
+        assert_eq!(<LinksOf<T>>::get(&did, &types::AccountType::Unknown), Some(address.try_into().unwrap()));
     }
 
     deposit {
@@ -118,7 +118,7 @@ benchmarks! {
     }
 
     submit_link {
-        let n in 0 .. 1000;
+        let n in 0 .. T::MaxLinkLen::get();
 
         let caller: T::AccountId = whitelisted_caller();
 
@@ -143,11 +143,11 @@ benchmarks! {
         Linker::<T>::link_sociality(RawOrigin::Signed(applicant.clone()).into(), types::AccountType::Mastodon, profile.clone())?;
     }: _(RawOrigin::Signed(caller), did.clone(), types::AccountType::Mastodon, profile.clone(), true)
     verify {
-        assert_eq!(<LinksOf<T>>::get(&did, &types::AccountType::Mastodon), Some(profile));
+        assert_eq!(<LinksOf<T>>::get(&did, &types::AccountType::Mastodon), Some(profile.try_into().unwrap()));
     }
 
     submit_score {
-        let n in 0 .. 1000;
+        let n in 0 .. 32; // within the tag length bound of the mock and runtimes
 
         let caller: T::AccountId = whitelisted_caller();
 
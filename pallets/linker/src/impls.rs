@@ -1,6 +1,6 @@
 use crate::{
-    btc, types, witness::WitnessProgram, Config, DidOf, Error, Event, Linked, LinksOf, Pallet,
-    PendingOf,
+    btc, types, witness::WitnessProgram, Config, DidOf, Error, Event, LinkOf, Linked, LinksOf,
+    Pallet, PendingOf,
 };
 
 use base58::ToBase58;
@@ -16,7 +16,11 @@ macro_rules! is_task {
 }
 
 impl<T: Config> Pallet<T> {
-    fn ensure_profile(did: &DidOf<T>, site: types::AccountType, profile: &[u8]) -> DispatchResult {
+    fn ensure_profile(
+        did: &DidOf<T>,
+        site: types::AccountType,
+        profile: &LinkOf<T>,
+    ) -> DispatchResult {
         use types::AccountType::*;
 
         ensure!(!<LinksOf<T>>::contains_key(did, site), Error::<T>::Exists);
@@ -75,13 +79,18 @@ impl<T: Config> Pallet<T> {
         profile: Vec<u8>,
         registrar: DidOf<T>,
     ) -> DispatchResult {
-        Self::ensure_profile(&did, site, &profile)?;
+        let link: LinkOf<T> = profile
+            .clone()
+            .try_into()
+            .map_err(|_| Error::<T>::TooLong)?;
+
+        Self::ensure_profile(&did, site, &link)?;
 
         <PendingOf<T>>::remove(site, &did);
 
-        <Linked<T>>::insert(site, &profile, true);
+        <Linked<T>>::insert(site, &link, true);
 
-        <LinksOf<T>>::insert(&did, site, profile.clone());
+        <LinksOf<T>>::insert(&did, site, link);
 
         Self::deposit_event(Event::<T>::AccountLinked(did, site, profile, registrar));
 
@@ -96,6 +105,8 @@ impl<T: Config> Pallet<T> {
         use frame_support::traits::Get;
         use sp_runtime::traits::Saturating;
 
+        let profile: LinkOf<T> = profile.try_into().map_err(|_| Error::<T>::TooLong)?;
+
         Self::ensure_profile(&did, site, &profile)?;
 
         ensure!(
@@ -19,6 +19,7 @@ mod benchmarking;
 mod btc;
 mod did;
 mod impls;
+mod migrations;
 mod ocw;
 mod types;
 mod witness;
@@ -27,11 +28,11 @@ use frame_support::{
     dispatch::{DispatchResult, DispatchResultWithPostInfo},
     ensure,
     traits::{Currency, NamedReservableCurrency, OnUnbalanced},
-    PalletId,
+    BoundedVec, PalletId,
 };
 use frame_system::offchain::CreateSignedTransaction;
 use parami_did::{EnsureDid, Pallet as Did};
-use parami_traits::Tags;
+use parami_traits::{Links, Tags};
 use sp_runtime::traits::Hash;
 use sp_std::prelude::*;
 
@@ -42,6 +43,7 @@ type BalanceOf<T> = <CurrencyOf<T> as Currency<AccountOf<T>>>::Balance;
 type CurrencyOf<T> = <T as parami_did::Config>::Currency;
 type DidOf<T> = <T as parami_did::Config>::DecentralizedId;
 type HashOf<T> = <<T as frame_system::Config>::Hashing as Hash>::Output;
+type LinkOf<T> = BoundedVec<u8, <T as Config>::MaxLinkLen>;
 type NegativeImbOf<T> = <CurrencyOf<T> as Currency<AccountOf<T>>>::NegativeImbalance;
 
 #[frame_support::pallet]
@@ -59,6 +61,10 @@ pub mod pallet {
         /// The overarching event type
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
+        /// The maximum length of a linked account or profile
+        #[pallet::constant]
+        type MaxLinkLen: Get<u32>;
+
         /// Minimum deposit to become a registrar
         #[pallet::constant]
         type MinimumDeposit: Get<BalanceOf<Self>>;
@@ -90,6 +96,7 @@ pub mod pallet {
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::generate_storage_info]
     pub struct Pallet<T>(_);
 
     /// Linked accounts of a DID
@@ -101,7 +108,7 @@ pub mod pallet {
         DidOf<T>,
         Twox64Concat,
         types::AccountType,
-        Vec<u8>, //
+        LinkOf<T>, //
     >;
 
     /// Accounts pending to be checked with the offchain worker
@@ -113,7 +120,7 @@ pub mod pallet {
         types::AccountType,
         Identity,
         DidOf<T>,
-        types::Pending<T::BlockNumber>,
+        types::Pending<T::BlockNumber, LinkOf<T>>,
     >;
 
     /// Linked accounts
@@ -124,7 +131,7 @@ pub mod pallet {
         Twox64Concat,
         types::AccountType,
         Blake2_256,
-        Vec<u8>,
+        LinkOf<T>,
         bool,
         ValueQuery,
     >;
@@ -134,6 +141,10 @@ pub mod pallet {
     #[pallet::getter(fn registrar)]
     pub(super) type Registrar<T: Config> = StorageMap<_, Identity, DidOf<T>, bool>;
 
+    /// Storage version of the pallet
+    #[pallet::storage]
+    pub(crate) type StorageVersion<T: Config> = StorageValue<_, types::Releases, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -161,6 +172,14 @@ pub mod pallet {
                 }
             }
         }
+
+        fn on_runtime_upgrade() -> Weight {
+            if <StorageVersion<T>>::get() == types::Releases::V1 {
+                return 0;
+            }
+
+            migrations::migrate::<T>()
+        }
     }
 
     #[pallet::error]
@@ -173,6 +192,7 @@ pub mod pallet {
         InvalidAddress,
         InvalidSignature,
         NotExists,
+        TooLong,
         UnexpectedAddress,
         UnsupportedSite,
     }
@@ -386,13 +406,17 @@ pub mod pallet {
     impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
         fn build(&self) {
             for (did, typ, dat) in &self.links {
-                <LinksOf<T>>::insert(did, typ, dat);
-                <Linked<T>>::insert(typ, dat, true);
+                let dat: LinkOf<T> = dat.clone().try_into().expect("link too long");
+
+                <LinksOf<T>>::insert(did, typ, &dat);
+                <Linked<T>>::insert(typ, &dat, true);
             }
 
             for registrar in &self.registrars {
                 <Registrar<T>>::insert(registrar, true);
             }
+
+            <StorageVersion<T>>::set(types::Releases::V1);
         }
     }
 
@@ -417,3 +441,12 @@ pub mod pallet {
         }
     }
 }
+
+impl<T: Config> Links for Pallet<T> {
+    type DecentralizedId = DidOf<T>;
+    type AccountType = types::AccountType;
+
+    fn has_link(did: &Self::DecentralizedId, site: &Self::AccountType) -> bool {
+        <LinksOf<T>>::contains_key(did, site)
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{types, Config, Linked, LinksOf, PendingOf, StorageVersion};
+use codec::{Encode, EncodeLike, Output};
+use frame_support::{traits::Get, weights::Weight, BoundedVec};
+use sp_std::prelude::*;
+
+pub fn migrate<T: Config>() -> Weight {
+    let version = <StorageVersion<T>>::get();
+
+    let mut weight: Weight = 0;
+
+    if version < types::Releases::V1 {
+        weight = weight.saturating_add(bounded::migrate::<T>());
+    }
+
+    <StorageVersion<T>>::set(types::Releases::V1);
+
+    weight.saturating_add(T::DbWeight::get().writes(1))
+}
+
+mod bounded {
+    use super::*;
+
+    /// A raw link encodes as a bounded one, to address keys of links which no longer fit
+    struct RawLink<'a>(&'a [u8]);
+
+    impl Encode for RawLink<'_> {
+        fn size_hint(&self) -> usize {
+            self.0.size_hint()
+        }
+
+        fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+            self.0.encode_to(dest)
+        }
+    }
+
+    impl<S: Get<u32>> EncodeLike<BoundedVec<u8, S>> for RawLink<'_> {}
+
+    /// Links and pendings were unbounded, drop those exceeding the maximum length
+    pub fn migrate<T: Config>() -> Weight {
+        let mut read = 0;
+        let mut write = 0;
+
+        let mut dropped = Vec::new();
+
+        <LinksOf<T>>::translate::<Vec<u8>, _>(|_, site, link| {
+            read += 1;
+            write += 1;
+
+            match link.clone().try_into() {
+                Ok(bounded) => Some(bounded),
+                Err(_) => {
+                    dropped.push((site, link));
+                    None
+                }
+            }
+        });
+
+        for (site, link) in dropped {
+            write += 1;
+
+            <Linked<T>>::remove(site, RawLink(&link));
+        }
+
+        <PendingOf<T>>::translate::<types::Pending<T::BlockNumber, Vec<u8>>, _>(|_, _, pending| {
+            read += 1;
+            write += 1;
+
+            Some(types::Pending {
+                profile: pending.profile.try_into().ok()?,
+                deadline: pending.deadline,
+                created: pending.created,
+            })
+        });
+
+        T::DbWeight::get().reads_writes(read as Weight, write as Weight)
+    }
+}
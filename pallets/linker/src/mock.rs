@@ -141,6 +141,7 @@ impl parami_did::Config for Test {
 
 parameter_types! {
     pub const SubmissionFee: Balance = 1;
+    pub const MaxTagLen: u32 = 32;
 }
 
 impl parami_tag::Config for Test {
@@ -148,12 +149,14 @@ impl parami_tag::Config for Test {
     type Currency = Balances;
     type DecentralizedId = H160;
     type SubmissionFee = SubmissionFee;
+    type MaxTagLen = MaxTagLen;
     type CallOrigin = parami_did::EnsureDid<Self>;
     type ForceOrigin = EnsureRoot<Self::AccountId>;
     type WeightInfo = ();
 }
 
 parameter_types! {
+    pub const MaxLinkLen: u32 = 256;
     pub const PendingLifetime: u64 = 5;
     pub const UnsignedPriority: u64 = 3;
     pub const MinimumDeposit: Balance = 10;
@@ -163,6 +166,7 @@ parameter_types! {
 impl parami_linker::Config for Test {
     type Event = Event;
     type ForceOrigin = EnsureRoot<Self::AccountId>;
+    type MaxLinkLen = MaxLinkLen;
     type MinimumDeposit = MinimumDeposit;
     type PalletId = LinkerPalletId;
     type PendingLifetime = PendingLifetime;
@@ -84,7 +84,7 @@ impl<T: Config + CreateSignedTransaction<Call<T>>> Pallet<T> {
                 };
 
                 if let Ok(()) = result {
-                    Self::ocw_submit_link(did, site, task.profile, true);
+                    Self::ocw_submit_link(did, site, task.profile.into_inner(), true);
                 }
             }
         }
@@ -1,6 +1,6 @@
 use crate::{
-    mock::*, ocw::USER_AGENT, types::AccountType, Config, Error, Linked, LinksOf, PendingOf,
-    Registrar,
+    mock::*, ocw::USER_AGENT, types::AccountType, Config, Error, LinkOf, Linked, LinksOf,
+    PendingOf, Registrar,
 };
 use codec::Decode;
 use frame_support::{assert_noop, assert_ok, traits::Hooks};
@@ -46,7 +46,7 @@ fn should_link() {
         assert_ne!(maybe_pending, None);
 
         let pending = maybe_pending.unwrap();
-        assert_eq!(pending.profile, profile);
+        assert_eq!(pending.profile.into_inner(), profile);
         assert_eq!(pending.deadline, <Test as Config>::PendingLifetime::get());
 
         assert_ok!(Linker::insert_link(
@@ -61,11 +61,13 @@ fn should_link() {
             None
         );
 
-        assert!(<Linked<Test>>::get(AccountType::Telegram, &profile));
+        let link: LinkOf<Test> = profile.try_into().unwrap();
+
+        assert!(<Linked<Test>>::get(AccountType::Telegram, &link));
 
         assert_eq!(
             <LinksOf<Test>>::get(&DID_ALICE, AccountType::Telegram),
-            Some(profile)
+            Some(link)
         );
     })
 }
@@ -138,6 +140,18 @@ fn should_ocw_submit() {
     });
 }
 
+#[test]
+fn should_fail_when_too_long() {
+    new_test_ext().execute_with(|| {
+        let profile = [&b"https://t.me/"[..], &[b'a'; 256]].concat();
+
+        assert_noop!(
+            Linker::link_sociality(Origin::signed(ALICE), AccountType::Telegram, profile),
+            Error::<Test>::TooLong
+        );
+    })
+}
+
 #[test]
 fn should_submit() {
     new_test_ext().execute_with(|| {
@@ -151,11 +165,13 @@ fn should_submit() {
             true,
         ));
 
-        assert!(<Linked<Test>>::get(AccountType::Telegram, &profile));
+        let link: LinkOf<Test> = profile.try_into().unwrap();
+
+        assert!(<Linked<Test>>::get(AccountType::Telegram, &link));
 
         assert_eq!(
             <LinksOf<Test>>::get(&DID_ALICE, AccountType::Telegram),
-            Some(profile)
+            Some(link)
         );
     })
 }
@@ -336,11 +352,13 @@ fn should_link_crypto() {
             signature,
         ));
 
-        assert!(<Linked<Test>>::get(AccountType::Unknown, &address));
+        let link: LinkOf<Test> = address.try_into().unwrap();
+
+        assert!(<Linked<Test>>::get(AccountType::Unknown, &link));
 
         assert_eq!(
             <LinksOf<Test>>::get(&DID_ALICE, AccountType::Unknown),
-            Some(address)
+            Some(link)
         );
     });
 }
@@ -26,6 +26,7 @@ use frame_support::{
     weights::GetDispatchInfo,
     PalletId,
 };
+use parami_primitives::derive;
 use sp_runtime::traits::{AccountIdConversion, Dispatchable, Saturating};
 use sp_std::boxed::Box;
 
@@ -68,6 +69,7 @@ pub mod pallet {
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::generate_storage_info]
     pub struct Pallet<T>(_);
 
     /// map from controller account to `StableAccount`
@@ -86,6 +88,11 @@ pub mod pallet {
     #[pallet::getter(fn controller)]
     pub(super) type Controller<T: Config> = StorageMap<_, Blake2_256, AccountOf<T>, AccountOf<T>>;
 
+    /// map from magic account to nonce of derived stash accounts
+    #[pallet::storage]
+    #[pallet::getter(fn id_nonce_of)]
+    pub(super) type IdNonceOf<T: Config> = StorageMap<_, Blake2_256, AccountOf<T>, u64, ValueQuery>;
+
     /// Storage version of the pallet
     #[pallet::storage]
     pub(crate) type StorageVersion<T: Config> = StorageValue<_, types::Releases, ValueQuery>;
@@ -175,12 +182,12 @@ pub mod pallet {
 
             let created = <frame_system::Pallet<T>>::block_number();
 
-            // TODO: use a HMAC-based algorithm.
-            let mut raw = <AccountOf<T>>::encode(&magic_account);
-            let mut ord = T::BlockNumber::encode(&created);
-            raw.append(&mut ord);
+            let nonce = Self::id_nonce_of(&magic_account);
+            <IdNonceOf<T>>::insert(&magic_account, nonce.saturating_add(1));
+
+            let id = derive::id::<T::Hashing, _>(&T::PalletId::get().0, &magic_account, nonce);
 
-            let stash_account = T::PalletId::get().into_sub_account(raw);
+            let stash_account = T::PalletId::get().into_sub_account(&id);
 
             let sa = types::StableAccount {
                 stash_account,
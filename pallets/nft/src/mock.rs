@@ -5,6 +5,7 @@ use sp_core::{sr25519, H160, H256};
 use sp_runtime::{
     testing::Header,
     traits::{BlakeTwo256, Keccak256},
+    Permill,
 };
 
 type UncheckedExtrinsic = system::mocking::MockUncheckedExtrinsic<Test>;
@@ -150,7 +151,10 @@ impl parami_did::Config for Test {
 }
 
 parameter_types! {
+    pub const SwapDefaultFee: Permill = Permill::from_parts(3_000);
+    pub const SwapMaxPathLen: u32 = 8;
     pub const SwapPalletId: PalletId = PalletId(*b"prm/swap");
+    pub const SwapProtocolFeeShare: Permill = Permill::zero();
 }
 
 impl parami_swap::Config for Test {
@@ -158,8 +162,13 @@ impl parami_swap::Config for Test {
     type AssetId = AssetId;
     type Assets = Assets;
     type Currency = Balances;
+    type DefaultFee = SwapDefaultFee;
     type FarmingCurve = ();
+    type ForceOrigin = EnsureRoot<Self::AccountId>;
+    type MaxPathLen = SwapMaxPathLen;
     type PalletId = SwapPalletId;
+    type ProtocolFee = ();
+    type ProtocolFeeShare = SwapProtocolFeeShare;
     type WeightInfo = ();
 }
 
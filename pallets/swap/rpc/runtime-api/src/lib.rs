@@ -6,6 +6,7 @@ use sp_runtime::{
     traits::{MaybeDisplay, MaybeFromStr},
     DispatchError,
 };
+use sp_std::vec::Vec;
 
 type ApiResult<T> = Result<T, DispatchError>;
 
@@ -58,6 +59,51 @@ sp_api::decl_runtime_apis! {
             BalanceWrapper<Balance>,
         )>;
 
+        /// Get dry-run result of add_liquidity_to
+        ///
+        /// # Arguments
+        ///
+        /// * `lp_token_id` - The Liquidity Provider Token ID
+        /// * `currency` - The currency to be involved in the swap
+        /// * `max_tokens` - The maximum amount of tokens to be involved in the swap
+        ///
+        /// # Results
+        ///
+        /// tuple of (tokens, liquidity)
+        ///
+        /// * `tokens` - The amount of tokens to be involved in the swap
+        /// * `liquidity` - The amount of liquidity to be added
+        fn dryly_add_liquidity_to(
+            lp_token_id: AssetId,
+            currency: BalanceWrapper<Balance>,
+            max_tokens: BalanceWrapper<Balance>,
+        ) -> ApiResult<(BalanceWrapper<Balance>, BalanceWrapper<Balance>)>;
+
+        /// Get dry-run result of remove_liquidity_partial
+        ///
+        /// # Arguments
+        ///
+        /// * `lp_token_id` - The Liquidity Provider Token ID
+        /// * `liquidity` - The amount of liquidity to be removed
+        ///
+        /// # Results
+        ///
+        /// tuple of (token_id, liquidity, tokens, currency)
+        ///
+        /// * `token_id` - The Asset ID
+        /// * `liquidity` - The amount of liquidity removed
+        /// * `tokens` - The amount of tokens to be returned
+        /// * `currency` - The currency to be returned
+        fn dryly_remove_liquidity_partial(
+            lp_token_id: AssetId,
+            liquidity: BalanceWrapper<Balance>,
+        ) -> ApiResult<(
+            AssetId,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+        )>;
+
         /// Get dry-run result of buy_tokens
         ///
         /// # Arguments
@@ -118,6 +164,36 @@ sp_api::decl_runtime_apis! {
             currency: BalanceWrapper<Balance>,
         ) -> ApiResult<BalanceWrapper<Balance>>;
 
+        /// Get dry-run result of swap_exact_tokens_for_tokens
+        ///
+        /// # Arguments
+        ///
+        /// * `path` - The Asset IDs to swap through, starting with the one to be sold
+        /// * `amount_in` - The amount of tokens to be sold
+        ///
+        /// # Results
+        ///
+        /// The amount of tokens to be gained
+        fn dryly_swap_tokens(
+            path: Vec<AssetId>,
+            amount_in: BalanceWrapper<Balance>,
+        ) -> ApiResult<BalanceWrapper<Balance>>;
+
+        /// Get dry-run result of swap_tokens_for_exact_tokens
+        ///
+        /// # Arguments
+        ///
+        /// * `path` - The Asset IDs to swap through, starting with the one to be sold
+        /// * `amount_out` - The amount of tokens to be bought
+        ///
+        /// # Results
+        ///
+        /// The amount of tokens needed
+        fn dryly_swap_tokens_for_exact(
+            path: Vec<AssetId>,
+            amount_out: BalanceWrapper<Balance>,
+        ) -> ApiResult<BalanceWrapper<Balance>>;
+
         /// Calculate staking reward
         ///
         /// # Arguments
@@ -66,6 +66,57 @@ where
         BalanceWrapper<Balance>,
     )>;
 
+    /// Get dry-run result of add_liquidity_to
+    ///
+    /// # Arguments
+    ///
+    /// * `lp_token_id` - The Liquidity Provider Token ID
+    /// * `currency` - The currency to be involved in the swap
+    /// * `max_tokens` - The maximum amount of tokens to be involved in the swap
+    ///
+    /// # Results
+    ///
+    /// tuple of (tokens, liquidity)
+    ///
+    /// * `tokens` - The amount of tokens to be involved in the swap
+    /// * `liquidity` - The amount of liquidity to be added
+    #[rpc(name = "swap_drylyAddLiquidityTo")]
+    fn dryly_add_liquidity_to(
+        &self,
+        lp_token_id: AssetId,
+        currency: BalanceWrapper<Balance>,
+        max_tokens: BalanceWrapper<Balance>,
+        at: Option<BlockHash>,
+    ) -> Result<(BalanceWrapper<Balance>, BalanceWrapper<Balance>)>;
+
+    /// Get dry-run result of remove_liquidity_partial
+    ///
+    /// # Arguments
+    ///
+    /// * `lp_token_id` - The Liquidity Provider Token ID
+    /// * `liquidity` - The amount of liquidity to be removed
+    ///
+    /// # Results
+    ///
+    /// tuple of (token_id, liquidity, tokens, currency)
+    ///
+    /// * `token_id` - The Asset ID
+    /// * `liquidity` - The amount of liquidity removed
+    /// * `tokens` - The amount of tokens to be returned
+    /// * `currency` - The currency to be returned
+    #[rpc(name = "swap_drylyRemoveLiquidityPartial")]
+    fn dryly_remove_liquidity_partial(
+        &self,
+        lp_token_id: AssetId,
+        liquidity: BalanceWrapper<Balance>,
+        at: Option<BlockHash>,
+    ) -> Result<(
+        AssetId,
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+    )>;
+
     /// Get dry-run result of buy_tokens
     ///
     /// # Arguments
@@ -138,6 +189,42 @@ where
         at: Option<BlockHash>,
     ) -> Result<BalanceWrapper<Balance>>;
 
+    /// Get dry-run result of swap_exact_tokens_for_tokens
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The Asset IDs to swap through, starting with the one to be sold
+    /// * `amount_in` - The amount of tokens to be sold
+    ///
+    /// # Results
+    ///
+    /// The amount of tokens to be gained
+    #[rpc(name = "swap_drylySwapTokens")]
+    fn dryly_swap_tokens(
+        &self,
+        path: Vec<AssetId>,
+        amount_in: BalanceWrapper<Balance>,
+        at: Option<BlockHash>,
+    ) -> Result<BalanceWrapper<Balance>>;
+
+    /// Get dry-run result of swap_tokens_for_exact_tokens
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The Asset IDs to swap through, starting with the one to be sold
+    /// * `amount_out` - The amount of tokens to be bought
+    ///
+    /// # Results
+    ///
+    /// The amount of tokens needed
+    #[rpc(name = "swap_drylySwapTokensForExact")]
+    fn dryly_swap_tokens_for_exact(
+        &self,
+        path: Vec<AssetId>,
+        amount_out: BalanceWrapper<Balance>,
+        at: Option<BlockHash>,
+    ) -> Result<BalanceWrapper<Balance>>;
+
     /// Calculate staking reward
     ///
     /// # Arguments
@@ -231,6 +318,60 @@ where
         })
     }
 
+    fn dryly_add_liquidity_to(
+        &self,
+        lp_token_id: AssetId,
+        currency: BalanceWrapper<Balance>,
+        max_tokens: BalanceWrapper<Balance>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<(BalanceWrapper<Balance>, BalanceWrapper<Balance>)> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api
+            .dryly_add_liquidity_to(&at, lp_token_id, currency, max_tokens)
+            .map_err(|e| RpcError {
+                code: ErrorCode::InternalError,
+                message: "Unable to dry-run mint_to.".into(),
+                data: Some(format!("{:?}", e).into()),
+            })?;
+
+        res.map_err(|e| RpcError {
+            code: ErrorCode::ServerError(1),
+            message: "Unable to dry-run mint_to.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+
+    fn dryly_remove_liquidity_partial(
+        &self,
+        lp_token_id: AssetId,
+        liquidity: BalanceWrapper<Balance>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<(
+        AssetId,
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+    )> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api
+            .dryly_remove_liquidity_partial(&at, lp_token_id, liquidity)
+            .map_err(|e| RpcError {
+                code: ErrorCode::InternalError,
+                message: "Unable to dry-run burn_partial.".into(),
+                data: Some(format!("{:?}", e).into()),
+            })?;
+
+        res.map_err(|e| RpcError {
+            code: ErrorCode::ServerError(1),
+            message: "Unable to dry-run burn_partial.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+
     fn dryly_buy_tokens(
         &self,
         token_id: AssetId,
@@ -327,6 +468,54 @@ where
         })
     }
 
+    fn dryly_swap_tokens(
+        &self,
+        path: Vec<AssetId>,
+        amount_in: BalanceWrapper<Balance>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<BalanceWrapper<Balance>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api
+            .dryly_swap_tokens(&at, path, amount_in)
+            .map_err(|e| RpcError {
+                code: ErrorCode::InternalError,
+                message: "Unable to dry-run swap_in.".into(),
+                data: Some(format!("{:?}", e).into()),
+            })?;
+
+        res.map_err(|e| RpcError {
+            code: ErrorCode::ServerError(1),
+            message: "Unable to dry-run swap_in.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+
+    fn dryly_swap_tokens_for_exact(
+        &self,
+        path: Vec<AssetId>,
+        amount_out: BalanceWrapper<Balance>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<BalanceWrapper<Balance>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api
+            .dryly_swap_tokens_for_exact(&at, path, amount_out)
+            .map_err(|e| RpcError {
+                code: ErrorCode::InternalError,
+                message: "Unable to dry-run swap_out.".into(),
+                data: Some(format!("{:?}", e).into()),
+            })?;
+
+        res.map_err(|e| RpcError {
+            code: ErrorCode::ServerError(1),
+            message: "Unable to dry-run swap_out.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+
     fn calculate_reward(
         &self,
         lp_token_id: AssetId,
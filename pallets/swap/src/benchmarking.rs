@@ -2,9 +2,9 @@ use super::*;
 
 #[allow(unused)]
 use crate::Pallet as Swap;
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
 use frame_system::RawOrigin;
-use sp_runtime::traits::Saturating;
+use sp_runtime::traits::{One, Saturating};
 
 benchmarks! {
     create {
@@ -205,6 +205,215 @@ benchmarks! {
     verify {
         assert_eq!(T::Currency::free_balance(&caller), pot.saturating_mul(4u32.into()));
     }
+
+    transfer_liquidity {
+        let caller: T::AccountId = whitelisted_caller();
+        let receiver: T::AccountId = account("receiver", 0, 0);
+
+        let min = T::Currency::minimum_balance();
+
+        let pot = min.saturating_mul(1_000_000u32.into());
+
+        let id = T::AssetId::min_value();
+        let deadline = HeightOf::<T>::max_value();
+
+        T::Assets::create(id, caller.clone(), true, min)?;
+
+        T::Currency::make_free_balance_be(&caller, pot.saturating_mul(5u32.into()));
+        T::Assets::mint_into(id, &caller, pot.saturating_mul(5u32.into()))?;
+
+        Swap::<T>::create(RawOrigin::Signed(caller.clone()).into(), id)?;
+
+        Swap::<T>::add_liquidity(
+            RawOrigin::Signed(caller.clone()).into(),
+            id,
+            pot,
+            min,
+            pot.saturating_mul(2u32.into()),
+            deadline,
+        )?;
+
+        let lp_token_id = T::AssetId::min_value();
+    }: _(RawOrigin::Signed(caller.clone()), lp_token_id, receiver.clone())
+    verify {
+        let liquidity = <Liquidity<T>>::get(lp_token_id).unwrap();
+        assert_eq!(liquidity.owner, receiver);
+        assert_eq!(<Provider<T>>::get(id, &receiver), liquidity.amount);
+    }
+
+    add_liquidity_to {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let max = BalanceOf::<T>::max_value();
+        let min = T::Currency::minimum_balance();
+
+        let pot = min.saturating_mul(1_000_000u32.into());
+
+        let id = T::AssetId::min_value();
+        let deadline = HeightOf::<T>::max_value();
+
+        T::Assets::create(id, caller.clone(), true, min)?;
+
+        T::Currency::make_free_balance_be(&caller, pot.saturating_mul(5u32.into()));
+        T::Assets::mint_into(id, &caller, pot.saturating_mul(5u32.into()))?;
+
+        Swap::<T>::create(RawOrigin::Signed(caller.clone()).into(), id)?;
+
+        Swap::<T>::add_liquidity(
+            RawOrigin::Signed(caller.clone()).into(),
+            id,
+            pot,
+            min,
+            pot.saturating_mul(2u32.into()),
+            deadline,
+        )?;
+
+        let lp_token_id = T::AssetId::min_value();
+    }: _(RawOrigin::Signed(caller.clone()), lp_token_id, pot, min, max, deadline)
+    verify {
+        let meta = <Metadata<T>>::get(id).unwrap();
+        assert_eq!(<Liquidity<T>>::get(lp_token_id).unwrap().amount, meta.liquidity);
+        assert_eq!(<NextTokenId<T>>::get(), One::one());
+    }
+
+    remove_liquidity_partial {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let min = T::Currency::minimum_balance();
+
+        let pot = min.saturating_mul(1_000_000u32.into());
+
+        let id = T::AssetId::min_value();
+        let deadline = HeightOf::<T>::max_value();
+
+        T::Assets::create(id, caller.clone(), true, min)?;
+
+        T::Currency::make_free_balance_be(&caller, pot.saturating_mul(5u32.into()));
+        T::Assets::mint_into(id, &caller, pot.saturating_mul(5u32.into()))?;
+
+        Swap::<T>::create(RawOrigin::Signed(caller.clone()).into(), id)?;
+
+        Swap::<T>::add_liquidity(
+            RawOrigin::Signed(caller.clone()).into(),
+            id,
+            pot,
+            min,
+            pot.saturating_mul(2u32.into()),
+            deadline,
+        )?;
+
+        let lp_token_id = T::AssetId::min_value();
+
+        let meta = <Metadata<T>>::get(id).unwrap();
+        let liquidity = meta.liquidity / 2u32.into();
+    }: _(RawOrigin::Signed(caller.clone()), lp_token_id, liquidity, min, min, deadline)
+    verify {
+        let meta = <Metadata<T>>::get(id).unwrap();
+        assert_eq!(<Liquidity<T>>::get(lp_token_id).unwrap().amount, meta.liquidity);
+        assert_eq!(<Provider<T>>::get(id, &caller), meta.liquidity);
+    }
+
+    set_fee {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let min = T::Currency::minimum_balance();
+
+        let id = T::AssetId::min_value();
+
+        T::Assets::create(id, caller.clone(), true, min)?;
+
+        Swap::<T>::create(RawOrigin::Signed(caller).into(), id)?;
+
+        let fee = Permill::from_percent(1);
+    }: _(RawOrigin::Root, id, fee)
+    verify {
+        let meta = <Metadata<T>>::get(id).unwrap();
+        assert_eq!(meta.fee, fee);
+    }
+
+    swap_exact_tokens_for_tokens {
+        let n in 2 .. 8;
+
+        let caller: T::AccountId = whitelisted_caller();
+
+        let min = T::Currency::minimum_balance();
+
+        let pot = min.saturating_mul(1_000_000u32.into());
+
+        let deadline = HeightOf::<T>::max_value();
+
+        T::Currency::make_free_balance_be(&caller, pot.saturating_mul((n + 1).into()));
+
+        let mut path = Vec::new();
+
+        for i in 0..n {
+            let id: AssetOf<T> = i.into();
+
+            T::Assets::create(id, caller.clone(), true, min)?;
+            T::Assets::mint_into(id, &caller, pot.saturating_mul(5u32.into()))?;
+
+            Swap::<T>::create(RawOrigin::Signed(caller.clone()).into(), id)?;
+
+            Swap::<T>::add_liquidity(
+                RawOrigin::Signed(caller.clone()).into(),
+                id,
+                pot,
+                min,
+                pot.saturating_mul(2u32.into()),
+                deadline,
+            )?;
+
+            path.push(id);
+        }
+
+        let last = path[path.len() - 1];
+        let amount = pot / 100u32.into();
+    }: _(RawOrigin::Signed(caller.clone()), path, amount, One::one(), deadline)
+    verify {
+        assert!(T::Assets::balance(last, &caller) > pot.saturating_mul(3u32.into()));
+    }
+
+    swap_tokens_for_exact_tokens {
+        let n in 2 .. 8;
+
+        let caller: T::AccountId = whitelisted_caller();
+
+        let min = T::Currency::minimum_balance();
+
+        let pot = min.saturating_mul(1_000_000u32.into());
+
+        let deadline = HeightOf::<T>::max_value();
+
+        T::Currency::make_free_balance_be(&caller, pot.saturating_mul((n + 1).into()));
+
+        let mut path = Vec::new();
+
+        for i in 0..n {
+            let id: AssetOf<T> = i.into();
+
+            T::Assets::create(id, caller.clone(), true, min)?;
+            T::Assets::mint_into(id, &caller, pot.saturating_mul(5u32.into()))?;
+
+            Swap::<T>::create(RawOrigin::Signed(caller.clone()).into(), id)?;
+
+            Swap::<T>::add_liquidity(
+                RawOrigin::Signed(caller.clone()).into(),
+                id,
+                pot,
+                min,
+                pot.saturating_mul(2u32.into()),
+                deadline,
+            )?;
+
+            path.push(id);
+        }
+
+        let last = path[path.len() - 1];
+        let amount = pot / 100u32.into();
+    }: _(RawOrigin::Signed(caller.clone()), path, amount, pot, deadline)
+    verify {
+        assert_eq!(T::Assets::balance(last, &caller), pot.saturating_mul(3u32.into()) + amount);
+    }
 }
 
 impl_benchmark_test_suite!(Swap, crate::mock::new_test_ext(), crate::mock::Test);
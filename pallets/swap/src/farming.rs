@@ -2,7 +2,13 @@ use crate::{
     Account, AssetOf, BalanceOf, Config, Error, HeightOf, Liquidity, LiquidityOf, Metadata, Pallet,
 };
 
-use frame_support::traits::{tokens::fungibles::Inspect, Currency, Get};
+use frame_support::{
+    dispatch::DispatchResult,
+    traits::{
+        tokens::fungibles::{Inspect, Mutate},
+        Currency, Get,
+    },
+};
 use sp_core::U512;
 use sp_runtime::{
     traits::{Saturating, Zero},
@@ -139,4 +145,19 @@ impl<T: Config> Pallet<T> {
 
         Ok((liquidity, reward))
     }
+
+    /// Pay out the reward accrued by a liquidity,
+    /// and restart accruing from current height
+    pub(super) fn settle_reward(lp_token_id: AssetOf<T>) -> DispatchResult {
+        let (liquidity, reward) = Self::calculate_reward(lp_token_id)?;
+
+        if !reward.is_zero() {
+            T::Assets::mint_into(liquidity.token_id, &liquidity.owner, reward)?;
+        }
+
+        let claimed = <frame_system::Pallet<T>>::block_number();
+        <Account<T>>::insert(&liquidity.owner, lp_token_id, claimed);
+
+        Ok(())
+    }
 }
@@ -1,12 +1,18 @@
-use crate::{AssetOf, BalanceOf, Config, Error, Metadata, Pallet, SwapOf};
+use crate::{AccountOf, AssetOf, BalanceOf, Config, Error, Metadata, Pallet, SwapOf};
 
 use frame_support::{
+    dispatch::DispatchResult,
     ensure,
-    traits::{tokens::fungibles::Inspect as FungInspect, Currency},
+    traits::{
+        tokens::fungibles::{Inspect as FungInspect, Transfer as FungTransfer},
+        Currency,
+        ExistenceRequirement::{AllowDeath, KeepAlive},
+        Get, OnUnbalanced, WithdrawReasons,
+    },
 };
 use parami_traits::Swaps;
 use sp_core::U512;
-use sp_runtime::{traits::Zero, DispatchError};
+use sp_runtime::{traits::Zero, DispatchError, PerThing, Permill};
 
 impl<T: Config> Pallet<T> {
     pub(super) fn try_into<S, D>(value: S) -> Result<D, DispatchError>
@@ -55,6 +61,54 @@ impl<T: Config> Pallet<T> {
         Ok((tokens, liquidity, meta))
     }
 
+    /// Transfer currency and tokens into the pool
+    /// and calculate how many the liquidity should be minted
+    pub(super) fn deposit(
+        who: &AccountOf<T>,
+        token_id: AssetOf<T>,
+        currency: BalanceOf<T>,
+        min_liquidity: BalanceOf<T>,
+        max_tokens: BalanceOf<T>,
+        keep_alive: bool,
+    ) -> Result<(BalanceOf<T>, BalanceOf<T>, SwapOf<T>), DispatchError> {
+        ensure!(currency > Zero::zero(), Error::<T>::ZeroCurrency);
+        ensure!(min_liquidity > Zero::zero(), Error::<T>::ZeroLiquidity);
+        ensure!(max_tokens > Zero::zero(), Error::<T>::ZeroTokens);
+
+        let (tokens, liquidity, meta) = Self::calculate_liquidity(token_id, currency, max_tokens)?;
+
+        ensure!(max_tokens >= tokens, Error::<T>::TooExpensiveCurrency);
+        ensure!(liquidity >= min_liquidity, Error::<T>::TooLowLiquidity);
+
+        if keep_alive {
+            ensure!(
+                T::Currency::free_balance(who) - T::Currency::minimum_balance() >= currency,
+                Error::<T>::InsufficientCurrency
+            );
+        } else {
+            ensure!(
+                T::Currency::free_balance(who) >= currency,
+                Error::<T>::InsufficientCurrency
+            );
+        }
+        ensure!(
+            T::Assets::balance(token_id, who) >= tokens,
+            Error::<T>::InsufficientTokens
+        );
+
+        let pot = Self::get_pool_account(token_id);
+
+        T::Currency::transfer(
+            &who,
+            &pot,
+            currency,
+            if keep_alive { KeepAlive } else { AllowDeath },
+        )?;
+        T::Assets::transfer(token_id, who, &pot, tokens, false)?;
+
+        Ok((tokens, liquidity, meta))
+    }
+
     /// Calculate how many tokens and currency should be returned
     pub(super) fn calculate_solidness(
         token_id: AssetOf<T>,
@@ -90,20 +144,25 @@ impl<T: Config> Pallet<T> {
         output_amount: U512,
         input_reserve: U512,
         output_reserve: U512,
+        fee: Permill,
     ) -> U512 {
         let ten_percent = output_reserve / 10;
 
         if output_amount > ten_percent {
-            let d = Self::calculate_price_buy(ten_percent, input_reserve, output_reserve);
+            let d = Self::calculate_price_buy(ten_percent, input_reserve, output_reserve, fee);
 
             d + Self::calculate_price_buy(
                 output_amount - ten_percent,
                 input_reserve + d,
                 output_reserve - ten_percent,
+                fee,
             )
         } else {
-            let numerator = input_reserve * output_amount * U512::from(1000);
-            let denominator = (output_reserve - output_amount) * U512::from(997);
+            let accuracy = U512::from(Permill::ACCURACY);
+            let remains = U512::from((Permill::one() - fee).deconstruct());
+
+            let numerator = input_reserve * output_amount * accuracy;
+            let denominator = (output_reserve - output_amount) * remains;
             let result = numerator / denominator + U512::from(1);
 
             result
@@ -115,21 +174,26 @@ impl<T: Config> Pallet<T> {
         input_amount: U512,
         input_reserve: U512,
         output_reserve: U512,
+        fee: Permill,
     ) -> U512 {
         let ten_percent = input_reserve / 10;
 
         if input_amount > ten_percent {
-            let d = Self::calculate_price_sell(ten_percent, input_reserve, output_reserve);
+            let d = Self::calculate_price_sell(ten_percent, input_reserve, output_reserve, fee);
 
             d + Self::calculate_price_sell(
                 input_amount - ten_percent,
                 input_reserve + ten_percent,
                 output_reserve - d,
+                fee,
             )
         } else {
-            let input_amount_with_fee = input_amount * U512::from(997);
+            let accuracy = U512::from(Permill::ACCURACY);
+            let remains = U512::from((Permill::one() - fee).deconstruct());
+
+            let input_amount_with_fee = input_amount * remains;
             let numerator = input_amount_with_fee * output_reserve;
-            let denominator = (input_reserve * U512::from(1000)) + input_amount_with_fee;
+            let denominator = (input_reserve * accuracy) + input_amount_with_fee;
             let result = numerator / denominator;
 
             result
@@ -141,6 +205,7 @@ impl<T: Config> Pallet<T> {
         output_amount: BalanceOf<T>,
         input_reserve: BalanceOf<T>,
         output_reserve: BalanceOf<T>,
+        fee: Permill,
     ) -> Result<BalanceOf<T>, DispatchError> {
         ensure!(
             output_reserve > output_amount,
@@ -151,7 +216,7 @@ impl<T: Config> Pallet<T> {
         let input_reserve = Self::try_into(input_reserve)?;
         let output_reserve = Self::try_into(output_reserve)?;
 
-        let result = Self::calculate_price_buy(output_amount, input_reserve, output_reserve);
+        let result = Self::calculate_price_buy(output_amount, input_reserve, output_reserve, fee);
 
         let result = Self::try_into(result)?;
 
@@ -163,12 +228,13 @@ impl<T: Config> Pallet<T> {
         input_amount: BalanceOf<T>,
         input_reserve: BalanceOf<T>,
         output_reserve: BalanceOf<T>,
+        fee: Permill,
     ) -> Result<BalanceOf<T>, DispatchError> {
         let input_amount = Self::try_into(input_amount)?;
         let input_reserve = Self::try_into(input_reserve)?;
         let output_reserve = Self::try_into(output_reserve)?;
 
-        let result = Self::calculate_price_sell(input_amount, input_reserve, output_reserve);
+        let result = Self::calculate_price_sell(input_amount, input_reserve, output_reserve, fee);
 
         ensure!(output_reserve > result, Error::<T>::InsufficientLiquidity);
 
@@ -176,4 +242,26 @@ impl<T: Config> Pallet<T> {
 
         Ok(result)
     }
+
+    /// Take the protocol share of a swap fee from the pool,
+    /// valued on the currency side of the swap
+    pub(super) fn charge_protocol_fee(
+        token_id: AssetOf<T>,
+        fee: Permill,
+        currency: BalanceOf<T>,
+    ) -> DispatchResult {
+        let cut = T::ProtocolFeeShare::get().mul_floor(fee.mul_floor(currency));
+
+        if cut.is_zero() {
+            return Ok(());
+        }
+
+        let pot = Self::get_pool_account(token_id);
+
+        let imbalance = T::Currency::withdraw(&pot, cut, WithdrawReasons::FEE, AllowDeath)?;
+
+        T::ProtocolFee::on_unbalanced(imbalance);
+
+        Ok(())
+    }
 }
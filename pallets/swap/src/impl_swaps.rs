@@ -52,6 +52,7 @@ impl<T: Config> Swaps for Pallet<T> {
             types::Swap {
                 created,
                 liquidity: Zero::zero(),
+                fee: T::DefaultFee::get(),
             },
         );
 
@@ -82,41 +83,14 @@ impl<T: Config> Swaps for Pallet<T> {
         max_tokens: Self::TokenBalance,
         keep_alive: bool,
     ) -> DispatchResult<(Self::TokenBalance, Self::TokenBalance)> {
-        ensure!(currency > Zero::zero(), Error::<T>::ZeroCurrency);
-        ensure!(min_liquidity > Zero::zero(), Error::<T>::ZeroLiquidity);
-        ensure!(max_tokens > Zero::zero(), Error::<T>::ZeroTokens);
-
-        let (tokens, liquidity, mut meta) =
-            Self::calculate_liquidity(token_id, currency, max_tokens)?;
-
-        ensure!(max_tokens >= tokens, Error::<T>::TooExpensiveCurrency);
-        ensure!(liquidity >= min_liquidity, Error::<T>::TooLowLiquidity);
-
-        if keep_alive {
-            ensure!(
-                T::Currency::free_balance(&who) - T::Currency::minimum_balance() >= currency,
-                Error::<T>::InsufficientCurrency
-            );
-        } else {
-            ensure!(
-                T::Currency::free_balance(&who) >= currency,
-                Error::<T>::InsufficientCurrency
-            );
-        }
-        ensure!(
-            T::Assets::balance(token_id, &who) >= tokens,
-            Error::<T>::InsufficientTokens
-        );
-
-        let pot = Self::get_pool_account(token_id);
-
-        T::Currency::transfer(
+        let (tokens, liquidity, mut meta) = Self::deposit(
             &who,
-            &pot,
+            token_id,
             currency,
-            if keep_alive { KeepAlive } else { AllowDeath },
+            min_liquidity,
+            max_tokens,
+            keep_alive,
         )?;
-        T::Assets::transfer(token_id, &who, &pot, tokens, false)?;
 
         let minted = <frame_system::Pallet<T>>::block_number();
 
@@ -150,6 +124,58 @@ impl<T: Config> Swaps for Pallet<T> {
         Ok((tokens, liquidity))
     }
 
+    fn mint_to_dry(
+        lp_token_id: Self::AssetId,
+        currency: Self::QuoteBalance,
+        max_tokens: Self::TokenBalance,
+    ) -> DispatchResult<(Self::TokenBalance, Self::TokenBalance)> {
+        let liquidity = <Liquidity<T>>::get(lp_token_id).ok_or(Error::<T>::NotExists)?;
+
+        Self::mint_dry(liquidity.token_id, currency, max_tokens)
+    }
+
+    fn mint_to(
+        who: Self::AccountId,
+        lp_token_id: Self::AssetId,
+        currency: Self::QuoteBalance,
+        min_liquidity: Self::TokenBalance,
+        max_tokens: Self::TokenBalance,
+        keep_alive: bool,
+    ) -> DispatchResult<(Self::TokenBalance, Self::TokenBalance)> {
+        let mut position = <Liquidity<T>>::get(lp_token_id).ok_or(Error::<T>::NotExists)?;
+        ensure!(position.owner == who, Error::<T>::NotExists);
+
+        let token_id = position.token_id;
+
+        let (tokens, liquidity, mut meta) = Self::deposit(
+            &who,
+            token_id,
+            currency,
+            min_liquidity,
+            max_tokens,
+            keep_alive,
+        )?;
+
+        // settle the reward accrued by the position before its amount changes
+        Self::settle_reward(lp_token_id)?;
+
+        position.amount.saturating_accrue(liquidity);
+        <Liquidity<T>>::insert(lp_token_id, position);
+
+        <Provider<T>>::mutate(token_id, &who, |holding| {
+            holding.saturating_accrue(liquidity);
+        });
+
+        meta.liquidity.saturating_accrue(liquidity);
+        <Metadata<T>>::insert(token_id, meta);
+
+        Self::deposit_event(Event::LiquidityAdded(
+            token_id, who, liquidity, currency, tokens,
+        ));
+
+        Ok((tokens, liquidity))
+    }
+
     fn burn_dry(
         lp_token_id: Self::AssetId,
     ) -> DispatchResult<(
@@ -216,16 +242,104 @@ impl<T: Config> Swaps for Pallet<T> {
         Ok((liquidity.token_id, liquidity.amount, tokens, currency))
     }
 
+    fn burn_partial_dry(
+        lp_token_id: Self::AssetId,
+        liquidity: Self::TokenBalance,
+    ) -> DispatchResult<(
+        Self::AssetId,
+        Self::TokenBalance,
+        Self::TokenBalance,
+        Self::QuoteBalance,
+    )> {
+        let position = <Liquidity<T>>::get(lp_token_id).ok_or(Error::<T>::NotExists)?;
+
+        ensure!(
+            position.amount >= liquidity,
+            Error::<T>::InsufficientLiquidity
+        );
+
+        let (tokens, currency, _) = Self::calculate_solidness(position.token_id, liquidity)?;
+
+        Ok((position.token_id, liquidity, tokens, currency))
+    }
+
+    fn burn_partial(
+        who: Self::AccountId,
+        lp_token_id: Self::AssetId,
+        liquidity: Self::TokenBalance,
+        min_currency: Self::QuoteBalance,
+        min_tokens: Self::TokenBalance,
+    ) -> DispatchResult<(
+        Self::AssetId,
+        Self::TokenBalance,
+        Self::TokenBalance,
+        Self::QuoteBalance,
+    )> {
+        ensure!(liquidity > Zero::zero(), Error::<T>::ZeroLiquidity);
+
+        let mut position = <Liquidity<T>>::get(lp_token_id).ok_or(Error::<T>::NotExists)?;
+        ensure!(position.owner == who, Error::<T>::NotExists);
+
+        ensure!(
+            position.amount >= liquidity,
+            Error::<T>::InsufficientLiquidity
+        );
+
+        let token_id = position.token_id;
+
+        let (tokens, currency, mut meta) = Self::calculate_solidness(token_id, liquidity)?;
+
+        ensure!(currency >= min_currency, Error::<T>::TooLowCurrency);
+        ensure!(tokens >= min_tokens, Error::<T>::TooLowTokens);
+
+        // fallible transfers go before any write to the position
+        let pot = Self::get_pool_account(token_id);
+
+        T::Assets::transfer(token_id, &pot, &who, tokens, false)?;
+        T::Currency::transfer(&pot, &who, currency, AllowDeath)?;
+
+        // settle the reward accrued by the position before its amount changes
+        Self::settle_reward(lp_token_id)?;
+
+        position.amount.saturating_reduce(liquidity);
+        if position.amount.is_zero() {
+            <Liquidity<T>>::remove(lp_token_id);
+            <Account<T>>::remove(&who, lp_token_id);
+        } else {
+            <Liquidity<T>>::insert(lp_token_id, position);
+        }
+
+        let mut holding = <Provider<T>>::get(token_id, &who);
+        if holding <= liquidity {
+            <Provider<T>>::remove(token_id, &who);
+        } else {
+            holding.saturating_reduce(liquidity);
+
+            <Provider<T>>::insert(token_id, &who, holding);
+        }
+
+        meta.liquidity.saturating_reduce(liquidity);
+        <Metadata<T>>::insert(token_id, meta);
+
+        Self::deposit_event(Event::LiquidityRemoved(
+            token_id, who, liquidity, currency, tokens,
+        ));
+
+        Ok((token_id, liquidity, tokens, currency))
+    }
+
     fn token_out_dry(
         token_id: Self::AssetId,
         tokens: Self::TokenBalance,
     ) -> DispatchResult<Self::QuoteBalance> {
+        let meta = <Metadata<T>>::get(token_id).ok_or(Error::<T>::NotExists)?;
+
         let pot = Self::get_pool_account(token_id);
 
         let total_quote = T::Currency::free_balance(&pot);
         let total_token = T::Assets::balance(token_id, &pot);
 
-        let currency_sold = Self::price_buy(tokens, total_quote, total_token)?;
+        let currency_sold = Self::price_buy(tokens, total_quote, total_token, meta.fee)?;
 
         Ok(currency_sold)
     }
@@ -240,12 +354,14 @@ impl<T: Config> Swaps for Pallet<T> {
         ensure!(tokens > Zero::zero(), Error::<T>::ZeroTokens);
         ensure!(max_currency > Zero::zero(), Error::<T>::ZeroCurrency);
 
+        let meta = <Metadata<T>>::get(token_id).ok_or(Error::<T>::NotExists)?;
+
         let pot = Self::get_pool_account(token_id);
 
         let total_quote = T::Currency::free_balance(&pot);
         let total_token = T::Assets::balance(token_id, &pot);
 
-        let currency_sold = Self::price_buy(tokens, total_quote, total_token)?;
+        let currency_sold = Self::price_buy(tokens, total_quote, total_token, meta.fee)?;
 
         ensure!(
             currency_sold <= max_currency,
@@ -260,7 +376,15 @@ impl<T: Config> Swaps for Pallet<T> {
         )?;
         T::Assets::transfer(token_id, &pot, &who, tokens, false)?;
 
-        Self::deposit_event(Event::TokenBought(token_id, who, tokens, currency_sold));
+        Self::charge_protocol_fee(token_id, meta.fee, currency_sold)?;
+
+        Self::deposit_event(Event::TokenBought(
+            token_id,
+            who,
+            tokens,
+            currency_sold,
+            meta.fee,
+        ));
 
         Ok(currency_sold)
     }
@@ -269,12 +393,14 @@ impl<T: Config> Swaps for Pallet<T> {
         token_id: Self::AssetId,
         tokens: Self::TokenBalance,
     ) -> DispatchResult<Self::QuoteBalance> {
+        let meta = <Metadata<T>>::get(token_id).ok_or(Error::<T>::NotExists)?;
+
         let pot = Self::get_pool_account(token_id);
 
         let total_quote = T::Currency::free_balance(&pot);
         let total_token = T::Assets::balance(token_id, &pot);
 
-        let currency_bought = Self::price_sell(tokens, total_token, total_quote)?;
+        let currency_bought = Self::price_sell(tokens, total_token, total_quote, meta.fee)?;
 
         Ok(currency_bought)
     }
@@ -289,19 +415,29 @@ impl<T: Config> Swaps for Pallet<T> {
         ensure!(tokens > Zero::zero(), Error::<T>::ZeroTokens);
         ensure!(min_currency > Zero::zero(), Error::<T>::ZeroCurrency);
 
+        let meta = <Metadata<T>>::get(token_id).ok_or(Error::<T>::NotExists)?;
+
         let pot = Self::get_pool_account(token_id);
 
         let total_quote = T::Currency::free_balance(&pot);
         let total_token = T::Assets::balance(token_id, &pot);
 
-        let currency_bought = Self::price_sell(tokens, total_token, total_quote)?;
+        let currency_bought = Self::price_sell(tokens, total_token, total_quote, meta.fee)?;
 
         ensure!(currency_bought >= min_currency, Error::<T>::TooLowCurrency);
 
         T::Assets::transfer(token_id, &who, &pot, tokens, keep_alive)?;
         T::Currency::transfer(&pot, &who, currency_bought, AllowDeath)?;
 
-        Self::deposit_event(Event::TokenSold(token_id, who, tokens, currency_bought));
+        Self::charge_protocol_fee(token_id, meta.fee, currency_bought)?;
+
+        Self::deposit_event(Event::TokenSold(
+            token_id,
+            who,
+            tokens,
+            currency_bought,
+            meta.fee,
+        ));
 
         Ok(currency_bought)
     }
@@ -310,12 +446,14 @@ impl<T: Config> Swaps for Pallet<T> {
         token_id: Self::AssetId,
         currency: Self::QuoteBalance,
     ) -> DispatchResult<Self::TokenBalance> {
+        let meta = <Metadata<T>>::get(token_id).ok_or(Error::<T>::NotExists)?;
+
         let pot = Self::get_pool_account(token_id);
 
         let total_quote = T::Currency::free_balance(&pot);
         let total_token = T::Assets::balance(token_id, &pot);
 
-        let tokens_bought = Self::price_sell(currency, total_quote, total_token)?;
+        let tokens_bought = Self::price_sell(currency, total_quote, total_token, meta.fee)?;
 
         Ok(tokens_bought)
     }
@@ -330,12 +468,14 @@ impl<T: Config> Swaps for Pallet<T> {
         ensure!(currency > Zero::zero(), Error::<T>::ZeroCurrency);
         ensure!(min_tokens > Zero::zero(), Error::<T>::ZeroTokens);
 
+        let meta = <Metadata<T>>::get(token_id).ok_or(Error::<T>::NotExists)?;
+
         let pot = Self::get_pool_account(token_id);
 
         let total_quote = T::Currency::free_balance(&pot);
         let total_token = T::Assets::balance(token_id, &pot);
 
-        let tokens_bought = Self::price_sell(currency, total_quote, total_token)?;
+        let tokens_bought = Self::price_sell(currency, total_quote, total_token, meta.fee)?;
 
         ensure!(tokens_bought >= min_tokens, Error::<T>::TooExpensiveTokens);
 
@@ -347,7 +487,15 @@ impl<T: Config> Swaps for Pallet<T> {
         )?;
         T::Assets::transfer(token_id, &pot, &who, tokens_bought, false)?;
 
-        Self::deposit_event(Event::TokenBought(token_id, who, tokens_bought, currency));
+        Self::charge_protocol_fee(token_id, meta.fee, currency)?;
+
+        Self::deposit_event(Event::TokenBought(
+            token_id,
+            who,
+            tokens_bought,
+            currency,
+            meta.fee,
+        ));
 
         Ok(tokens_bought)
     }
@@ -356,12 +504,14 @@ impl<T: Config> Swaps for Pallet<T> {
         token_id: Self::AssetId,
         currency: Self::QuoteBalance,
     ) -> DispatchResult<Self::TokenBalance> {
+        let meta = <Metadata<T>>::get(token_id).ok_or(Error::<T>::NotExists)?;
+
         let pot = Self::get_pool_account(token_id);
 
         let total_quote = T::Currency::free_balance(&pot);
         let total_token = T::Assets::balance(token_id, &pot);
 
-        let tokens_sold = Self::price_buy(currency, total_token, total_quote)?;
+        let tokens_sold = Self::price_buy(currency, total_token, total_quote, meta.fee)?;
 
         Ok(tokens_sold)
     }
@@ -376,19 +526,29 @@ impl<T: Config> Swaps for Pallet<T> {
         ensure!(max_tokens > Zero::zero(), Error::<T>::ZeroTokens);
         ensure!(currency > Zero::zero(), Error::<T>::ZeroCurrency);
 
+        let meta = <Metadata<T>>::get(token_id).ok_or(Error::<T>::NotExists)?;
+
         let pot = Self::get_pool_account(token_id);
 
         let total_quote = T::Currency::free_balance(&pot);
         let total_token = T::Assets::balance(token_id, &pot);
 
-        let tokens_sold = Self::price_buy(currency, total_token, total_quote)?;
+        let tokens_sold = Self::price_buy(currency, total_token, total_quote, meta.fee)?;
 
         ensure!(max_tokens >= tokens_sold, Error::<T>::TooLowTokens);
 
         T::Assets::transfer(token_id, &who, &pot, tokens_sold, keep_alive)?;
         T::Currency::transfer(&pot, &who, currency, AllowDeath)?;
 
-        Self::deposit_event(Event::TokenSold(token_id, who, tokens_sold, currency));
+        Self::charge_protocol_fee(token_id, meta.fee, currency)?;
+
+        Self::deposit_event(Event::TokenSold(
+            token_id,
+            who,
+            tokens_sold,
+            currency,
+            meta.fee,
+        ));
 
         Ok(tokens_sold)
     }
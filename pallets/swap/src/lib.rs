@@ -18,6 +18,8 @@ mod benchmarking;
 mod farming;
 mod functions;
 mod impl_swaps;
+mod migrations;
+mod routing;
 mod types;
 
 use frame_support::{
@@ -27,12 +29,15 @@ use frame_support::{
         tokens::fungibles::{
             InspectMetadata as FungMeta, Mutate as FungMutate, Transfer as FungTransfer,
         },
-        Currency, Get,
+        Currency, Get, OnUnbalanced,
     },
-    PalletId,
+    transactional, PalletId,
 };
 use parami_traits::Swaps;
-use sp_runtime::traits::{AtLeast32BitUnsigned, Bounded};
+use sp_runtime::{
+    traits::{AtLeast32BitUnsigned, Bounded, Saturating},
+    Permill,
+};
 use sp_std::prelude::*;
 
 use weights::WeightInfo;
@@ -41,6 +46,7 @@ type AssetOf<T> = <T as Config>::AssetId;
 type AccountOf<T> = <T as frame_system::Config>::AccountId;
 type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountOf<T>>>::Balance;
 type HeightOf<T> = <T as frame_system::Config>::BlockNumber;
+type NegativeImbOf<T> = <<T as Config>::Currency as Currency<AccountOf<T>>>::NegativeImbalance;
 type SwapOf<T> = types::Swap<HeightOf<T>, BalanceOf<T>>;
 type LiquidityOf<T> = types::Liquidity<AccountOf<T>, BalanceOf<T>, HeightOf<T>, AssetOf<T>>;
 
@@ -62,7 +68,8 @@ pub mod pallet {
             + AtLeast32BitUnsigned
             + Default
             + Bounded
-            + Copy;
+            + Copy
+            + MaxEncodedLen;
 
         /// The assets trait to create, mint, and transfer fungible tokens
         type Assets: FungMeta<AccountOf<Self>, AssetId = AssetOf<Self>>
@@ -72,19 +79,38 @@ pub mod pallet {
         /// The currency trait
         type Currency: Currency<AccountOf<Self>>;
 
+        /// The swap fee of a newly created pair
+        #[pallet::constant]
+        type DefaultFee: Get<Permill>;
+
         /// The curve for seasoned orffering
         type FarmingCurve: FarmingCurve<Self>;
 
+        /// The origin which may set the swap fee of a pair
+        type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+        /// The maximum number of assets in a swap path
+        #[pallet::constant]
+        type MaxPathLen: Get<u32>;
+
         /// The pallet id, used for deriving liquid accounts
         #[pallet::constant]
         type PalletId: Get<PalletId>;
 
+        /// Handler for the protocol share of swap fees
+        type ProtocolFee: OnUnbalanced<NegativeImbOf<Self>>;
+
+        /// The share of swap fees taken by the protocol, zero to leave all fees in the pool
+        #[pallet::constant]
+        type ProtocolFeeShare: Get<Permill>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::generate_storage_info]
     pub struct Pallet<T>(_);
 
     /// Metadata of a swap
@@ -128,6 +154,10 @@ pub mod pallet {
     #[pallet::storage]
     pub(super) type NextTokenId<T: Config> = StorageValue<_, AssetOf<T>, ValueQuery>;
 
+    /// Storage version of the pallet
+    #[pallet::storage]
+    pub(crate) type StorageVersion<T: Config> = StorageValue<_, types::Releases, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub fn deposit_event)]
     pub enum Event<T: Config> {
@@ -149,14 +179,38 @@ pub mod pallet {
             BalanceOf<T>,
             BalanceOf<T>,
         ),
-        /// Tokens bought \[id, account, tokens, currency\]
-        TokenBought(AssetOf<T>, AccountOf<T>, BalanceOf<T>, BalanceOf<T>),
-        /// Tokens sold \[id, account, tokens, currency\]
-        TokenSold(AssetOf<T>, AccountOf<T>, BalanceOf<T>, BalanceOf<T>),
+        /// Tokens bought \[id, account, tokens, currency, fee\]
+        TokenBought(
+            AssetOf<T>,
+            AccountOf<T>,
+            BalanceOf<T>,
+            BalanceOf<T>,
+            Permill,
+        ),
+        /// Tokens sold \[id, account, tokens, currency, fee\]
+        TokenSold(
+            AssetOf<T>,
+            AccountOf<T>,
+            BalanceOf<T>,
+            BalanceOf<T>,
+            Permill,
+        ),
+        /// Liquidity transferred \[lp_token_id, from, to\]
+        LiquidityTransferred(AssetOf<T>, AccountOf<T>, AccountOf<T>),
+        /// Swap fee changed \[id, fee\]
+        FeeChanged(AssetOf<T>, Permill),
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if <StorageVersion<T>>::get() == types::Releases::V1 {
+                return 0;
+            }
+
+            migrations::migrate::<T>()
+        }
+    }
 
     #[pallet::error]
     pub enum Error<T> {
@@ -165,11 +219,13 @@ pub mod pallet {
         InsufficientCurrency,
         InsufficientLiquidity,
         InsufficientTokens,
+        InvalidPath,
         NoLiquidity,
         NotExists,
         Overflow,
         TooExpensiveCurrency,
         TooExpensiveTokens,
+        TooHighFee,
         TooLowCurrency,
         TooLowLiquidity,
         TooLowTokens,
@@ -373,6 +429,184 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Transfer Liquidity
+        ///
+        /// * `lp_token_id` - The Liquidity Provider Token ID
+        /// * `to` - The account to receive the liquidity
+        #[pallet::weight(T::WeightInfo::transfer_liquidity())]
+        pub fn transfer_liquidity(
+            origin: OriginFor<T>,
+            #[pallet::compact] lp_token_id: AssetOf<T>,
+            to: AccountOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut liquidity = <Liquidity<T>>::get(lp_token_id).ok_or(Error::<T>::NotExists)?;
+            ensure!(liquidity.owner == who, Error::<T>::NotExists);
+
+            // keep the last claimed height, so that farming accrues as before
+            let claimed = <Account<T>>::take(&who, lp_token_id).unwrap_or_default();
+            <Account<T>>::insert(&to, lp_token_id, claimed);
+
+            let mut holding = <Provider<T>>::get(liquidity.token_id, &who);
+            if holding <= liquidity.amount {
+                <Provider<T>>::remove(liquidity.token_id, &who);
+            } else {
+                holding.saturating_reduce(liquidity.amount);
+
+                <Provider<T>>::insert(liquidity.token_id, &who, holding);
+            }
+
+            <Provider<T>>::mutate(liquidity.token_id, &to, |holding| {
+                holding.saturating_accrue(liquidity.amount);
+            });
+
+            liquidity.owner = to.clone();
+            <Liquidity<T>>::insert(lp_token_id, liquidity);
+
+            Self::deposit_event(Event::LiquidityTransferred(lp_token_id, who, to));
+
+            Ok(())
+        }
+
+        /// Add Liquidity to an existing Liquidity Provider Token
+        ///
+        /// # Arguments
+        ///
+        /// * `lp_token_id` - The Liquidity Provider Token ID
+        /// * `currency` - The currency to be involved in the swap
+        /// * `min_liquidity` - The minimum amount of liquidity to be added
+        /// * `max_tokens` - The maximum amount of tokens to be involved in the swap
+        /// * `deadline` - The block number at which the swap should be invalidated
+        #[pallet::weight(T::WeightInfo::add_liquidity_to())]
+        #[transactional]
+        pub fn add_liquidity_to(
+            origin: OriginFor<T>,
+            #[pallet::compact] lp_token_id: AssetOf<T>,
+            #[pallet::compact] currency: BalanceOf<T>,
+            #[pallet::compact] min_liquidity: BalanceOf<T>,
+            #[pallet::compact] max_tokens: BalanceOf<T>,
+            deadline: HeightOf<T>,
+        ) -> DispatchResult {
+            let height = <frame_system::Pallet<T>>::block_number();
+            ensure!(deadline > height, Error::<T>::Deadline);
+
+            let who = ensure_signed(origin)?;
+
+            let _ = Self::mint_to(
+                who,
+                lp_token_id,
+                currency,
+                min_liquidity,
+                max_tokens,
+                true, // keep alive
+            )?;
+
+            Ok(())
+        }
+
+        /// Remove part of the Liquidity
+        ///
+        /// * `lp_token_id` - The Liquidity Provider Token ID
+        /// * `liquidity` - The amount of liquidity to be removed
+        /// * `min_currency` - The minimum currency to be returned
+        /// * `min_tokens` - The minimum amount of tokens to be returned
+        /// * `deadline` - The block number at which the swap should be invalidated
+        #[pallet::weight(T::WeightInfo::remove_liquidity_partial())]
+        #[transactional]
+        pub fn remove_liquidity_partial(
+            origin: OriginFor<T>,
+            #[pallet::compact] lp_token_id: AssetOf<T>,
+            #[pallet::compact] liquidity: BalanceOf<T>,
+            #[pallet::compact] min_currency: BalanceOf<T>,
+            #[pallet::compact] min_tokens: BalanceOf<T>,
+            deadline: HeightOf<T>,
+        ) -> DispatchResult {
+            let height = <frame_system::Pallet<T>>::block_number();
+            ensure!(deadline > height, Error::<T>::Deadline);
+
+            let who = ensure_signed(origin)?;
+
+            let _ = Self::burn_partial(who, lp_token_id, liquidity, min_currency, min_tokens)?;
+
+            Ok(())
+        }
+
+        /// Set the swap fee of a pair
+        ///
+        /// * `token_id` - The Asset ID
+        /// * `fee` - The fee charged on the input of each swap
+        #[pallet::weight(T::WeightInfo::set_fee())]
+        pub fn set_fee(
+            origin: OriginFor<T>,
+            #[pallet::compact] token_id: AssetOf<T>,
+            fee: Permill,
+        ) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            ensure!(fee < Permill::one(), Error::<T>::TooHighFee);
+
+            let mut meta = <Metadata<T>>::get(token_id).ok_or(Error::<T>::NotExists)?;
+
+            meta.fee = fee;
+
+            <Metadata<T>>::insert(token_id, meta);
+
+            Self::deposit_event(Event::FeeChanged(token_id, fee));
+
+            Ok(())
+        }
+
+        /// Swap exact tokens for tokens of another asset, routed via currency
+        ///
+        /// * `path` - The Asset IDs to swap through, starting with the one to be sold
+        /// * `amount_in` - The amount of tokens to be sold
+        /// * `min_out` - The minimum amount of tokens to be gained
+        /// * `deadline` - The block number at which the swap should be invalidated
+        #[pallet::weight(T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32))]
+        #[transactional]
+        pub fn swap_exact_tokens_for_tokens(
+            origin: OriginFor<T>,
+            path: Vec<AssetOf<T>>,
+            #[pallet::compact] amount_in: BalanceOf<T>,
+            #[pallet::compact] min_out: BalanceOf<T>,
+            deadline: HeightOf<T>,
+        ) -> DispatchResult {
+            let height = <frame_system::Pallet<T>>::block_number();
+            ensure!(deadline > height, Error::<T>::Deadline);
+
+            let who = ensure_signed(origin)?;
+
+            let _ = Self::swap_in(who, &path, amount_in, min_out)?;
+
+            Ok(())
+        }
+
+        /// Swap tokens for exact tokens of another asset, routed via currency
+        ///
+        /// * `path` - The Asset IDs to swap through, starting with the one to be sold
+        /// * `amount_out` - The amount of tokens to be bought
+        /// * `max_in` - The maximum amount of tokens to be sold
+        /// * `deadline` - The block number at which the swap should be invalidated
+        #[pallet::weight(T::WeightInfo::swap_tokens_for_exact_tokens(path.len() as u32))]
+        #[transactional]
+        pub fn swap_tokens_for_exact_tokens(
+            origin: OriginFor<T>,
+            path: Vec<AssetOf<T>>,
+            #[pallet::compact] amount_out: BalanceOf<T>,
+            #[pallet::compact] max_in: BalanceOf<T>,
+            deadline: HeightOf<T>,
+        ) -> DispatchResult {
+            let height = <frame_system::Pallet<T>>::block_number();
+            ensure!(deadline > height, Error::<T>::Deadline);
+
+            let who = ensure_signed(origin)?;
+
+            let _ = Self::swap_out(who, &path, amount_out, max_in)?;
+
+            Ok(())
+        }
     }
 
     #[pallet::genesis_config]
@@ -402,10 +636,13 @@ pub mod pallet {
                     token_id,
                     types::Swap {
                         liquidity,
+                        fee: T::DefaultFee::get(),
                         ..Default::default()
                     },
                 );
             }
+
+            <StorageVersion<T>>::set(types::Releases::V1);
         }
     }
 }
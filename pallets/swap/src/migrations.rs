@@ -0,0 +1,46 @@
+use crate::{types, BalanceOf, Config, HeightOf, Metadata, StorageVersion};
+use codec::Decode;
+use frame_support::{traits::Get, weights::Weight};
+
+pub fn migrate<T: Config>() -> Weight {
+    let version = <StorageVersion<T>>::get();
+
+    let mut weight: Weight = 0;
+
+    if version < types::Releases::V1 {
+        weight = weight.saturating_add(v1::migrate::<T>());
+    }
+
+    <StorageVersion<T>>::set(types::Releases::V1);
+
+    weight.saturating_add(T::DbWeight::get().writes(1))
+}
+
+mod v1 {
+    use super::*;
+
+    #[derive(Decode)]
+    struct SwapV0<N, B> {
+        created: N,
+        liquidity: B,
+    }
+
+    /// Swaps used to charge a hard-coded 0.3% fee, carry the default one now
+    pub fn migrate<T: Config>() -> Weight {
+        let fee = T::DefaultFee::get();
+
+        let mut count = 0;
+
+        <Metadata<T>>::translate::<SwapV0<HeightOf<T>, BalanceOf<T>>, _>(|_, swap| {
+            count += 1;
+
+            Some(types::Swap {
+                created: swap.created,
+                liquidity: swap.liquidity,
+                fee,
+            })
+        });
+
+        T::DbWeight::get().reads_writes(count as Weight, count as Weight)
+    }
+}
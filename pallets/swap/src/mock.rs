@@ -5,12 +5,14 @@ use sp_core::{sr25519, H256};
 use sp_runtime::{
     testing::Header,
     traits::{BlakeTwo256, IdentityLookup},
+    Permill,
 };
 
 type UncheckedExtrinsic = system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = system::mocking::MockBlock<Test>;
 
 pub const ALICE: sr25519::Public = sr25519::Public([1; 32]);
+pub const BOB: sr25519::Public = sr25519::Public([2; 32]);
 
 frame_support::construct_runtime!(
     pub enum Test where
@@ -104,7 +106,10 @@ impl pallet_balances::Config for Test {
 }
 
 parameter_types! {
+    pub const SwapDefaultFee: Permill = Permill::from_parts(3_000);
+    pub const SwapMaxPathLen: u32 = 8;
     pub const SwapPalletId: PalletId = PalletId(*b"prm/swap");
+    pub storage SwapProtocolFeeShare: Permill = Permill::zero();
 }
 
 pub struct FarmingCurve;
@@ -124,8 +129,13 @@ impl parami_swap::Config for Test {
     type AssetId = AssetId;
     type Assets = Assets;
     type Currency = Balances;
+    type DefaultFee = SwapDefaultFee;
     type FarmingCurve = FarmingCurve;
+    type ForceOrigin = EnsureRoot<Self::AccountId>;
+    type MaxPathLen = SwapMaxPathLen;
     type PalletId = SwapPalletId;
+    type ProtocolFee = ();
+    type ProtocolFeeShare = SwapProtocolFeeShare;
     type WeightInfo = ();
 }
 
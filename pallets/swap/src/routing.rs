@@ -0,0 +1,131 @@
+use crate::{AccountOf, AssetOf, BalanceOf, Config, Error, Pallet};
+
+use frame_support::ensure;
+use parami_traits::Swaps;
+use sp_runtime::{traits::One, DispatchError};
+use sp_std::prelude::*;
+
+impl<T: Config> Pallet<T> {
+    /// Get dry-run result of swapping exact tokens along a path
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The Asset IDs to swap through, each hop is routed via currency
+    /// * `amount_in` - The amount of tokens of the first asset to be sold
+    ///
+    /// # Returns
+    ///
+    /// The amount of tokens of the last asset to be gained
+    pub fn swap_in_dry(
+        path: &[AssetOf<T>],
+        amount_in: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        ensure!(
+            path.len() >= 2 && path.len() <= T::MaxPathLen::get() as usize,
+            Error::<T>::InvalidPath
+        );
+
+        let mut amount = amount_in;
+
+        for hop in path.windows(2) {
+            let currency = Self::token_in_dry(hop[0], amount)?;
+            amount = Self::quote_in_dry(hop[1], currency)?;
+        }
+
+        Ok(amount)
+    }
+
+    /// Get dry-run result of swapping tokens for exact tokens along a path
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The Asset IDs to swap through, each hop is routed via currency
+    /// * `amount_out` - The amount of tokens of the last asset to be bought
+    ///
+    /// # Returns
+    ///
+    /// The amount of tokens of the first asset needed
+    pub fn swap_out_dry(
+        path: &[AssetOf<T>],
+        amount_out: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let hops = Self::calculate_route_out(path, amount_out)?;
+
+        Ok(hops[0].0)
+    }
+
+    /// Swap exact tokens along a path
+    pub(super) fn swap_in(
+        who: AccountOf<T>,
+        path: &[AssetOf<T>],
+        amount_in: BalanceOf<T>,
+        min_out: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let amount_out = Self::swap_in_dry(path, amount_in)?;
+
+        ensure!(amount_out >= min_out, Error::<T>::TooLowTokens);
+
+        let mut amount = amount_in;
+
+        for hop in path.windows(2) {
+            // the currency only passes through the account
+            let currency = Self::token_in(who.clone(), hop[0], amount, One::one(), false)?;
+            amount = Self::quote_in(who.clone(), hop[1], currency, One::one(), false)?;
+        }
+
+        ensure!(amount >= min_out, Error::<T>::TooLowTokens);
+
+        Ok(amount)
+    }
+
+    /// Swap tokens for exact tokens along a path
+    pub(super) fn swap_out(
+        who: AccountOf<T>,
+        path: &[AssetOf<T>],
+        amount_out: BalanceOf<T>,
+        max_in: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let hops = Self::calculate_route_out(path, amount_out)?;
+
+        let amount_in = hops[0].0;
+
+        ensure!(amount_in <= max_in, Error::<T>::TooExpensiveTokens);
+
+        for (hop, (tokens_in, currency, tokens_out)) in path.windows(2).zip(hops) {
+            // the currency only passes through the account
+            Self::quote_out(who.clone(), hop[0], currency, tokens_in, false)?;
+            Self::token_out(who.clone(), hop[1], tokens_out, currency, false)?;
+        }
+
+        Ok(amount_in)
+    }
+
+    /// Calculate tokens sold, currency routed and tokens bought of each hop,
+    /// backwards from the amount of tokens to be bought at last
+    fn calculate_route_out(
+        path: &[AssetOf<T>],
+        amount_out: BalanceOf<T>,
+    ) -> Result<Vec<(BalanceOf<T>, BalanceOf<T>, BalanceOf<T>)>, DispatchError> {
+        ensure!(
+            path.len() >= 2 && path.len() <= T::MaxPathLen::get() as usize,
+            Error::<T>::InvalidPath
+        );
+
+        let mut hops = Vec::with_capacity(path.len() - 1);
+
+        let mut amount = amount_out;
+
+        for hop in path.windows(2).rev() {
+            let currency = Self::token_out_dry(hop[1], amount)?;
+            let tokens = Self::quote_out_dry(hop[0], currency)?;
+
+            hops.push((tokens, currency, amount));
+
+            amount = tokens;
+        }
+
+        hops.reverse();
+
+        Ok(hops)
+    }
+}
@@ -4,6 +4,7 @@ use frame_support::{
     traits::{tokens::fungibles::Mutate as FungMutate, Currency},
 };
 use parami_traits::Swaps;
+use sp_runtime::{DispatchError, Permill};
 
 #[test]
 fn should_create() {
@@ -102,6 +103,70 @@ fn should_add_liquidity() {
     });
 }
 
+#[test]
+fn should_add_liquidity_to() {
+    new_test_ext().execute_with(|| {
+        let token = 1;
+
+        assert_ok!(Swap::create(Origin::signed(ALICE), token));
+
+        assert_noop!(
+            Swap::add_liquidity_to(Origin::signed(ALICE), 0, 100, 100, 10, 100),
+            Error::<Test>::NotExists
+        );
+
+        assert_ok!(Swap::add_liquidity(
+            Origin::signed(ALICE),
+            token,
+            200,
+            200,
+            20,
+            100,
+        ));
+
+        assert_noop!(
+            Swap::add_liquidity_to(Origin::signed(BOB), 0, 100, 100, 10, 100),
+            Error::<Test>::NotExists
+        );
+
+        assert_noop!(
+            Swap::add_liquidity_to(Origin::signed(ALICE), 0, 100, 101, 10, 100),
+            Error::<Test>::TooLowLiquidity
+        );
+
+        System::set_block_number(5);
+
+        assert_ok!(Swap::add_liquidity_to(
+            Origin::signed(ALICE),
+            0,
+            100,
+            100,
+            10,
+            100,
+        ));
+
+        let meta = <Metadata<Test>>::get(&token).unwrap();
+        assert_eq!(meta.liquidity, 200 + 100);
+
+        let pot = Swap::get_pool_account(token);
+        assert_eq!(Balances::free_balance(&pot), 200 + 100);
+        assert_eq!(Assets::balance(token, &pot), 20 + 10);
+
+        // farming reward settled
+        assert_eq!(Balances::free_balance(&ALICE), 10000 - 200 - 100);
+        assert_eq!(Assets::balance(token, &ALICE), 44 - 20 - 10 + 100);
+
+        assert_eq!(<Provider<Test>>::get(token, &ALICE), 200 + 100);
+        assert_eq!(<Account<Test>>::get(&ALICE, 0), Some(5));
+
+        let liquidity = <Liquidity<Test>>::get(0).unwrap();
+        assert_eq!(liquidity.amount, 200 + 100);
+        assert_eq!(liquidity.minted, 0);
+
+        assert_eq!(<Liquidity<Test>>::get(1), None);
+    });
+}
+
 #[test]
 fn should_remove_liquidity() {
     new_test_ext().execute_with(|| {
@@ -157,6 +222,168 @@ fn should_remove_liquidity() {
     });
 }
 
+#[test]
+fn should_remove_liquidity_partial() {
+    new_test_ext().execute_with(|| {
+        let token = 1;
+
+        assert_ok!(Swap::create(Origin::signed(ALICE), token));
+
+        assert_noop!(
+            Swap::remove_liquidity_partial(Origin::signed(ALICE), 0, 100, 100, 10, 100),
+            Error::<Test>::NotExists
+        );
+
+        assert_ok!(Swap::add_liquidity(
+            Origin::signed(ALICE),
+            token,
+            200,
+            200,
+            20,
+            100,
+        ));
+
+        assert_noop!(
+            Swap::remove_liquidity_partial(Origin::signed(BOB), 0, 100, 100, 10, 100),
+            Error::<Test>::NotExists
+        );
+
+        assert_noop!(
+            Swap::remove_liquidity_partial(Origin::signed(ALICE), 0, 0, 0, 0, 100),
+            Error::<Test>::ZeroLiquidity
+        );
+
+        assert_noop!(
+            Swap::remove_liquidity_partial(Origin::signed(ALICE), 0, 300, 0, 0, 100),
+            Error::<Test>::InsufficientLiquidity
+        );
+
+        assert_noop!(
+            Swap::remove_liquidity_partial(Origin::signed(ALICE), 0, 100, 101, 0, 100),
+            Error::<Test>::TooLowCurrency
+        );
+
+        assert_noop!(
+            Swap::remove_liquidity_partial(Origin::signed(ALICE), 0, 100, 0, 11, 100),
+            Error::<Test>::TooLowTokens
+        );
+
+        System::set_block_number(5);
+
+        assert_ok!(Swap::remove_liquidity_partial(
+            Origin::signed(ALICE),
+            0,
+            100,
+            100,
+            10,
+            100,
+        ));
+
+        let meta = <Metadata<Test>>::get(&token).unwrap();
+        assert_eq!(meta.liquidity, 200 - 100);
+
+        let pot = Swap::get_pool_account(token);
+        assert_eq!(Balances::free_balance(&pot), 200 - 100);
+        assert_eq!(Assets::balance(token, &pot), 20 - 10);
+
+        // farming reward settled
+        assert_eq!(Balances::free_balance(&ALICE), 10000 - 200 + 100);
+        assert_eq!(Assets::balance(token, &ALICE), 44 - 20 + 10 + 100);
+
+        assert_eq!(<Provider<Test>>::get(token, &ALICE), 200 - 100);
+        assert_eq!(<Account<Test>>::get(&ALICE, 0), Some(5));
+
+        let liquidity = <Liquidity<Test>>::get(0).unwrap();
+        assert_eq!(liquidity.amount, 200 - 100);
+
+        assert_ok!(Swap::remove_liquidity_partial(
+            Origin::signed(ALICE),
+            0,
+            100,
+            100,
+            10,
+            100,
+        ));
+
+        let meta = <Metadata<Test>>::get(&token).unwrap();
+        assert_eq!(meta.liquidity, 0);
+
+        assert_eq!(Balances::free_balance(&pot), 0);
+        assert_eq!(Assets::balance(token, &pot), 0);
+
+        assert_eq!(Balances::free_balance(&ALICE), 10000);
+        assert_eq!(Assets::balance(token, &ALICE), 44 + 100 + 100);
+
+        assert_eq!(<Provider<Test>>::get(token, &ALICE), 0);
+        assert_eq!(<Account<Test>>::get(&ALICE, 0), None);
+        assert_eq!(<Liquidity<Test>>::get(0), None);
+    });
+}
+
+#[test]
+fn should_transfer_liquidity() {
+    new_test_ext().execute_with(|| {
+        let token = 1;
+
+        assert_ok!(Swap::create(Origin::signed(ALICE), token));
+
+        assert_noop!(
+            Swap::transfer_liquidity(Origin::signed(ALICE), 0, BOB),
+            Error::<Test>::NotExists
+        );
+
+        assert_ok!(Swap::add_liquidity(
+            Origin::signed(ALICE),
+            token,
+            200,
+            200,
+            20,
+            100,
+        ));
+
+        assert_ok!(Swap::add_liquidity(
+            Origin::signed(ALICE),
+            token,
+            100,
+            100,
+            10,
+            100,
+        ));
+
+        <Account<Test>>::insert(&ALICE, 0, 5);
+
+        assert_noop!(
+            Swap::transfer_liquidity(Origin::signed(BOB), 0, BOB),
+            Error::<Test>::NotExists
+        );
+
+        assert_ok!(Swap::transfer_liquidity(Origin::signed(ALICE), 0, BOB));
+
+        let liquidity = <Liquidity<Test>>::get(0).unwrap();
+        assert_eq!(liquidity.owner, BOB);
+        assert_eq!(liquidity.amount, 200);
+
+        assert_eq!(<Account<Test>>::get(&ALICE, 0), None);
+        assert_eq!(<Account<Test>>::get(&BOB, 0), Some(5));
+
+        assert_eq!(<Provider<Test>>::get(token, &ALICE), 100);
+        assert_eq!(<Provider<Test>>::get(token, &BOB), 200);
+
+        assert_noop!(
+            Swap::acquire_reward(Origin::signed(ALICE), 0),
+            Error::<Test>::NotExists
+        );
+
+        assert_ok!(Swap::transfer_liquidity(Origin::signed(ALICE), 1, BOB));
+
+        assert_eq!(<Provider<Test>>::get(token, &ALICE), 0);
+        assert_eq!(<Provider<Test>>::get(token, &BOB), 300);
+
+        let meta = <Metadata<Test>>::get(&token).unwrap();
+        assert_eq!(meta.liquidity, 200 + 100);
+    });
+}
+
 #[test]
 fn should_buy_tokens() {
     new_test_ext().execute_with(|| {
@@ -313,20 +540,260 @@ fn should_buy_currency() {
     });
 }
 
+#[test]
+fn should_set_fee() {
+    new_test_ext().execute_with(|| {
+        let token = 1;
+
+        assert_noop!(
+            Swap::set_fee(Origin::root(), token, Permill::from_percent(1)),
+            Error::<Test>::NotExists
+        );
+
+        assert_ok!(Swap::create(Origin::signed(ALICE), token));
+
+        let meta = <Metadata<Test>>::get(&token).unwrap();
+        assert_eq!(meta.fee, Permill::from_parts(3_000));
+
+        assert_noop!(
+            Swap::set_fee(Origin::signed(ALICE), token, Permill::zero()),
+            DispatchError::BadOrigin
+        );
+
+        assert_noop!(
+            Swap::set_fee(Origin::root(), token, Permill::one()),
+            Error::<Test>::TooHighFee
+        );
+
+        assert_ok!(Swap::add_liquidity(
+            Origin::signed(ALICE),
+            token,
+            1000,
+            1000,
+            44,
+            100,
+        ));
+
+        assert_eq!(Swap::quote_in_dry(token, 100), Ok(3));
+
+        assert_ok!(Swap::set_fee(Origin::root(), token, Permill::zero()));
+
+        let meta = <Metadata<Test>>::get(&token).unwrap();
+        assert_eq!(meta.fee, Permill::zero());
+
+        assert_eq!(Swap::quote_in_dry(token, 100), Ok(4));
+    });
+}
+
+#[test]
+fn should_charge_protocol_fee() {
+    new_test_ext().execute_with(|| {
+        let token = 1;
+
+        SwapProtocolFeeShare::set(&Permill::from_percent(50));
+
+        assert_ok!(Swap::create(Origin::signed(ALICE), token));
+
+        assert_ok!(Swap::set_fee(
+            Origin::root(),
+            token,
+            Permill::from_percent(10)
+        ));
+
+        assert_ok!(Swap::add_liquidity(
+            Origin::signed(ALICE),
+            token,
+            1000,
+            1000,
+            44,
+            100,
+        ));
+
+        assert_eq!(Swap::token_out_dry(token, 4), Ok(112));
+
+        assert_ok!(Swap::buy_tokens(Origin::signed(ALICE), token, 4, 112, 100));
+
+        // 10% of 112 is the fee, half of which goes to the protocol
+        let pot = Swap::get_pool_account(token);
+        assert_eq!(Balances::free_balance(&pot), 1000 + 112 - 5);
+        assert_eq!(Assets::balance(token, &pot), 44 - 4);
+
+        assert_eq!(Balances::free_balance(&ALICE), 10000 - 1000 - 112);
+        assert_eq!(Assets::balance(token, &ALICE), 4);
+
+        assert_eq!(Balances::total_issuance(), 10000 - 5);
+    });
+}
+
+#[test]
+fn should_swap_exact_tokens_for_tokens() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::mint_into(1, &ALICE, 100));
+
+        assert_ok!(Assets::create(Origin::signed(ALICE), 2, ALICE, 1));
+        assert_ok!(Assets::mint_into(2, &ALICE, 1000));
+
+        assert_ok!(Swap::create(Origin::signed(ALICE), 1));
+        assert_ok!(Swap::create(Origin::signed(ALICE), 2));
+
+        assert_ok!(Swap::add_liquidity(
+            Origin::signed(ALICE),
+            1,
+            1000,
+            1000,
+            100,
+            100,
+        ));
+        assert_ok!(Swap::add_liquidity(
+            Origin::signed(ALICE),
+            2,
+            1000,
+            1000,
+            100,
+            100,
+        ));
+
+        assert_noop!(
+            Swap::swap_exact_tokens_for_tokens(Origin::signed(ALICE), vec![1], 10, 1, 100),
+            Error::<Test>::InvalidPath
+        );
+
+        assert_noop!(
+            Swap::swap_exact_tokens_for_tokens(
+                Origin::signed(ALICE),
+                vec![1, 2].repeat(5),
+                10,
+                1,
+                100
+            ),
+            Error::<Test>::InvalidPath
+        );
+
+        assert_noop!(
+            Swap::swap_exact_tokens_for_tokens(Origin::signed(ALICE), vec![1, 3], 10, 1, 100),
+            Error::<Test>::NotExists
+        );
+
+        assert_eq!(Swap::swap_in_dry(&[1, 2], 10), Ok(8));
+
+        assert_noop!(
+            Swap::swap_exact_tokens_for_tokens(Origin::signed(ALICE), vec![1, 2], 10, 9, 100),
+            Error::<Test>::TooLowTokens
+        );
+
+        assert_ok!(Swap::swap_exact_tokens_for_tokens(
+            Origin::signed(ALICE),
+            vec![1, 2],
+            10,
+            8,
+            100
+        ));
+
+        let pot = Swap::get_pool_account(1);
+        assert_eq!(Balances::free_balance(&pot), 1000 - 90);
+        assert_eq!(Assets::balance(1, &pot), 100 + 10);
+
+        let pot = Swap::get_pool_account(2);
+        assert_eq!(Balances::free_balance(&pot), 1000 + 90);
+        assert_eq!(Assets::balance(2, &pot), 100 - 8);
+
+        // the deposit of asset 2 is reserved
+        assert_eq!(Balances::free_balance(&ALICE), 10000 - 100 - 1000 - 1000);
+        assert_eq!(Assets::balance(1, &ALICE), 44 + 100 - 100 - 10);
+        assert_eq!(Assets::balance(2, &ALICE), 1000 - 100 + 8);
+    });
+}
+
+#[test]
+fn should_swap_tokens_for_exact_tokens() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::mint_into(1, &ALICE, 100));
+
+        assert_ok!(Assets::create(Origin::signed(ALICE), 2, ALICE, 1));
+        assert_ok!(Assets::mint_into(2, &ALICE, 1000));
+
+        assert_ok!(Swap::create(Origin::signed(ALICE), 1));
+        assert_ok!(Swap::create(Origin::signed(ALICE), 2));
+
+        assert_ok!(Swap::add_liquidity(
+            Origin::signed(ALICE),
+            1,
+            1000,
+            1000,
+            100,
+            100,
+        ));
+        assert_ok!(Swap::add_liquidity(
+            Origin::signed(ALICE),
+            2,
+            1000,
+            1000,
+            100,
+            100,
+        ));
+
+        assert_noop!(
+            Swap::swap_tokens_for_exact_tokens(Origin::signed(ALICE), vec![2], 8, 10, 100),
+            Error::<Test>::InvalidPath
+        );
+
+        assert_noop!(
+            Swap::swap_tokens_for_exact_tokens(
+                Origin::signed(ALICE),
+                vec![1, 2].repeat(5),
+                8,
+                10,
+                100
+            ),
+            Error::<Test>::InvalidPath
+        );
+
+        assert_eq!(Swap::swap_out_dry(&[1, 2], 8), Ok(10));
+
+        assert_noop!(
+            Swap::swap_tokens_for_exact_tokens(Origin::signed(ALICE), vec![1, 2], 8, 9, 100),
+            Error::<Test>::TooExpensiveTokens
+        );
+
+        assert_ok!(Swap::swap_tokens_for_exact_tokens(
+            Origin::signed(ALICE),
+            vec![1, 2],
+            8,
+            10,
+            100
+        ));
+
+        let pot = Swap::get_pool_account(1);
+        assert_eq!(Balances::free_balance(&pot), 1000 - 88);
+        assert_eq!(Assets::balance(1, &pot), 100 + 10);
+
+        let pot = Swap::get_pool_account(2);
+        assert_eq!(Balances::free_balance(&pot), 1000 + 88);
+        assert_eq!(Assets::balance(2, &pot), 100 - 8);
+
+        // the deposit of asset 2 is reserved
+        assert_eq!(Balances::free_balance(&ALICE), 10000 - 100 - 1000 - 1000);
+        assert_eq!(Assets::balance(1, &ALICE), 44 + 100 - 100 - 10);
+        assert_eq!(Assets::balance(2, &ALICE), 1000 - 100 + 8);
+    });
+}
+
 #[test]
 fn should_swap_in_piecewisely() {
     use sp_core::U512;
 
+    let fee = Permill::from_parts(3_000);
+
     let mut input_reserve = U512::from(1_000_000_000_000_000_000_000_000u128);
     let mut output_reserve = U512::from(1_000_000_000_000_000_000_000_000u128);
 
     let input = U512::from(300_000_000_000_000_000_000_000u128);
-    let obtain_once = Swap::calculate_price_sell(input, input_reserve, output_reserve);
+    let obtain_once = Swap::calculate_price_sell(input, input_reserve, output_reserve, fee);
 
     let mut obtain_quintuple = U512::zero();
     {
         let input = U512::from(100_000_000_000_000_000_000_000u128);
-        let obtain = Swap::calculate_price_sell(input, input_reserve, output_reserve);
+        let obtain = Swap::calculate_price_sell(input, input_reserve, output_reserve, fee);
 
         input_reserve += input;
         output_reserve -= obtain;
@@ -335,7 +802,7 @@ fn should_swap_in_piecewisely() {
     }
     {
         let input = U512::from(110_000_000_000_000_000_000_000u128);
-        let obtain = Swap::calculate_price_sell(input, input_reserve, output_reserve);
+        let obtain = Swap::calculate_price_sell(input, input_reserve, output_reserve, fee);
 
         input_reserve += input;
         output_reserve -= obtain;
@@ -344,7 +811,7 @@ fn should_swap_in_piecewisely() {
     }
     {
         let input = U512::from(90_000_000_000_000_000_000_000u128);
-        let obtain = Swap::calculate_price_sell(input, input_reserve, output_reserve);
+        let obtain = Swap::calculate_price_sell(input, input_reserve, output_reserve, fee);
 
         input_reserve += input;
         output_reserve -= obtain;
@@ -359,16 +826,18 @@ fn should_swap_in_piecewisely() {
 fn should_swap_out_piecewisely() {
     use sp_core::U512;
 
+    let fee = Permill::from_parts(3_000);
+
     let mut input_reserve = U512::from(1_000_000_000_000_000_000_000_000u128);
     let mut output_reserve = U512::from(1_000_000_000_000_000_000_000_000u128);
 
     let output = U512::from(300_000_000_000_000_000_000_000u128);
-    let coast_once = Swap::calculate_price_buy(output, input_reserve, output_reserve);
+    let coast_once = Swap::calculate_price_buy(output, input_reserve, output_reserve, fee);
 
     let mut coast_quintuple = U512::zero();
     {
         let output = U512::from(100_000_000_000_000_000_000_000u128);
-        let coast = Swap::calculate_price_buy(output, input_reserve, output_reserve);
+        let coast = Swap::calculate_price_buy(output, input_reserve, output_reserve, fee);
 
         input_reserve += coast;
         output_reserve -= output;
@@ -377,7 +846,7 @@ fn should_swap_out_piecewisely() {
     }
     {
         let output = U512::from(90_000_000_000_000_000_000_000u128);
-        let coast = Swap::calculate_price_buy(output, input_reserve, output_reserve);
+        let coast = Swap::calculate_price_buy(output, input_reserve, output_reserve, fee);
 
         input_reserve += coast;
         output_reserve -= output;
@@ -386,7 +855,7 @@ fn should_swap_out_piecewisely() {
     }
     {
         let output = U512::from(81_000_000_000_000_000_000_000u128);
-        let coast = Swap::calculate_price_buy(output, input_reserve, output_reserve);
+        let coast = Swap::calculate_price_buy(output, input_reserve, output_reserve, fee);
 
         input_reserve += coast;
         output_reserve -= output;
@@ -395,7 +864,7 @@ fn should_swap_out_piecewisely() {
     }
     {
         let output = U512::from(29_000_000_000_000_000_000_000u128);
-        let coast = Swap::calculate_price_buy(output, input_reserve, output_reserve);
+        let coast = Swap::calculate_price_buy(output, input_reserve, output_reserve, fee);
 
         input_reserve += coast;
         output_reserve -= output;
@@ -15,13 +15,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Autogenerated weights for parami_swap
+//! Weights for parami_swap
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2021-12-20, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+//! NOT GENERATED: entries added since the benchmark of 2021-12-20 are hand-written placeholders,
+//! estimated from the storage reads and writes of each call.
+//! Regenerate this file with the command below before release.
 
-// Executed Command:
+// Command to regenerate:
 // ./target/release/parami
 // benchmark
 // --chain=dev
@@ -50,6 +50,12 @@ pub trait WeightInfo {
     fn sell_tokens() -> Weight;
     fn sell_currency() -> Weight;
     fn buy_currency() -> Weight;
+    fn transfer_liquidity() -> Weight;
+    fn add_liquidity_to() -> Weight;
+    fn remove_liquidity_partial() -> Weight;
+    fn set_fee() -> Weight;
+    fn swap_exact_tokens_for_tokens(n: u32, ) -> Weight;
+    fn swap_tokens_for_exact_tokens(n: u32, ) -> Weight;
 }
 
 /// Weights for parami_swap using the Substrate node and recommended hardware.
@@ -117,6 +123,66 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(5 as Weight))
             .saturating_add(T::DbWeight::get().writes(4 as Weight))
     }
+    // Storage: Swap Liquidity (r:1 w:1)
+    // Storage: Swap Account (r:1 w:2)
+    // Storage: Swap Provider (r:2 w:2)
+    fn transfer_liquidity() -> Weight {
+        (31_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    // Storage: Swap Liquidity (r:1 w:1)
+    // Storage: Swap Metadata (r:1 w:1)
+    // Storage: Swap Account (r:1 w:1)
+    // Storage: Swap Provider (r:1 w:1)
+    // Storage: Assets Asset (r:3 w:3)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Assets Account (r:4 w:4)
+    fn add_liquidity_to() -> Weight {
+        (112_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(12 as Weight))
+            .saturating_add(T::DbWeight::get().writes(12 as Weight))
+    }
+    // Storage: Swap Liquidity (r:1 w:1)
+    // Storage: Swap Metadata (r:1 w:1)
+    // Storage: Swap Account (r:1 w:1)
+    // Storage: Swap Provider (r:1 w:1)
+    // Storage: Assets Asset (r:3 w:2)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Assets Account (r:3 w:3)
+    fn remove_liquidity_partial() -> Weight {
+        (118_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(11 as Weight))
+            .saturating_add(T::DbWeight::get().writes(10 as Weight))
+    }
+    // Storage: Swap Metadata (r:1 w:1)
+    fn set_fee() -> Weight {
+        (18_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Swap Metadata (r:1 w:0)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: Assets Asset (r:1 w:1)
+    fn swap_exact_tokens_for_tokens(n: u32, ) -> Weight {
+        (0 as Weight)
+            // Standard Error: 41_000
+            .saturating_add((131_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads((10 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((8 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Swap Metadata (r:1 w:0)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: Assets Asset (r:1 w:1)
+    fn swap_tokens_for_exact_tokens(n: u32, ) -> Weight {
+        (0 as Weight)
+            // Standard Error: 43_000
+            .saturating_add((134_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads((10 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((8 as Weight).saturating_mul(n as Weight)))
+    }
 }
 
 // For backwards compatibility and tests
@@ -183,4 +249,64 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(5 as Weight))
             .saturating_add(RocksDbWeight::get().writes(4 as Weight))
     }
+    // Storage: Swap Liquidity (r:1 w:1)
+    // Storage: Swap Account (r:1 w:2)
+    // Storage: Swap Provider (r:2 w:2)
+    fn transfer_liquidity() -> Weight {
+        (31_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    // Storage: Swap Liquidity (r:1 w:1)
+    // Storage: Swap Metadata (r:1 w:1)
+    // Storage: Swap Account (r:1 w:1)
+    // Storage: Swap Provider (r:1 w:1)
+    // Storage: Assets Asset (r:3 w:3)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Assets Account (r:4 w:4)
+    fn add_liquidity_to() -> Weight {
+        (112_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(12 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(12 as Weight))
+    }
+    // Storage: Swap Liquidity (r:1 w:1)
+    // Storage: Swap Metadata (r:1 w:1)
+    // Storage: Swap Account (r:1 w:1)
+    // Storage: Swap Provider (r:1 w:1)
+    // Storage: Assets Asset (r:3 w:2)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Assets Account (r:3 w:3)
+    fn remove_liquidity_partial() -> Weight {
+        (118_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(11 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(10 as Weight))
+    }
+    // Storage: Swap Metadata (r:1 w:1)
+    fn set_fee() -> Weight {
+        (18_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Swap Metadata (r:1 w:0)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: Assets Asset (r:1 w:1)
+    fn swap_exact_tokens_for_tokens(n: u32, ) -> Weight {
+        (0 as Weight)
+            // Standard Error: 41_000
+            .saturating_add((131_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads((10 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((8 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Swap Metadata (r:1 w:0)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: Assets Asset (r:1 w:1)
+    fn swap_tokens_for_exact_tokens(n: u32, ) -> Weight {
+        (0 as Weight)
+            // Standard Error: 43_000
+            .saturating_add((134_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads((10 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((8 as Weight).saturating_mul(n as Weight)))
+    }
 }
@@ -16,7 +16,7 @@ benchmarks! {
     }
 
     create {
-        let n in 0 .. 1000;
+        let n in 0 .. T::MaxTagLen::get();
 
         let caller: T::AccountId = whitelisted_caller();
 
@@ -31,16 +31,16 @@ benchmarks! {
         let name = vec![0u8; n as usize];
     }: _(RawOrigin::Signed(caller), name.clone())
     verify {
-        assert_ne!(<Metadata<T>>::get(&name), None);
+        assert_ne!(<Metadata<T>>::get(Tag::<T>::hash(&name)), None);
     }
 
     force_create {
-        let n in 0 .. 1000;
+        let n in 0 .. T::MaxTagLen::get();
 
         let name = vec![0u8; n as usize];
     }: _(RawOrigin::Root, name.clone())
     verify {
-        assert_ne!(<Metadata<T>>::get(&name), None);
+        assert_ne!(<Metadata<T>>::get(Tag::<T>::hash(&name)), None);
     }
 }
 
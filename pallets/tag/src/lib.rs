@@ -16,11 +16,13 @@ mod benchmarking;
 
 mod types;
 
+use codec::Encode;
 use frame_support::{
     dispatch::DispatchResult,
     ensure,
     storage::PrefixIterator,
-    traits::{Currency, ExistenceRequirement::KeepAlive, WithdrawReasons},
+    traits::{Currency, ExistenceRequirement::KeepAlive, Get, WithdrawReasons},
+    Blake2_256, StorageHasher,
 };
 #[cfg(not(feature = "std"))]
 use num_traits::Float;
@@ -36,6 +38,7 @@ type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountOf<T>>>::Balance
 type HashOf<T> = <<T as frame_system::Config>::Hashing as Hash>::Output;
 type HeightOf<T> = <T as frame_system::Config>::BlockNumber;
 type MetaOf<T> = types::Metadata<<T as Config>::DecentralizedId, HeightOf<T>>;
+type TagOf = [u8; 32];
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -68,6 +71,10 @@ pub mod pallet {
         #[pallet::constant]
         type SubmissionFee: Get<BalanceOf<Self>>;
 
+        /// The maximum length of a tag
+        #[pallet::constant]
+        type MaxTagLen: Get<u32>;
+
         /// The origin which may do calls
         type CallOrigin: EnsureOrigin<
             Self::Origin,
@@ -83,12 +90,16 @@ pub mod pallet {
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::generate_storage_info]
     pub struct Pallet<T>(_);
 
     /// Metadata of a tag
+    ///
+    /// Tags are keyed by their Blake2 256 hash,
+    /// which equals keying the tag itself with the Blake2_256 hasher
     #[pallet::storage]
     #[pallet::getter(fn meta)]
-    pub(super) type Metadata<T: Config> = StorageMap<_, Blake2_256, Vec<u8>, MetaOf<T>>;
+    pub(super) type Metadata<T: Config> = StorageMap<_, Identity, TagOf, MetaOf<T>>;
 
     /// Tags of an advertisement
     #[pallet::storage]
@@ -96,8 +107,8 @@ pub mod pallet {
         _,
         Identity,
         HashOf<T>,
-        Blake2_256,
-        Vec<u8>, //
+        Identity,
+        TagOf, //
         bool,
         ValueQuery,
     >;
@@ -108,8 +119,8 @@ pub mod pallet {
         _,
         Identity,
         T::DecentralizedId,
-        Blake2_256,
-        Vec<u8>, //
+        Identity,
+        TagOf, //
         i32,
         ValueQuery,
     >;
@@ -120,8 +131,8 @@ pub mod pallet {
         _,
         Identity,
         T::DecentralizedId,
-        Blake2_256,
-        Vec<u8>, //
+        Identity,
+        TagOf, //
         i32,
         ValueQuery,
     >;
@@ -140,6 +151,7 @@ pub mod pallet {
     pub enum Error<T> {
         Exists,
         InsufficientBalance,
+        TooLong,
     }
 
     #[pallet::call]
@@ -148,7 +160,12 @@ pub mod pallet {
         pub fn create(origin: OriginFor<T>, tag: Vec<u8>) -> DispatchResult {
             let (did, who) = T::CallOrigin::ensure_origin(origin)?;
 
-            ensure!(!<Metadata<T>>::contains_key(&tag), Error::<T>::Exists);
+            ensure!(
+                tag.len() <= T::MaxTagLen::get() as usize,
+                Error::<T>::TooLong
+            );
+
+            ensure!(!Self::exists(&tag), Error::<T>::Exists);
 
             let fee = T::SubmissionFee::get();
 
@@ -169,7 +186,12 @@ pub mod pallet {
         pub fn force_create(origin: OriginFor<T>, tag: Vec<u8>) -> DispatchResult {
             T::ForceOrigin::ensure_origin(origin)?;
 
-            ensure!(!<Metadata<T>>::contains_key(&tag), Error::<T>::Exists);
+            ensure!(
+                tag.len() <= T::MaxTagLen::get() as usize,
+                Error::<T>::TooLong
+            );
+
+            ensure!(!Self::exists(&tag), Error::<T>::Exists);
 
             let did = T::DecentralizedId::default();
 
@@ -206,7 +228,7 @@ pub mod pallet {
         fn build(&self) {
             for tag in &self.tag {
                 <Metadata<T>>::insert(
-                    tag,
+                    Self::hash(tag),
                     types::Metadata {
                         creator: T::DecentralizedId::default(),
                         created: Default::default(),
@@ -215,15 +237,15 @@ pub mod pallet {
             }
 
             for (ad, tag) in &self.tags {
-                <TagsOf<T>>::insert(ad, tag, true);
+                <TagsOf<T>>::insert(ad, Self::hash(tag), true);
             }
 
             for (did, tag, score) in &self.personas {
-                <PersonasOf<T>>::insert(did, tag, score);
+                <PersonasOf<T>>::insert(did, Self::hash(tag), score);
             }
 
             for (did, tag, score) in &self.influences {
-                <InfluencesOf<T>>::insert(did, tag, score);
+                <InfluencesOf<T>>::insert(did, Self::hash(tag), score);
             }
         }
     }
@@ -233,9 +255,15 @@ impl<T: Config> Pallet<T> {
     fn inner_create(creator: T::DecentralizedId, tag: Vec<u8>) -> Vec<u8> {
         let created = <frame_system::Pallet<T>>::block_number();
 
-        <Metadata<T>>::insert(&tag, types::Metadata { creator, created });
+        let hash = Self::hash(&tag);
+
+        <Metadata<T>>::insert(&hash, types::Metadata { creator, created });
 
-        Self::key(&tag)
+        hash.to_vec()
+    }
+
+    fn hash<K: AsRef<Vec<u8>>>(tag: K) -> TagOf {
+        tag.as_ref().using_encoded(Blake2_256::hash)
     }
 
     fn accrue(score: i32, delta: i32) -> i32 {
@@ -279,14 +307,11 @@ impl<T: Config> Tags for Pallet<T> {
     type Hash = HashOf<T>;
 
     fn key<K: AsRef<Vec<u8>>>(tag: K) -> Vec<u8> {
-        use codec::Encode;
-        use frame_support::{Blake2_256, StorageHasher};
-
-        tag.as_ref().using_encoded(Blake2_256::hash).to_vec()
+        Self::hash(tag).to_vec()
     }
 
     fn exists<K: AsRef<Vec<u8>>>(tag: K) -> bool {
-        <Metadata<T>>::contains_key(tag.as_ref())
+        <Metadata<T>>::contains_key(Self::hash(tag))
     }
 
     fn tags_of(id: &Self::Hash) -> BTreeMap<Vec<u8>, bool> {
@@ -294,13 +319,13 @@ impl<T: Config> Tags for Pallet<T> {
     }
 
     fn add_tag(id: &Self::Hash, tag: Vec<u8>) -> DispatchResult {
-        <TagsOf<T>>::insert(id, &tag, true);
+        <TagsOf<T>>::insert(id, Self::hash(tag), true);
 
         Ok(())
     }
 
     fn del_tag<K: AsRef<Vec<u8>>>(id: &Self::Hash, tag: K) -> DispatchResult {
-        <TagsOf<T>>::remove(id, tag.as_ref());
+        <TagsOf<T>>::remove(id, Self::hash(tag));
 
         Ok(())
     }
@@ -312,7 +337,7 @@ impl<T: Config> Tags for Pallet<T> {
     }
 
     fn has_tag<K: AsRef<Vec<u8>>>(id: &Self::Hash, tag: K) -> bool {
-        <TagsOf<T>>::contains_key(id, tag.as_ref())
+        <TagsOf<T>>::contains_key(id, Self::hash(tag))
     }
 
     fn personas_of(did: &Self::DecentralizedId) -> BTreeMap<Vec<u8>, i32> {
@@ -320,7 +345,7 @@ impl<T: Config> Tags for Pallet<T> {
     }
 
     fn get_score<K: AsRef<Vec<u8>>>(did: &Self::DecentralizedId, tag: K) -> i32 {
-        <PersonasOf<T>>::get(did, tag.as_ref())
+        <PersonasOf<T>>::get(did, Self::hash(tag))
     }
 
     fn influence<K: AsRef<Vec<u8>>>(
@@ -328,7 +353,7 @@ impl<T: Config> Tags for Pallet<T> {
         tag: K,
         delta: i32,
     ) -> DispatchResult {
-        <PersonasOf<T>>::mutate(&did, tag.as_ref(), |score| {
+        <PersonasOf<T>>::mutate(&did, Self::hash(tag), |score| {
             *score = Self::accrue(*score, delta);
         });
 
@@ -340,7 +365,7 @@ impl<T: Config> Tags for Pallet<T> {
     }
 
     fn get_influence<K: AsRef<Vec<u8>>>(kol: &Self::DecentralizedId, tag: K) -> i32 {
-        <InfluencesOf<T>>::get(kol, tag.as_ref())
+        <InfluencesOf<T>>::get(kol, Self::hash(tag))
     }
 
     fn impact<K: AsRef<Vec<u8>>>(
@@ -348,7 +373,7 @@ impl<T: Config> Tags for Pallet<T> {
         tag: K,
         delta: i32,
     ) -> DispatchResult {
-        <InfluencesOf<T>>::mutate(&kol, tag.as_ref(), |score| {
+        <InfluencesOf<T>>::mutate(&kol, Self::hash(tag), |score| {
             *score = Self::accrue(*score, delta);
         });
 
@@ -93,6 +93,7 @@ impl parami_did::Config for Test {
 
 parameter_types! {
     pub const SubmissionFee: Balance = 1;
+    pub const MaxTagLen: u32 = 32;
 }
 
 impl parami_tag::Config for Test {
@@ -100,6 +101,7 @@ impl parami_tag::Config for Test {
     type Currency = Balances;
     type DecentralizedId = DID;
     type SubmissionFee = SubmissionFee;
+    type MaxTagLen = MaxTagLen;
     type CallOrigin = parami_did::EnsureDid<Self>;
     type ForceOrigin = EnsureRoot<Self::AccountId>;
     type WeightInfo = ();
@@ -1,4 +1,4 @@
-use crate::{mock::*, Error, HashOf, Metadata};
+use crate::{mock::*, types, Error, HashOf, Metadata};
 use frame_support::{assert_noop, assert_ok, traits::Currency};
 use sp_core::sr25519;
 use sp_runtime::DispatchError;
@@ -16,7 +16,7 @@ fn should_create() {
 
         assert_ok!(Tag::create(Origin::signed(alice), tag.clone()));
 
-        let maybe_tag = <Metadata<Test>>::get(&tag);
+        let maybe_tag = <Metadata<Test>>::get(Tag::hash(&tag));
         assert_ne!(maybe_tag, None);
 
         assert_eq!(Balances::free_balance(&alice), 99);
@@ -55,7 +55,7 @@ fn should_fail_when_insufficient() {
             Error::<Test>::InsufficientBalance
         );
 
-        let maybe_tag = <Metadata<Test>>::get(&tag);
+        let maybe_tag = <Metadata<Test>>::get(Tag::hash(&tag));
         assert_eq!(maybe_tag, None);
 
         assert_eq!(Balances::free_balance(&bob), 1);
@@ -72,7 +72,7 @@ fn should_force_create() {
 
         assert_ok!(Tag::force_create(Origin::root(), tag.clone()));
 
-        let maybe_tag = <Metadata<Test>>::get(&tag);
+        let maybe_tag = <Metadata<Test>>::get(Tag::hash(&tag));
         assert_ne!(maybe_tag, None);
 
         assert_eq!(Balances::total_issuance(), 100);
@@ -124,3 +124,56 @@ fn tags_trait() {
         assert_eq!(Tag::get_influence(&did, &tag1), 3);
     });
 }
+
+#[test]
+fn should_fail_when_too_long() {
+    use parami_traits::Tags;
+
+    new_test_ext().execute_with(|| {
+        let alice = sr25519::Public([1; 32]);
+
+        let tag = vec![0u8; 33];
+
+        assert_noop!(
+            Tag::create(Origin::signed(alice), tag.clone()),
+            Error::<Test>::TooLong
+        );
+
+        assert_noop!(
+            Tag::force_create(Origin::root(), tag.clone()),
+            Error::<Test>::TooLong
+        );
+
+        assert_eq!(Tag::exists(&tag), false);
+    });
+}
+
+#[test]
+fn should_keep_tags_created_before_limit() {
+    use parami_traits::Tags;
+
+    new_test_ext().execute_with(|| {
+        let tag = vec![0u8; 33];
+
+        // created before the maximum length was enforced
+        <Metadata<Test>>::insert(
+            Tag::hash(&tag),
+            types::Metadata {
+                creator: DID::default(),
+                created: 0,
+            },
+        );
+
+        assert_eq!(Tag::exists(&tag), true);
+
+        let ad = <HashOf<Test>>::default();
+
+        assert_ok!(Tag::add_tag(&ad, tag.clone()));
+        assert_eq!(Tag::has_tag(&ad, &tag), true);
+
+        let did = DID::from_slice(&[0xff; 20]);
+
+        assert_ok!(Tag::influence(&did, &tag, 5));
+        assert_eq!(Tag::get_score(&did, &tag), 5);
+    });
+}
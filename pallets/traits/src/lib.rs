@@ -1,5 +1,9 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod links;
+
+pub use links::Links;
+
 mod swaps;
 
 pub use swaps::Swaps;
@@ -0,0 +1,21 @@
+use codec::MaxEncodedLen;
+use frame_support::Parameter;
+use sp_runtime::traits::{MaybeSerializeDeserialize, Member};
+
+pub trait Links {
+    type DecentralizedId: Parameter + Member + MaybeSerializeDeserialize;
+
+    type AccountType: Parameter + Member + MaybeSerializeDeserialize + MaxEncodedLen;
+
+    /// Determine if a DID has linked an account of a type
+    ///
+    /// # Arguments
+    ///
+    /// * `did` - the DID
+    /// * `site` - Type of the account
+    ///
+    /// # Returns
+    ///
+    /// `true` if the account is linked, `false` otherwise
+    fn has_link(did: &Self::DecentralizedId, site: &Self::AccountType) -> bool;
+}
@@ -130,6 +130,53 @@ pub trait Swaps {
         keep_alive: bool,
     ) -> Result<(Self::TokenBalance, Self::TokenBalance), DispatchError>;
 
+    /// Get dry-run result of mint_to
+    ///
+    /// # Arguments
+    ///
+    /// * `lp_token_id` - The Liquidity Provider Token ID
+    /// * `currency` - The currency to be involved in the swap
+    /// * `max_tokens` - The maximum amount of tokens to be involved in the swap
+    ///
+    /// # Returns
+    ///
+    /// tuple of (tokens, liquidity)
+    ///
+    /// * `tokens` - The amount of tokens to be involved in the swap
+    /// * `liquidity` - The amount of liquidity to be added
+    fn mint_to_dry(
+        lp_token_id: Self::AssetId,
+        currency: Self::QuoteBalance,
+        max_tokens: Self::TokenBalance,
+    ) -> Result<(Self::TokenBalance, Self::TokenBalance), DispatchError>;
+
+    /// Add Liquidity to an existing Liquidity Provider Token,
+    /// settling its farming reward
+    ///
+    /// # Arguments
+    ///
+    /// * `who` - The account ID of the operator
+    /// * `lp_token_id` - The Liquidity Provider Token ID
+    /// * `currency` - The currency to be involved in the swap
+    /// * `min_liquidity` - The minimum amount of liquidity to be added
+    /// * `max_tokens` - The maximum amount of tokens to be involved in the swap
+    /// * `keep_alive` - Whether to keep the account alive
+    ///
+    /// # Returns
+    ///
+    /// tuple of (tokens, liquidity)
+    ///
+    /// * `tokens` - The amount of tokens involved
+    /// * `liquidity` - The amount of liquidity added
+    fn mint_to(
+        who: Self::AccountId,
+        lp_token_id: Self::AssetId,
+        currency: Self::QuoteBalance,
+        min_liquidity: Self::TokenBalance,
+        max_tokens: Self::TokenBalance,
+        keep_alive: bool,
+    ) -> Result<(Self::TokenBalance, Self::TokenBalance), DispatchError>;
+
     /// Get dry-run result of burn
     ///
     /// # Arguments
@@ -186,6 +233,66 @@ pub trait Swaps {
         DispatchError,
     >;
 
+    /// Get dry-run result of burn_partial
+    ///
+    /// # Arguments
+    ///
+    /// * `lp_token_id` - The Liquidity Provider Token ID
+    /// * `liquidity` - The amount of liquidity to be removed
+    ///
+    /// # Returns
+    ///
+    /// tuple of (token_id, liquidity, tokens, currency)
+    ///
+    /// * `token_id` - The Asset ID
+    /// * `liquidity` - The amount of liquidity removed
+    /// * `tokens` - The amount of tokens returned
+    /// * `currency` - The currency returned
+    fn burn_partial_dry(
+        lp_token_id: Self::AssetId,
+        liquidity: Self::TokenBalance,
+    ) -> Result<
+        (
+            Self::AssetId,
+            Self::TokenBalance,
+            Self::TokenBalance,
+            Self::QuoteBalance,
+        ),
+        DispatchError,
+    >;
+
+    /// Remove part of the Liquidity, settling its farming reward
+    ///
+    /// * `who` - The account ID of the operator
+    /// * `lp_token_id` - The Liquidity Provider Token ID
+    /// * `liquidity` - The amount of liquidity to be removed
+    /// * `min_currency` - The minimum currency to be returned
+    /// * `min_tokens` - The minimum amount of tokens to be returned
+    ///
+    /// # Returns
+    ///
+    /// tuple of (token_id, liquidity, tokens, currency)
+    ///
+    /// * `token_id` - The Asset ID
+    /// * `liquidity` - The amount of liquidity removed
+    /// * `tokens` - The amount of tokens returned
+    /// * `currency` - The currency returned
+    fn burn_partial(
+        who: Self::AccountId,
+        lp_token_id: Self::AssetId,
+        liquidity: Self::TokenBalance,
+        min_currency: Self::QuoteBalance,
+        min_tokens: Self::TokenBalance,
+    ) -> Result<
+        (
+            Self::AssetId,
+            Self::TokenBalance,
+            Self::TokenBalance,
+            Self::QuoteBalance,
+        ),
+        DispatchError,
+    >;
+
     /// Get dry-run result of token_out
     ///
     /// # Arguments
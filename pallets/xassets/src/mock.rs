@@ -119,6 +119,8 @@ parameter_types! {
     pub const MockChainId: ChainId = 5;
     pub const ChainBridgePalletId: PalletId = PalletId(*b"chnbrdge");
     pub const ProposalLifetime: u64 = 10;
+    pub const MaxRelayers: u32 = 8;
+    pub const MaxResourceLen: u32 = 64;
 }
 
 impl parami_chainbridge::Config for MockRuntime {
@@ -128,6 +130,8 @@ impl parami_chainbridge::Config for MockRuntime {
     type PalletId = ChainBridgePalletId;
     type AdminOrigin = EnsureRoot<Self::AccountId>;
     type ProposalLifetime = ProposalLifetime;
+    type MaxRelayers = MaxRelayers;
+    type MaxResourceLen = MaxResourceLen;
     type WeightInfo = ();
 }
 
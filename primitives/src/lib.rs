@@ -174,6 +174,20 @@ pub mod names {
     pub const CHAIN_BRIDGE: &[u8; 8] = b"chnbrdge";
 }
 
+pub mod derive {
+    use codec::Encode;
+    use sp_runtime::traits::Hash;
+
+    /// Derive an ID of an account from a domain separator and a nonce.
+    ///
+    /// The domain separator (usually the pallet id) keeps IDs of different kinds apart,
+    /// while the nonce, which MUST be increased per derivation, keeps IDs of the same
+    /// account apart, even within one block.
+    pub fn id<H: Hash, A: Encode>(domain: &[u8], who: &A, nonce: u64) -> H::Output {
+        (domain, who, nonce).using_encoded(H::hash)
+    }
+}
+
 pub const fn deposit(items: u32, bytes: u32) -> Balance {
     use constants::CENTS;
 
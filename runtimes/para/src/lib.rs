@@ -214,10 +214,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("parami"),
     impl_name: create_runtime_str!("parami-node"),
     authoring_version: 20,
-    spec_version: 320,
+    spec_version: 321,
     impl_version: 0,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 2,
+    transaction_version: 3,
 };
 
 /// The version information used to identify this runtime when compiled natively.
@@ -1299,7 +1299,21 @@ parameter_types! {
     pub const AdPalletId: PalletId = PalletId(*names::AD);
     pub const AdvertiserMinimumFee: Balance = 50 * MILLICENTS;
     pub const PayoutBase: Balance = 1 * CENTS;
+    pub const MaxMetadataLen: u32 = 512;
     pub const SlotLifetime: BlockNumber = 3 * DAYS;
+    pub const MaxSlotsPerKol: u32 = 10;
+    pub const MaxAdsPerAdvertiser: u32 = 100;
+    pub const MaxSlotsPerAd: u32 = 200;
+    pub const MaxBidsPerRound: u32 = 10;
+    pub const MaxBatchSize: u32 = 100;
+    pub const MaxExpiredPerBlock: u32 = 100;
+    pub const MaxExpiryDelay: u32 = 100;
+    pub const ExtendSlotsOnResume: bool = true;
+    pub const MaxReferralLevels: u32 = 2;
+    pub const MaxTargetingLen: u32 = 16;
+    pub const MaxSlotConfigLen: u32 = 64;
+    pub const KolShare: Percent = Percent::from_percent(5);
+    pub const HolderShare: Percent = Percent::from_percent(0);
 }
 
 impl parami_ad::Config for Runtime {
@@ -1308,9 +1322,28 @@ impl parami_ad::Config for Runtime {
     type MinimumFeeBalance = AdvertiserMinimumFee;
     type PalletId = AdPalletId;
     type PayoutBase = PayoutBase;
+    type MaxMetadataLen = MaxMetadataLen;
     type SlotLifetime = SlotLifetime;
+    type MaxSlotsPerKol = MaxSlotsPerKol;
+    type MaxAdsPerAdvertiser = MaxAdsPerAdvertiser;
+    type MaxSlotsPerAd = MaxSlotsPerAd;
+    type MaxBidsPerRound = MaxBidsPerRound;
+    type MaxBatchSize = MaxBatchSize;
+    type MaxExpiredPerBlock = MaxExpiredPerBlock;
+    type MaxExpiryDelay = MaxExpiryDelay;
+    type ExtendSlotsOnResume = ExtendSlotsOnResume;
+    type MaxReferralLevels = MaxReferralLevels;
+    type MaxTagLen = MaxTagLen;
+    type MaxTargetingLen = MaxTargetingLen;
+    type MaxSlotConfigLen = MaxSlotConfigLen;
+    type KolShare = KolShare;
+    type HolderShare = HolderShare;
+    type BidPolicy = ();
     type Swaps = Swap;
     type Tags = Tag;
+    type Links = Linker;
+    type Signature = Signature;
+    type Signer = <Signature as Verify>::Signer;
     type CallOrigin = parami_advertiser::EnsureAdvertiser<Self>;
     type ForceOrigin = EnsureRootOrHalfCouncil;
     type WeightInfo = ();
@@ -1334,6 +1367,8 @@ parameter_types! {
     pub const ChainBridgePalletId: PalletId = PalletId(*names::CHAIN_BRIDGE);
     pub const ParamiChainId: parami_chainbridge::ChainId = 233;
     pub const ProposalLifetime: BlockNumber = 50;
+    pub const MaxRelayers: u32 = 16;
+    pub const MaxResourceLen: u32 = 64;
 }
 
 impl parami_chainbridge::Config for Runtime {
@@ -1343,6 +1378,8 @@ impl parami_chainbridge::Config for Runtime {
     type ChainId = ParamiChainId;
     type PalletId = ChainBridgePalletId;
     type ProposalLifetime = ProposalLifetime;
+    type MaxRelayers = MaxRelayers;
+    type MaxResourceLen = MaxResourceLen;
     type WeightInfo = parami_chainbridge::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1382,6 +1419,7 @@ impl parami_did::Config for Runtime {
 
 parameter_types! {
     pub const LinkerPalletId: PalletId = PalletId(*names::LINKER);
+    pub const MaxLinkLen: u32 = 256;
     pub const PendingLifetime: BlockNumber = 5;
     pub const RegistrarMinimumDeposit: Balance = 1_000_000 * DOLLARS;
     pub const UnsignedPriority: TransactionPriority = 3;
@@ -1390,6 +1428,7 @@ parameter_types! {
 impl parami_linker::Config for Runtime {
     type Event = Event;
     type ForceOrigin = EnsureRootOrHalfCouncil;
+    type MaxLinkLen = MaxLinkLen;
     type MinimumDeposit = MinimumDeposit;
     type PalletId = LinkerPalletId;
     type PendingLifetime = PendingLifetime;
@@ -1431,7 +1470,10 @@ impl parami_nft::Config for Runtime {
 
 parameter_types! {
     pub const InitialFarmingReward: Balance = 100 * DOLLARS;
+    pub const SwapDefaultFee: Permill = Permill::from_parts(3_000); // 0.3%
+    pub const SwapMaxPathLen: u32 = 8;
     pub const SwapPalletId: PalletId = PalletId(*names::SWAP);
+    pub const SwapProtocolFeeShare: Permill = Permill::zero();
 }
 
 impl parami_swap::Config for Runtime {
@@ -1439,13 +1481,19 @@ impl parami_swap::Config for Runtime {
     type AssetId = AssetId;
     type Assets = Assets;
     type Currency = Balances;
+    type DefaultFee = SwapDefaultFee;
     type FarmingCurve = LinearFarmingCurve<Runtime, InitialFarmingReward, InitialMintingValueBase>;
+    type ForceOrigin = EnsureRootOrHalfCouncil;
+    type MaxPathLen = SwapMaxPathLen;
     type PalletId = SwapPalletId;
+    type ProtocolFee = Treasury;
+    type ProtocolFeeShare = SwapProtocolFeeShare;
     type WeightInfo = parami_swap::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
     pub const SubmissionFee: Balance = 1 * DOLLARS;
+    pub const MaxTagLen: u32 = 64;
 }
 
 impl parami_tag::Config for Runtime {
@@ -1453,6 +1501,7 @@ impl parami_tag::Config for Runtime {
     type Currency = Balances;
     type DecentralizedId = <Self as parami_did::Config>::DecentralizedId;
     type SubmissionFee = SubmissionFee;
+    type MaxTagLen = MaxTagLen;
     type CallOrigin = parami_advertiser::EnsureAdvertiser<Self>;
     type ForceOrigin = EnsureRootOrHalfCouncil;
     type WeightInfo = parami_tag::weights::SubstrateWeight<Runtime>;
@@ -1697,6 +1746,36 @@ impl_runtime_apis! {
         }
     }
 
+    impl parami_ad_rpc_runtime_api::AdRuntimeApi<Block, Hash, DecentralizedId, Balance> for Runtime {
+        fn dryly_pay(
+            ad: Hash,
+            kol: DecentralizedId,
+            visitor: DecentralizedId,
+            referer: Option<DecentralizedId>,
+        ) -> Result<(
+            u32,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+            bool,
+        ), DispatchError> {
+            Ad::dryly_pay(ad, kol, visitor, referer)
+                .map(|(score, amount, award, swapped)| (score, amount.into(), award.into(), swapped))
+        }
+
+        fn get_stats(ad: Hash) -> Result<parami_ad_rpc_runtime_api::StatsTuple<Balance>, DispatchError> {
+            Ad::get_stats(ad)
+                .map(|s| (s.paid.into(), s.visitors, s.awarded.into(), s.tokens.into(), s.spent.into()))
+        }
+
+        fn get_slot_stats(
+            ad: Hash,
+            kol: DecentralizedId,
+        ) -> Result<parami_ad_rpc_runtime_api::StatsTuple<Balance>, DispatchError> {
+            Ad::get_slot_stats(ad, kol)
+                .map(|s| (s.paid.into(), s.visitors, s.awarded.into(), s.tokens.into(), s.spent.into()))
+        }
+    }
+
     impl parami_swap_rpc_runtime_api::SwapRuntimeApi<Block, AssetId, Balance> for Runtime {
         fn dryly_add_liquidity(
             token_id: AssetId,
@@ -1721,6 +1800,32 @@ impl_runtime_apis! {
             })
         }
 
+        fn dryly_add_liquidity_to(
+            lp_token_id: AssetId,
+            currency: BalanceWrapper<Balance>,
+            max_tokens: BalanceWrapper<Balance>,
+        ) -> Result<(
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+        ), DispatchError> {
+            Swap::mint_to_dry(lp_token_id, currency.into(), max_tokens.into())
+                .map(|(tokens, liquidity)| (tokens.into(), liquidity.into()))
+        }
+
+        fn dryly_remove_liquidity_partial(
+            lp_token_id: AssetId,
+            liquidity: BalanceWrapper<Balance>,
+        ) -> Result<(
+            AssetId,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+        ), DispatchError> {
+            Swap::burn_partial_dry(lp_token_id, liquidity.into()).map(|(token_id, liquidity, tokens, currency)| {
+                (token_id, liquidity.into(), tokens.into(), currency.into())
+            })
+        }
+
         fn dryly_buy_tokens(
             token_id: AssetId,
             tokens: BalanceWrapper<Balance>,
@@ -1753,6 +1858,22 @@ impl_runtime_apis! {
                 .map(|tokens| tokens.into())
         }
 
+        fn dryly_swap_tokens(
+            path: Vec<AssetId>,
+            amount_in: BalanceWrapper<Balance>,
+        ) -> Result<BalanceWrapper<Balance>, DispatchError> {
+            Swap::swap_in_dry(&path, amount_in.into())
+                .map(|tokens| tokens.into())
+        }
+
+        fn dryly_swap_tokens_for_exact(
+            path: Vec<AssetId>,
+            amount_out: BalanceWrapper<Balance>,
+        ) -> Result<BalanceWrapper<Balance>, DispatchError> {
+            Swap::swap_out_dry(&path, amount_out.into())
+                .map(|tokens| tokens.into())
+        }
+
         fn calculate_reward(
             lp_token_id: AssetId,
         ) -> Result<BalanceWrapper<Balance>, DispatchError> {